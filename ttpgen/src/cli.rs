@@ -1,12 +1,40 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::data_set::CapacityWindow;
+use crate::naming::{NameTemplate, DEFAULT_NAME_TEMPLATE};
+use crate::solution::{ConstructionMethod, Improvement};
+use crate::violation_report::ConstraintFamily;
 
 /// Command-line interface for TTP Solution Generator.
-#[derive(Parser, Debug)]
+#[derive(Parser, Debug, Clone)]
 #[command(name = "ttpgen", version = "1.01", about = "Generates TTP schedules")]
+#[command(subcommand_negates_reqs = true)]
 pub struct Cli {
+    /// Optional subcommand running a single phase of the framework
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Path to the XML instance file
-    #[arg(long = "input")]
-    pub input: String,
+    #[arg(long = "input", required_unless_present = "input_list")]
+    pub input: Option<String>,
+
+    /// Run the framework on every instance listed in a file (one path per line, with optional seed=, permutations= and name= overrides)
+    #[arg(long = "input-list", value_name = "FILE", conflicts_with_all = ["input", "permutations_file", "explain", "compare_methods"])]
+    pub input_list: Option<String>,
+
+    /// Path of the combined summary csv written by `--input-list`
+    #[arg(long = "input-list-csv", value_name = "PATH", default_value = "input_list_summary.csv")]
+    pub input_list_csv: String,
+
+    /// Override the instance name used to label outputs (defaults to the parsed name, or the file name if empty)
+    #[arg(long = "name")]
+    pub name: Option<String>,
+
+    /// Load the permutations from a file written by the `permutations` subcommand
+    #[arg(long = "permutations-file")]
+    pub permutations_file: Option<String>,
 
     /// Directory to save generated solutions
     #[arg(long = "output-solutions", default_value = "solutions_output")]
@@ -16,19 +44,406 @@ pub struct Cli {
     #[arg(long = "output-permutations", default_value = "perms_output")]
     pub output_permutations: String,
 
+    /// Directory to save statistics outputs (histograms)
+    #[arg(long = "output-statistics", default_value = ".")]
+    pub output_statistics: String,
+
     /// Number of random permutations to generate
     #[arg(long = "permutations", default_value_t = 10)]
     pub permutations: i32,
 
-    /// Random seed for reproducibility
-    #[arg(long = "seed", default_value_t = 42)]
+    /// Random seed for reproducibility, or `random` to draw one from system entropy
+    #[arg(long = "seed", default_value = "42", value_parser = parse_seed)]
     pub seed: u64,
 
+    /// Drop the permutations that generate the same pool as an earlier one before generating
+    #[arg(long = "dedup-perms", default_value_t = false)]
+    pub dedup_perms: bool,
+
     /// Disable saving to disk
     #[arg(long = "save", default_value_t = false)]
     pub save: bool,
 
+    /// Save the solutions as compact json (no indentation)
+    #[arg(long = "compact-json", default_value_t = false)]
+    pub compact_json: bool,
+
+    /// Save the solutions by batches of K in `chunk_<n>.json` files instead of one file each
+    #[arg(long = "chunk-save", value_name = "K", value_parser = clap::value_parser!(u64).range(1..), requires = "save",
+          conflicts_with_all = ["resume", "sort_output", "keep_infeasible_separate"])]
+    pub chunk_save: Option<u64>,
+
+    /// Template of the output file names, with {name} (default name), {instance}, {seed}, {id} and {method}
+    #[arg(long = "name-template", value_name = "TEMPLATE", default_value = DEFAULT_NAME_TEMPLATE, value_parser = parse_name_template)]
+    pub name_template: String,
+
+    /// Free-form label written into the manifest, the results csv and the saved solutions
+    #[arg(long = "tag", value_parser = parse_tag)]
+    pub tag: Option<String>,
+
+    /// Shuffle the (direction, fixed team) order of each permutation, reproducibly from the seed
+    #[arg(long = "shuffle-within", default_value_t = false)]
+    pub shuffle_within: bool,
+
+    /// Break the ties of the construction methods randomly, reproducibly from the seed
+    #[arg(long = "random-tie-breaks", default_value_t = false)]
+    pub random_tie_breaks: bool,
+
+    /// Build only K (direction, fixed team) pairs of each permutation, sampled from the seed
+    #[arg(long = "limit-per-permutation", value_name = "K", value_parser = clap::value_parser!(u64).range(1..))]
+    pub limit_per_permutation: Option<u64>,
+
+    /// Build only the solutions of the permutations at these positions (from 0, e.g. 3,7,12)
+    #[arg(long = "permutation-indices", value_name = "INDICES", value_delimiter = ',')]
+    pub permutation_indices: Vec<usize>,
+
+    /// Also log the generation progress every N solutions (the bar is not written to the log file)
+    #[arg(long = "progress-to-log", value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub progress_to_log: Option<u64>,
+
+    /// Stop the generation after SECS seconds and continue with the solutions generated so far
+    #[arg(long = "timeout", value_name = "SECS", value_parser = parse_timeout, conflicts_with = "self_check")]
+    pub timeout: Option<f64>,
+
+    /// Log an estimate of the remaining time after the first solutions
+    #[arg(long = "eta", default_value_t = false)]
+    pub eta: bool,
+
+    /// Skip solutions whose file already exists in the output directory
+    #[arg(long = "resume", default_value_t = false)]
+    pub resume: bool,
+
+    /// Rename the saved solutions by rank of the given key (`rank_0001_dist_<d>.json`, ...)
+    #[arg(long = "sort-output", value_parser = ["distance", "penalty", "breaks", "weighted"], requires = "save", conflicts_with = "resume")]
+    pub sort_output: Option<String>,
+
+    /// Save feasible and infeasible solutions in the `feasible/` and `infeasible/` subdirectories
+    #[arg(long = "keep-infeasible-separate", default_value_t = false, requires = "save", conflicts_with_all = ["only_distance", "sort_output"])]
+    pub keep_infeasible_separate: bool,
+
+    /// Skip the listed constraint families in the evaluation (CA, SE, RR)
+    #[arg(long = "disable-constraints", value_delimiter = ',')]
+    pub disable_constraints: Vec<ConstraintFamily>,
+
+    /// Number of times each pair of teams must meet (2 for a double round-robin)
+    #[arg(long = "max-meetings", default_value_t = 2, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_meetings: u64,
+
+    /// Weight each separation violation by its distance to the bound in the penalty
+    #[arg(long = "graduated-penalties", default_value_t = false)]
+    pub graduated_penalties: bool,
+
+    /// Evaluate CA2 constraints as a limit on the games between their two team groups
+    #[arg(long = "ca2-group-pairs", default_value_t = false)]
+    pub ca2_group_pairs: bool,
+
+    /// Count the capacity windows in games or slots for every constraint, instead of following their mode2
+    #[arg(long = "capacity-window", value_name = "UNIT")]
+    pub capacity_window: Option<CapacityWindow>,
+
+    /// Reduce the instance to these teams (e.g. 0,1,2,3), with their distances and constraints
+    #[arg(long = "subset-teams", value_name = "IDS", value_delimiter = ',')]
+    pub subset_teams: Vec<i32>,
+
+    /// Generate a single round-robin (the first n-1 rounds), each pair of teams meets once
+    #[arg(long = "single-rr", default_value_t = false, conflicts_with_all = ["max_meetings", "eval_slots"])]
+    pub single_rr: bool,
+
+    /// Exit with an error at the end of the run if any warning was emitted
+    #[arg(long = "fail-on-warning", default_value_t = false)]
+    pub fail_on_warning: bool,
+
+    /// Abort if the distance of some pair of teams is missing from the instance or negative
+    #[arg(long = "strict", default_value_t = false)]
+    pub strict: bool,
+
+    /// Abort if the instance declares constraint types that are not supported, or constraints with invalid bounds
+    #[arg(long = "strict-constraints", default_value_t = false)]
+    pub strict_constraints: bool,
+
+    /// Export each solution as an opponents grid csv (requires --save)
+    #[arg(long = "opponents-csv", default_value_t = false, requires = "save")]
+    pub opponents_csv: bool,
+
+    /// Write the home/away of each game in the sign of the opponents grid cells, so the files can be read back by --evaluate-csv
+    #[arg(long = "signed-opponents", default_value_t = false, requires = "opponents_csv")]
+    pub signed_opponents: bool,
+
+    /// Export the travel of each team entering each slot of each solution as a csv (requires --save)
+    #[arg(long = "heatmap-csv", default_value_t = false, requires = "save")]
+    pub heatmap_csv: bool,
+
+    /// Export each solution as a text listing of its rounds with the team names (requires --save)
+    #[arg(long = "round-listing", default_value_t = false, requires = "save")]
+    pub round_listing: bool,
+
+    /// Export each solution as a RobinX solution XML file (requires --save)
+    #[arg(long = "xml-solutions", default_value_t = false, requires = "save")]
+    pub xml_solutions: bool,
+
+    /// Write the manifest, statistics, best solution and violation summary of the run to a json file
+    #[arg(long = "run-json", value_name = "PATH")]
+    pub run_json: Option<String>,
+
+    /// Compare the distances of the run against a run saved by --run-json
+    #[arg(long = "baseline", value_name = "RUN_JSON")]
+    pub baseline: Option<String>,
+
+    /// Write the evaluation of every solution to a results csv file
+    #[arg(long = "results-csv")]
+    pub results_csv: Option<String>,
+
+    /// Append to an existing results csv instead of overwriting it
+    #[arg(long = "append-results", default_value_t = false)]
+    pub append_results: bool,
+
+    /// Weight of the penalty term in the weighted objective
+    #[arg(long = "lambda", default_value_t = 0.0)]
+    pub lambda: f64,
+
+    /// Weight of the travel imbalance term in the weighted objective
+    #[arg(long = "mu", default_value_t = 0.0)]
+    pub mu: f64,
+
+    /// Only evaluate the traveling distance, skipping the constraint checks
+    #[arg(long = "only-distance", default_value_t = false, conflicts_with = "stats_feasible_only")]
+    pub only_distance: bool,
+
+    /// Compute statistics only over feasible solutions
+    #[arg(long = "stats-feasible-only", default_value_t = false)]
+    pub stats_feasible_only: bool,
+
+    /// Run the pipeline once per construction method (e.g. `florian,circle`) and write a comparison csv
+    #[arg(long = "compare-methods", value_delimiter = ',')]
+    pub compare_methods: Vec<ConstructionMethod>,
+
+    /// Path of the comparison csv written by `--compare-methods`
+    #[arg(long = "compare-csv", default_value = "comparison.csv")]
+    pub compare_csv: String,
+
+    /// Improve each schedule after its construction, in the listed order (orientation, round-swaps, home-away)
+    #[arg(long = "improve", value_delimiter = ',')]
+    pub improve: Vec<Improvement>,
+
+    /// Evaluate travel and constraints over the first K slots of each solution only
+    #[arg(long = "eval-slots", value_parser = clap::value_parser!(u64).range(1..))]
+    pub eval_slots: Option<u64>,
+
+    /// Compute streaming statistics (mean, variance, min-max) without median, quartiles or histogram
+    #[arg(long = "approx-stats", default_value_t = false)]
+    pub approx_stats: bool,
+
+    /// Also write the summary statistics as tables in the given formats (markdown, latex)
+    #[arg(long = "stats-output", value_delimiter = ',', value_parser = ["markdown", "latex"], conflicts_with = "approx_stats")]
+    pub stats_output: Vec<String>,
+
+    /// Split each bin of the distance histogram into feasible and infeasible segments
+    #[arg(long = "histogram-feasibility", default_value_t = false, conflicts_with = "only_distance")]
+    pub histogram_feasibility: bool,
+
+    /// Only log count, feasible count, min, max and mean (no variance, quartiles or histogram)
+    #[arg(long = "count-only", default_value_t = false, conflicts_with_all = ["approx_stats", "stats_output"])]
+    pub count_only: bool,
+
+    /// Compute statistics over breaks and longest trips of the pool
+    #[arg(long = "analyze-breaks", default_value_t = false)]
+    pub analyze_breaks: bool,
+
+    /// Number of decimal places of the statistics in the log, tables and comparison csv
+    #[arg(long = "stats-precision", value_name = "N", default_value_t = 2, value_parser = clap::value_parser!(u64).range(..=17))]
+    pub stats_precision: u64,
+
+    /// Compute per-family statistics of the constraint violations of the pool
+    #[arg(long = "analyze-constraints", default_value_t = false)]
+    pub analyze_constraints: bool,
+
+    /// Log the mean pairwise Hamming distance of the pool (over at most 1000 sampled pairs)
+    #[arg(long = "diversity", default_value_t = false)]
+    pub diversity: bool,
+
+    /// Run the generation twice with the same seed and exit with an error if the results differ
+    #[arg(long = "self-check", default_value_t = false, conflicts_with = "resume")]
+    pub self_check: bool,
+
+    /// Print the details of a single solution (loaded if saved, regenerated otherwise) and exit
+    #[arg(long = "explain", value_name = "ID")]
+    pub explain: Option<i32>,
+
+    /// Relabel the teams of the explained solution, team t becoming team PERM[t] (e.g. 1,0,3,2)
+    #[arg(long = "relabel", value_name = "PERM", value_delimiter = ',', requires = "explain")]
+    pub relabel: Vec<i32>,
+
+    /// Print the details of a schedule loaded from a signed opponents csv (see --signed-opponents) and exit
+    #[arg(long = "evaluate-csv", value_name = "PATH", conflicts_with = "explain")]
+    pub evaluate_csv: Option<String>,
+
+    /// Log the full violation report of the best solution
+    #[arg(long = "verbose-eval", default_value_t = false)]
+    pub verbose_eval: bool,
+
+    /// Do not log the schedule and evaluation of each solution, only the phases and the summary
+    #[arg(long = "summary-only", default_value_t = false)]
+    pub summary_only: bool,
+
+    /// Log the total time and number of calls of the construction, evaluation and saving functions at the end of the run
+    #[arg(long = "profile", default_value_t = false)]
+    pub profile: bool,
+
     /// Enable or disable logging
     #[arg(long = "log", default_value_t = false)]
     pub log_enabled: bool,
 }
+
+/// Subcommands running a single phase of the framework.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Generate only the permutations file and exit
+    Permutations {
+        /// Path to the XML instance file
+        #[arg(long = "input")]
+        input: String,
+
+        /// Number of random permutations to generate
+        #[arg(long = "count", default_value_t = 10)]
+        count: i32,
+
+        /// Random seed for reproducibility, or `random` to draw one from system entropy
+        #[arg(long = "seed", default_value = "42", value_parser = parse_seed)]
+        seed: u64,
+
+        /// Path of the permutations file to write
+        #[arg(long = "out", default_value = "permutation.json")]
+        out: String,
+    },
+
+    /// Evaluate a directory of solution files against an instance
+    EvaluateDir {
+        /// Path to the XML instance file
+        #[arg(long = "instance")]
+        instance: String,
+
+        /// Directory containing the `solution_<id>.json` files
+        #[arg(long = "solutions-dir")]
+        solutions_dir: String,
+
+        /// Path of the results csv file to write
+        #[arg(long = "results-csv", default_value = "results.csv")]
+        results_csv: String,
+
+        /// Directory to save statistics outputs (histograms)
+        #[arg(long = "output-statistics", default_value = ".")]
+        output_statistics: String,
+    },
+
+    /// Merge the distance statistics of several `--run-json` files
+    Merge {
+        /// Comma-separated paths of the run json files to merge
+        #[arg(long = "inputs", value_delimiter = ',', required = true)]
+        inputs: Vec<String>,
+
+        /// Path of the json file receiving the merged statistics
+        #[arg(long = "out", default_value = "merged.json")]
+        out: String,
+
+        /// Directory to save the histogram of the merged distances
+        #[arg(long = "output-statistics", default_value = ".")]
+        output_statistics: String,
+    },
+}
+
+/// Parses a `--seed` value: a number, or `random` for a seed drawn from system entropy.
+///
+/// The drawn seed is stored as a plain number, so it is logged with the arguments and
+/// recorded in the manifest and the run can be reproduced with `--seed <value>`.
+///
+/// # Arguments
+/// * `value` - The command-line value.
+///
+/// # Returns
+/// A `Result` with the seed, or an error message if `value` is neither `random` nor a `u64`.
+fn parse_seed(value: &str) -> Result<u64, String> {
+    if value.eq_ignore_ascii_case("random") {
+        return Ok(StdRng::from_os_rng().random());
+    }
+    value
+        .parse()
+        .map_err(|_| format!("invalid seed '{}' (expected a number or 'random')", value))
+}
+
+/// Parses a `--tag` value, which is written as is in a csv column.
+///
+/// # Arguments
+/// * `value` - The command-line value.
+///
+/// # Returns
+/// A `Result` with the tag, or an error message if it contains a comma or a line break.
+fn parse_tag(value: &str) -> Result<String, String> {
+    if value.contains([',', '\n', '\r']) {
+        return Err(format!("invalid tag '{}' (commas and line breaks are not allowed)", value));
+    }
+    Ok(value.to_string())
+}
+
+/// Parses a `--name-template` value (see `NameTemplate::validate`).
+///
+/// # Arguments
+/// * `value` - The command-line value.
+///
+/// # Returns
+/// A `Result` with the template, or an error message if it uses an unknown placeholder,
+/// has unbalanced braces or does not contain `{name}`.
+fn parse_name_template(value: &str) -> Result<String, String> {
+    NameTemplate::validate(value)?;
+    Ok(value.to_string())
+}
+
+/// Parses a `--timeout` value, a positive number of seconds.
+///
+/// # Arguments
+/// * `value` - The command-line value.
+///
+/// # Returns
+/// A `Result` with the number of seconds, or an error message if it is not a positive number.
+fn parse_timeout(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(seconds) if seconds.is_finite() && seconds > 0.0 => Ok(seconds),
+        _ => Err(format!("invalid timeout '{}' (expected a positive number of seconds)", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn random_seeds_differ_between_runs() {
+        let seed = |value: &str| Cli::try_parse_from(["ttpgen", "--input", "NL8.xml", "--seed", value]).unwrap().seed;
+
+        assert_ne!(seed("random"), seed("random"));
+        assert_eq!(seed("7"), 7);
+        assert_eq!(Cli::try_parse_from(["ttpgen", "--input", "NL8.xml"]).unwrap().seed, 42);
+        assert!(parse_seed("seven").is_err());
+    }
+
+    #[test]
+    fn fail_on_warning_is_off_unless_requested() {
+        let default = Cli::try_parse_from(["ttpgen", "--input", "NL8.xml"]).unwrap();
+        assert!(!default.fail_on_warning);
+
+        let strict = Cli::try_parse_from(["ttpgen", "--input", "NL8.xml", "--fail-on-warning"]).unwrap();
+        assert!(strict.fail_on_warning);
+    }
+
+    #[test]
+    fn the_solution_exports_require_save() {
+        let requires_save = |flag: &str| {
+            assert!(Cli::try_parse_from(["ttpgen", "--input", "NL8.xml", flag]).is_err(), "{} without --save", flag);
+            assert!(Cli::try_parse_from(["ttpgen", "--input", "NL8.xml", "--save", flag]).is_ok(), "{} with --save", flag);
+        };
+
+        requires_save("--opponents-csv");
+        requires_save("--heatmap-csv");
+        requires_save("--round-listing");
+        requires_save("--xml-solutions");
+    }
+}
@@ -1,10 +1,20 @@
+// Std library
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+// External crates
 use serde::{Serialize, Deserialize};
+use sha2::{Digest, Sha256};
+
+// Local modules
+use crate::error::TtpError;
 
 /// All raw data parsed from a TTP XML instance.
 ///
 /// Contains all information necessary to generate solutions,
 /// including teams, slots, distances, and constraints.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Rawdata {
     /// Name of the instance.
     pub instance_name: String,
@@ -18,6 +28,507 @@ pub struct Rawdata {
     pub capacity_constraints: Vec<CapacityConstraints>,
     /// Separation constraints for the tournament.
     pub separation_constraints: Vec<SeparationConstraints>,
+    /// Tag names of the constraints found in the instance but not supported (ignored).
+    #[serde(default)]
+    pub unsupported_constraints: Vec<String>,
+    /// Metadata and format of the instance.
+    #[serde(default)]
+    pub metadata: Metadata,
+    /// Number of teams declared by the instance (`<Teams number="..">`), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub declared_teams: Option<usize>,
+    /// Number of slots declared by the instance (`<Slots number="..">`), if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub declared_slots: Option<usize>,
+}
+
+/// Unit of the sliding window of the capacity constraints (`--capacity-window`).
+///
+/// * `Games` - The window covers `c_intp` consecutive games of the team, the slots where it
+///   does not play are skipped (RobinX `mode2="GAMES"`).
+/// * `Slots` - The window covers `c_intp` consecutive slots, the slots where the team does not
+///   play included (RobinX `mode2="SLOTS"`).
+///
+/// # Example
+/// ```
+/// let window: CapacityWindow = "slots".parse().unwrap();
+/// assert_eq!(window, CapacityWindow::Slots);
+/// ```
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum CapacityWindow {
+    Games,
+    Slots,
+}
+
+impl FromStr for CapacityWindow {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "games" => Ok(CapacityWindow::Games),
+            "slots" => Ok(CapacityWindow::Slots),
+            other => Err(format!("Unknown capacity window '{}' (expected games or slots)", other)),
+        }
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Metadata and format flags of a RobinX instance.
+///
+/// They are kept as found in the XML so the instance can be described (and written back)
+/// faithfully, even if not all of them affect the evaluation.
+///
+/// # Fields
+/// * `phased` - True if the tournament is phased (`<gameMode>P</gameMode>`): every team meets
+///   every other team once in each half.
+/// * `objective` - The objective function (`<Objective>`, e.g. `"TR"` for total travel).
+/// * `game_mode` - The compactness of the format (`<compactness>`): `"C"` for a compact
+///   (time-constrained) schedule, `"R"` for a relaxed one.
+/// * `data_type` - The data type of the instance (`<DataType>`, e.g. `"A"` for artificial).
+/// * `contributor` - The contributor of the instance (`<Contributor>`).
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Metadata {
+    pub phased: bool,
+    pub objective: String,
+    pub game_mode: String,
+    pub data_type: String,
+    pub contributor: String,
+}
+
+impl Metadata {
+    /// Returns `true` if the instance is time-constrained (compact), i.e. every team plays
+    /// in every slot.
+    pub fn is_time_constrained(&self) -> bool {
+        self.game_mode.eq_ignore_ascii_case("C")
+    }
+}
+
+impl Rawdata {
+    /// Computes a deterministic fingerprint of the instance content.
+    ///
+    /// The instance is first canonicalized (teams, slots, distances and constraints
+    /// are sorted) so that the fingerprint does not depend on the order of the elements
+    /// in the XML file. The runtime settings of the evaluation are not part of `Rawdata`
+    /// (see `EvaluationOptions`), so they never change the fingerprint. The instance name
+    /// is left out: it labels the outputs (and can be overridden with `--name`) but does
+    /// not change the content. The canonical instance is serialized to json and hashed
+    /// with SHA-256.
+    ///
+    /// # Returns
+    /// A `String` with the lowercase hexadecimal SHA-256 digest of the instance.
+    ///
+    /// # Example
+    /// ```
+    /// let raw_data = XmlManager::read_xml("NL8.xml");
+    /// println!("Instance fingerprint: {}", raw_data.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> String {
+        let mut canonical = self.clone();
+        canonical.instance_name.clear();
+        canonical.teams.sort();
+        canonical.slots.sort();
+        canonical.distances.sort_by_key(|d| (d.team1, d.team2, d.dist));
+        canonical.capacity_constraints.sort();
+        canonical.separation_constraints.sort();
+
+        let bytes = serde_json::to_vec(&canonical).expect("Error serializing instance");
+        format!("{:x}", Sha256::digest(&bytes))
+    }
+
+    /// Sets the name used to label the outputs (statistics, manifest, results) of the instance.
+    ///
+    /// The parsed `instance_name` is overwritten by `name` when given. Otherwise, if the parsed
+    /// name is empty, it is derived from the file name of `input` (without extension).
+    /// The name is not part of the fingerprint (see `fingerprint`), so resolving or overriding
+    /// it never changes the fingerprint recorded in the manifest.
+    ///
+    /// # Arguments
+    /// * `name` - An optional name overriding the parsed one.
+    /// * `input` - The path of the XML instance file.
+    ///
+    /// # Example
+    /// ```
+    /// let mut raw_data = XmlManager::read_xml("instances/NL8.xml");
+    /// raw_data.resolve_instance_name(None, "instances/NL8.xml");
+    /// ```
+    pub fn resolve_instance_name(&mut self, name: Option<&str>, input: &str) {
+        if let Some(name) = name {
+            self.instance_name = name.to_string();
+        } else if self.instance_name.trim().is_empty() {
+            self.instance_name = Path::new(input)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+        }
+    }
+
+    /// Returns the number of teams of the instance.
+    pub fn n_teams(&self) -> usize {
+        self.teams.len()
+    }
+
+    /// Cross-checks the declared numbers of teams and slots against the parsed elements.
+    ///
+    /// A mismatch usually means a truncated or malformed instance file. Counts that are not
+    /// declared are not checked.
+    ///
+    /// # Returns
+    /// `Ok(())` if every declared count matches, otherwise a `TtpError::Validation` describing
+    /// the first mismatch.
+    ///
+    /// # Example
+    /// ```
+    /// let mut raw_data = XmlManager::read_xml("NL8.xml").unwrap();
+    /// raw_data.declared_teams = Some(10);
+    /// assert!(raw_data.check_declared_counts().is_err());
+    /// ```
+    pub fn check_declared_counts(&self) -> Result<(), TtpError> {
+        for (element, declared, parsed) in [
+            ("teams", self.declared_teams, self.n_teams()),
+            ("slots", self.declared_slots, self.n_slots()),
+        ] {
+            if let Some(declared) = declared
+                && declared != parsed
+            {
+                return Err(TtpError::Validation(format!(
+                    "instance declares {} {} but lists {}",
+                    declared, element, parsed
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks the bounds of the capacity and separation constraints.
+    ///
+    /// Every constraint must have `c_min <= c_max`, and every capacity constraint a window of
+    /// at least one slot (`c_intp >= 1`). With inverted bounds every occurrence counted by the
+    /// constraint is a violation, which points to an error in the instance data rather than
+    /// in the schedules.
+    ///
+    /// # Returns
+    /// A `Vec<String>` describing each malformed constraint with its index (see
+    /// `constraint_by_index`), empty if every bound is valid.
+    ///
+    /// # Example
+    /// ```
+    /// let mut raw_data = XmlManager::read_xml("NL8.xml").unwrap();
+    /// raw_data.capacity_constraints[0].c_min = raw_data.capacity_constraints[0].c_max + 1;
+    /// assert_eq!(raw_data.constraint_bound_errors().len(), 1);
+    /// ```
+    pub fn constraint_bound_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        for constraint in &self.capacity_constraints {
+            if constraint.c_min > constraint.c_max {
+                errors.push(format!(
+                    "Capacity constraint #{} has min {} greater than max {}",
+                    constraint.index, constraint.c_min, constraint.c_max
+                ));
+            }
+            if constraint.c_intp < 1 {
+                errors.push(format!(
+                    "Capacity constraint #{} has a window of {} slots (at least 1 expected)",
+                    constraint.index, constraint.c_intp
+                ));
+            }
+        }
+
+        for constraint in &self.separation_constraints {
+            if constraint.c_min > constraint.c_max {
+                errors.push(format!(
+                    "Separation constraint #{} has min {} greater than max {}",
+                    constraint.index, constraint.c_min, constraint.c_max
+                ));
+            }
+        }
+
+        errors
+    }
+
+    /// Returns the distances of the instance that are negative.
+    ///
+    /// A negative distance makes a trip shorten the objective, so the total traveling
+    /// distance of the solutions is no longer meaningful.
+    ///
+    /// # Returns
+    /// A `Vec<String>` describing each negative distance with its teams, empty if every
+    /// distance is non-negative.
+    ///
+    /// # Example
+    /// ```
+    /// let mut raw_data = XmlManager::read_xml("NL8.xml").unwrap();
+    /// raw_data.distances[1].dist = -5;
+    /// assert_eq!(raw_data.negative_distance_errors().len(), 1);
+    /// ```
+    pub fn negative_distance_errors(&self) -> Vec<String> {
+        self.distances
+            .iter()
+            .filter(|distance| distance.dist < 0)
+            .map(|distance| {
+                format!(
+                    "Distance from team {} to team {} is negative ({})",
+                    distance.team1, distance.team2, distance.dist
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the pairs of distinct teams whose distance is `0`.
+    ///
+    /// Such a trip is free of charge, which is rarely intended. A missing or unparseable
+    /// `dist` attribute also reads as `0` (see `XmlManager::parse_distance`).
+    ///
+    /// # Returns
+    /// A `Vec<(i32, i32)>` with the `(team1, team2)` pairs of the zero distances, in file order.
+    pub fn zero_distance_pairs(&self) -> Vec<(i32, i32)> {
+        self.distances
+            .iter()
+            .filter(|distance| distance.dist == 0 && distance.team1 != distance.team2)
+            .map(|distance| (distance.team1, distance.team2))
+            .collect()
+    }
+
+    /// Returns the number of slots of the instance.
+    pub fn n_slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Restricts the instance to a single round-robin.
+    ///
+    /// Only the first `teams - 1` slots are kept, so solutions are built and evaluated over the
+    /// first half of the tournament. Each pair of teams then meets once, so the evaluation should
+    /// set `EvaluationOptions::max_meetings` to `1`. The fingerprint of the instance should be
+    /// taken before.
+    ///
+    /// # Example
+    /// ```
+    /// let mut raw_data = XmlManager::read_xml("NL8.xml").unwrap();
+    /// raw_data.restrict_to_single_round_robin();
+    /// assert_eq!(raw_data.n_slots(), 7);
+    /// ```
+    pub fn restrict_to_single_round_robin(&mut self) {
+        self.slots.truncate(self.n_teams().saturating_sub(1));
+    }
+
+    /// Builds a reduced instance with a subset of the teams, to reproduce a problem on a small instance.
+    ///
+    /// The kept teams are renumbered `0..k` in the order of `teams`, and their venues
+    /// `0..` in the order they are first used, so the reduced instance indexes its teams
+    /// and distances like a parsed one. Only the distances between two kept venues are kept,
+    /// and the first `2 * (k - 1)` slots (a double round-robin of `k` teams). A constraint is
+    /// kept if each of its team groups is `ALL_TEAMS_GROUP` or the group of a kept team; it
+    /// keeps its `index` in the original file.
+    ///
+    /// # Arguments
+    /// * `ids` - The ids of the teams to keep (duplicates are ignored).
+    ///
+    /// # Returns
+    /// A new `Rawdata` with the kept teams, distances, slots and constraints.
+    ///
+    /// # Panics
+    /// Panics if an id is not a team of the instance.
+    ///
+    /// # Example
+    /// ```
+    /// let raw_data = XmlManager::read_xml("NL8.xml").unwrap();
+    /// let reduced = raw_data.subset_teams(&[0, 1, 2]);
+    /// assert_eq!(reduced.n_teams(), 3);
+    /// assert_eq!(reduced.n_slots(), 4);
+    /// ```
+    pub fn subset_teams(&self, ids: &[i32]) -> Rawdata {
+        for id in ids {
+            assert!(
+                self.teams.iter().any(|team| team.id == *id),
+                "Team {} is not a team of instance {}",
+                id,
+                self.instance_name
+            );
+        }
+
+        let kept: Vec<&Team> = self.teams.iter().filter(|team| ids.contains(&team.id)).collect();
+
+        let mut venues: Vec<usize> = Vec::new();
+        let mut teams = Vec::with_capacity(kept.len());
+        for (new_id, team) in kept.iter().enumerate() {
+            let venue = self.venue_of(team.id as usize);
+            let new_venue = venues.iter().position(|&kept_venue| kept_venue == venue).unwrap_or_else(|| {
+                venues.push(venue);
+                venues.len() - 1
+            });
+
+            teams.push(Team {
+                id: new_id as i32,
+                venue: (new_venue != new_id).then_some(new_venue as i32),
+                ..(*team).clone()
+            });
+        }
+
+        let new_venue_of = |venue: i32| venues.iter().position(|&kept_venue| kept_venue as i32 == venue);
+        let distances = self
+            .distances
+            .iter()
+            .filter_map(|distance| {
+                Some(Distance {
+                    dist: distance.dist,
+                    team1: new_venue_of(distance.team1)? as i32,
+                    team2: new_venue_of(distance.team2)? as i32,
+                })
+            })
+            .collect();
+
+        let groups: Vec<i32> = kept.iter().map(|team| team.team_groups).collect();
+        let group_kept = |group: i32| group == ALL_TEAMS_GROUP || groups.contains(&group);
+
+        let n_slots = 2 * teams.len().saturating_sub(1);
+
+        Rawdata {
+            teams,
+            slots: self.slots.iter().take(n_slots).cloned().collect(),
+            distances,
+            capacity_constraints: self
+                .capacity_constraints
+                .iter()
+                .filter(|constraint| group_kept(constraint.c_team_groups1) && group_kept(constraint.c_team_groups2))
+                .cloned()
+                .collect(),
+            separation_constraints: self
+                .separation_constraints
+                .iter()
+                .filter(|constraint| group_kept(constraint.c_team_groups))
+                .cloned()
+                .collect(),
+            declared_teams: self.declared_teams.map(|_| kept.len()),
+            declared_slots: self.declared_slots.map(|_| n_slots.min(self.n_slots())),
+            ..self.clone()
+        }
+    }
+
+
+    /// Returns the constraint at a position of the XML file.
+    ///
+    /// Constraints are numbered from `0` in the order of the `<Constraints>` section, all
+    /// families together. Unsupported constraints (see `unsupported_constraints`) take a
+    /// position too, so the index of a constraint is the same whatever is supported.
+    ///
+    /// # Arguments
+    /// * `index` - The position of the constraint.
+    ///
+    /// # Returns
+    /// The `ConstraintRef` of the constraint, or `None` if no supported constraint has this index.
+    ///
+    /// # Example
+    /// ```
+    /// let raw_data = XmlManager::read_xml("NL8.xml").unwrap();
+    /// if let Some(ConstraintRef::Capacity(constraint)) = raw_data.constraint_by_index(0) {
+    ///     println!("First constraint: max {}", constraint.c_max);
+    /// }
+    /// ```
+    pub fn constraint_by_index(&self, index: usize) -> Option<ConstraintRef<'_>> {
+        self.capacity_constraints
+            .iter()
+            .find(|constraint| constraint.index == index)
+            .map(ConstraintRef::Capacity)
+            .or_else(|| {
+                self.separation_constraints
+                    .iter()
+                    .find(|constraint| constraint.index == index)
+                    .map(ConstraintRef::Separation)
+            })
+    }
+
+    /// Returns the home venue of a team.
+    ///
+    /// Venues are the locations indexed by `distances`. Without a venue mapping in the
+    /// instance, every team plays at the location of its own id (identity mapping).
+    ///
+    /// # Arguments
+    /// * `team` - The team id.
+    ///
+    /// # Returns
+    /// The venue id of the team, `team` itself when it has no explicit venue.
+    ///
+    /// # Example
+    /// ```
+    /// let raw_data = XmlManager::read_xml("NL8.xml").unwrap();
+    /// assert_eq!(raw_data.venue_of(3), 3);
+    /// ```
+    pub fn venue_of(&self, team: usize) -> usize {
+        self.teams
+            .iter()
+            .find(|candidate| candidate.id as usize == team)
+            .and_then(|candidate| candidate.venue)
+            .map_or(team, |venue| venue as usize)
+    }
+
+    /// Returns the ids of the teams belonging to a team group.
+    ///
+    /// `ALL_TEAMS_GROUP` (used for an empty, `-1` or missing group in a constraint) is a
+    /// wildcard matching every team, any other group only matches its members.
+    ///
+    /// # Arguments
+    /// * `group` - The team group id (as in `Team.team_groups`).
+    ///
+    /// # Returns
+    /// A `Vec<i32>` with the ids of the member teams, in the order of `teams`.
+    ///
+    /// # Example
+    /// ```
+    /// let raw_data = XmlManager::read_xml("NL8.xml").unwrap();
+    /// assert_eq!(raw_data.teams_in_group(0).len(), raw_data.teams.len());
+    /// assert_eq!(raw_data.teams_in_group(ALL_TEAMS_GROUP).len(), raw_data.teams.len());
+    /// ```
+    pub fn teams_in_group(&self, group: i32) -> Vec<i32> {
+        self.teams
+            .iter()
+            .filter(|team| group == ALL_TEAMS_GROUP || team.team_groups == group)
+            .map(|team| team.id)
+            .collect()
+    }
+
+    /// Returns, for each team index, whether the team belongs to a team group.
+    ///
+    /// # Arguments
+    /// * `group` - The team group id, or `ALL_TEAMS_GROUP` for all teams (see `teams_in_group`).
+    ///
+    /// # Returns
+    /// A `Vec<bool>` of length `teams.len()` where entry `t` is `true` if team `t` is a member.
+    pub fn group_membership(&self, group: i32) -> Vec<bool> {
+        let mut membership = vec![false; self.teams.len()];
+        for id in self.teams_in_group(group) {
+            if let Some(member) = membership.get_mut(id as usize) {
+                *member = true;
+            }
+        }
+        membership
+    }
+
+    /// Builds the map from each team group to the ids of its member teams.
+    ///
+    /// The map is recomputed on each call (a single pass over `teams`), callers
+    /// evaluating many solutions should build it once and reuse it.
+    ///
+    /// # Returns
+    /// A `HashMap<i32, Vec<i32>>` where keys are team group ids and values are the
+    /// ids of the member teams, in the order of `teams`.
+    ///
+    /// # Example
+    /// ```
+    /// let raw_data = XmlManager::read_xml("NL8.xml");
+    /// let groups = raw_data.group_map();
+    /// println!("Teams of group 0: {:?}", groups[&0]);
+    /// ```
+    pub fn group_map(&self) -> HashMap<i32, Vec<i32>> {
+        let mut groups: HashMap<i32, Vec<i32>> = HashMap::new();
+        for team in &self.teams {
+            groups.entry(team.team_groups).or_default().push(team.id);
+        }
+        groups
+    }
 }
 
 /// Represents the travel distance between two teams.
@@ -43,7 +554,7 @@ impl Distance {
 }
 
 /// Represents a team in the tournament.
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Team {
     /// Unique team ID.
     pub id: i32,
@@ -53,6 +564,9 @@ pub struct Team {
     pub name: String,
     /// Team group or category.
     pub team_groups: i32,
+    /// Id of the home venue of the team, `None` when the team plays at its own location.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub venue: Option<i32>,
 }
 
 impl Team {
@@ -63,12 +577,26 @@ impl Team {
             league: 0,
             name: "Null".to_string(),
             team_groups: 0,
+            venue: None,
         }
     }
 }
 
+/// Team group of a constraint that applies to every team.
+///
+/// RobinX instances may leave the team group of a constraint empty or set it to `-1`
+/// to mean "all teams". The parser stores such groups (and missing ones) as this value.
+pub const ALL_TEAMS_GROUP: i32 = -1;
+
+/// A supported constraint of an instance, as returned by `Rawdata::constraint_by_index`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConstraintRef<'a> {
+    Capacity(&'a CapacityConstraints),
+    Separation(&'a SeparationConstraints),
+}
+
 /// Represents a time slot or round in the tournament.
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Slot {
     /// Slot ID.
     pub id: i32,
@@ -87,7 +615,7 @@ impl Slot {
 }
 
 /// Represents capacity constraints for the tournament.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct CapacityConstraints {
     /// Interval parameter
     pub c_intp: i32,
@@ -97,16 +625,29 @@ pub struct CapacityConstraints {
     pub c_min: i32,
     /// Mode type 1 ('A', 'H').
     pub c_mode1: char,
-    /// Mode type 2 (string).
+    /// Mode type 2: what the `c_intp` window counts. `"SLOTS"` (or `"S"`) for consecutive
+    /// slots, `"GAMES"` (or `"G"`) for consecutive games of the team. Any other value,
+    /// including a missing attribute, is read as `"GAMES"`.
     pub c_mode2: String,
     /// Penalty value for violation.
     pub c_penalty: i32,
-    /// First affected team group.
+    /// First affected team group: the teams whose games are counted (`ALL_TEAMS_GROUP` for all teams).
     pub c_team_groups1: i32,
-    /// Second affected team group.
+    /// Second affected team group: the opponents counted in the games (`ALL_TEAMS_GROUP` for all teams).
     pub c_team_groups2: i32,
     /// Type of constraint (description).
     pub c_type: String,
+    /// `true` for a `CA2` element, which limits the games between the two team groups
+    /// (see `EvaluationOptions::ca2_group_pairs`).
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub c_group_pairs: bool,
+    /// Slots of a group-pair constraint (`slots` attribute), empty for all slots.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub c_slots: Vec<i32>,
+    /// Position of the constraint among the constraints of the XML file (see `Rawdata::constraint_by_index`).
+    /// Not serialized, so it is not part of the fingerprint.
+    #[serde(skip)]
+    pub index: usize,
 }
 
 impl CapacityConstraints {
@@ -119,15 +660,43 @@ impl CapacityConstraints {
             c_mode1: 'N',
             c_mode2: "Null".to_string(),
             c_penalty: 0,
-            c_team_groups1: 0,
-            c_team_groups2: 0,
+            c_team_groups1: ALL_TEAMS_GROUP,
+            c_team_groups2: ALL_TEAMS_GROUP,
             c_type: "Null".to_string(),
+            c_group_pairs: false,
+            c_slots: Vec::new(),
+            index: 0,
         }
     }
+
+    /// Returns `true` if the constraint applies to `slot` (always, without a `slots` attribute).
+    ///
+    /// # Example
+    /// ```
+    /// let mut constraint = CapacityConstraints::new();
+    /// constraint.c_slots = vec![0, 1];
+    /// assert!(!constraint.applies_to_slot(2));
+    /// ```
+    pub fn applies_to_slot(&self, slot: usize) -> bool {
+        self.c_slots.is_empty() || self.c_slots.contains(&(slot as i32))
+    }
+
+    /// Returns `true` if the window of the constraint counts consecutive slots (`c_mode2` is
+    /// `"SLOTS"` or `"S"`, case insensitive), `false` if it counts consecutive games.
+    ///
+    /// # Example
+    /// ```
+    /// let mut constraint = CapacityConstraints::new();
+    /// constraint.c_mode2 = "SLOTS".to_string();
+    /// assert!(constraint.counts_slots());
+    /// ```
+    pub fn counts_slots(&self) -> bool {
+        self.c_mode2.eq_ignore_ascii_case("SLOTS") || self.c_mode2.eq_ignore_ascii_case("S")
+    }
 }
 
 /// Represents separation constraints for the tournament.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct SeparationConstraints {
     /// Maximum allowed distance between occurrences.
     pub c_max: i32,
@@ -135,10 +704,18 @@ pub struct SeparationConstraints {
     pub c_min: i32,
     /// Penalty value for violation.
     pub c_penalty: i32,
-    /// Team group affected by the constraint.
+    /// Team group affected by the constraint (`ALL_TEAMS_GROUP` for all teams).
     pub c_team_groups: i32,
+    /// Slots the constraint applies to: only meetings in these slots are separated. Empty
+    /// (the default when the `slots` attribute is missing) for all slots.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub c_slots: Vec<i32>,
     /// Type of constraint (description).
     pub c_type: String,
+    /// Position of the constraint among the constraints of the XML file (see `Rawdata::constraint_by_index`).
+    /// Not serialized, so it is not part of the fingerprint.
+    #[serde(skip)]
+    pub index: usize,
 }
 
 impl SeparationConstraints {
@@ -148,8 +725,285 @@ impl SeparationConstraints {
             c_max: 0,
             c_min: 0,
             c_penalty: 0,
-            c_team_groups: 0,
+            c_team_groups: ALL_TEAMS_GROUP,
+            c_slots: Vec::new(),
+            index: 0,
             c_type: "Null".to_string(),
         }
     }
+
+    /// Returns `true` if the constraint applies to `slot` (always, without a `slots` attribute).
+    ///
+    /// # Example
+    /// ```
+    /// let mut constraint = SeparationConstraints::new();
+    /// assert!(constraint.applies_to_slot(7));
+    /// constraint.c_slots = vec![0, 1, 2];
+    /// assert!(!constraint.applies_to_slot(7));
+    /// ```
+    pub fn applies_to_slot(&self, slot: usize) -> bool {
+        self.c_slots.is_empty() || self.c_slots.contains(&(slot as i32))
+    }
+
+    /// Returns how far a gap between two meetings is from the allowed window.
+    ///
+    /// A gap must be greater than `c_min` and at most `c_max`. A gap of `c_min` misses the
+    /// window by 1, a gap of `c_max + 1` too.
+    ///
+    /// # Arguments
+    /// * `gap` - The number of slots between two meetings of the same pair of teams.
+    ///
+    /// # Returns
+    /// The distance to the nearest bound (`i32`), `0` if the gap is in the window.
+    ///
+    /// # Example
+    /// ```
+    /// let mut constraint = SeparationConstraints::new();
+    /// constraint.c_min = 3;
+    /// constraint.c_max = 10;
+    /// assert_eq!(constraint.violation_depth(3), 1);
+    /// assert_eq!(constraint.violation_depth(1), 3);
+    /// assert_eq!(constraint.violation_depth(12), 2);
+    /// ```
+    pub fn violation_depth(&self, gap: usize) -> i32 {
+        let gap = gap as i32;
+
+        if gap <= self.c_min {
+            self.c_min - gap + 1
+        } else if gap > self.c_max {
+            gap - self.c_max
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xml_manager::XmlManager;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn nl8() -> Rawdata {
+        XmlManager::read_xml("NL8.xml").unwrap()
+    }
+
+    #[test]
+    fn fingerprint_is_stable_across_parses() {
+        assert_eq!(nl8().fingerprint(), nl8().fingerprint());
+    }
+
+    #[test]
+    fn fingerprint_ignores_element_order() {
+        let raw_data = nl8();
+        let mut reordered = raw_data.clone();
+        reordered.teams.reverse();
+        reordered.slots.reverse();
+        reordered.distances.reverse();
+        reordered.capacity_constraints.reverse();
+        reordered.separation_constraints.reverse();
+
+        assert_ne!(reordered, raw_data);
+        assert_eq!(reordered.fingerprint(), raw_data.fingerprint());
+    }
+
+    #[test]
+    fn fingerprint_changes_with_a_distance() {
+        let raw_data = nl8();
+        let mut edited = raw_data.clone();
+        edited.distances[1].dist += 1;
+
+        assert_ne!(edited.fingerprint(), raw_data.fingerprint());
+    }
+
+    #[test]
+    fn subset_teams_keeps_the_distances_between_the_kept_teams() {
+        let raw_data = nl8();
+        let kept = [1, 3, 6];
+        let reduced = raw_data.subset_teams(&kept);
+
+        assert_eq!(reduced.n_teams(), 3);
+        assert_eq!(reduced.n_slots(), 4);
+        assert_eq!(reduced.teams.iter().map(|team| team.id).collect::<Vec<_>>(), vec![0, 1, 2]);
+        let distance = |data: &Rawdata, from: i32, to: i32| {
+            data.distances.iter().find(|distance| distance.team1 == from && distance.team2 == to).map(|distance| distance.dist)
+        };
+        for (new_from, &from) in kept.iter().enumerate() {
+            for (new_to, &to) in kept.iter().enumerate() {
+                assert_eq!(distance(&reduced, new_from as i32, new_to as i32), distance(&raw_data, from, to));
+            }
+        }
+        assert!(reduced.distances.iter().all(|distance| distance.team1 < 3 && distance.team2 < 3));
+
+        // Every NL8 team is in group 0, so each constraint is kept with its position
+        let indices = |data: &Rawdata| {
+            let mut indices: Vec<usize> = data.capacity_constraints.iter().map(|constraint| constraint.index)
+                .chain(data.separation_constraints.iter().map(|constraint| constraint.index))
+                .collect();
+            indices.sort();
+            indices
+        };
+        assert_eq!(indices(&reduced), indices(&raw_data));
+    }
+
+    #[test]
+    fn metadata_is_parsed_from_the_instance() {
+        let metadata = nl8().metadata;
+
+        assert_eq!(
+            metadata,
+            Metadata {
+                phased: false,
+                objective: "TR".to_string(),
+                game_mode: "C".to_string(),
+                data_type: "A".to_string(),
+                contributor: "Easton, Nemhauser, and Trick".to_string(),
+            }
+        );
+        assert!(metadata.is_time_constrained());
+        assert!(!Metadata { game_mode: "R".to_string(), ..metadata }.is_time_constrained());
+    }
+
+    #[test]
+    fn empty_instance_name_falls_back_to_the_file_stem() {
+        let mut raw_data = nl8();
+        raw_data.instance_name = " ".to_string();
+        raw_data.resolve_instance_name(None, "instances/NL8_copy.xml");
+
+        assert_eq!(raw_data.instance_name, "NL8_copy");
+    }
+
+    #[test]
+    fn name_override_keeps_the_fingerprint() {
+        let raw_data = nl8();
+        let mut overridden = raw_data.clone();
+        overridden.resolve_instance_name(Some("custom"), "NL8.xml");
+
+        assert_eq!(overridden.instance_name, "custom");
+        assert_eq!(overridden.fingerprint(), raw_data.fingerprint());
+    }
+
+    #[test]
+    fn fingerprint_ignores_the_instance_name() {
+        let raw_data = nl8();
+        let mut renamed = raw_data.clone();
+        renamed.instance_name = "renamed".to_string();
+
+        assert_eq!(renamed.fingerprint(), raw_data.fingerprint());
+    }
+
+    #[test]
+    fn constraint_indices_follow_the_parse_order() {
+        let raw_data = nl8();
+
+        // NL8 lists two CA3 then one SE1
+        let capacity: Vec<usize> = raw_data.capacity_constraints.iter().map(|constraint| constraint.index).collect();
+        let separation: Vec<usize> = raw_data.separation_constraints.iter().map(|constraint| constraint.index).collect();
+        assert_eq!(capacity, vec![0, 1]);
+        assert_eq!(separation, vec![2]);
+    }
+
+    #[test]
+    fn constraint_by_index_returns_the_constraint_at_that_position() {
+        let raw_data = nl8();
+
+        assert_eq!(raw_data.constraint_by_index(1), Some(ConstraintRef::Capacity(&raw_data.capacity_constraints[1])));
+        assert_eq!(raw_data.constraint_by_index(2), Some(ConstraintRef::Separation(&raw_data.separation_constraints[0])));
+        assert_eq!(raw_data.constraint_by_index(3), None);
+    }
+
+    #[test]
+    fn teams_are_indexed_by_their_group() {
+        let mut data = nl8();
+        for team in data.teams.iter_mut().filter(|team| team.id % 2 == 1) {
+            team.team_groups = 1;
+        }
+
+        let groups = data.group_map();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&0], vec![0, 2, 4, 6]);
+        assert_eq!(groups[&1], vec![1, 3, 5, 7]);
+        assert_eq!(data.teams_in_group(1), groups[&1]);
+        assert!(data.teams_in_group(2).is_empty());
+        assert!(data.teams_in_group(-2).is_empty());
+        assert_eq!(data.teams_in_group(ALL_TEAMS_GROUP).len(), data.teams.len());
+        assert_eq!(data.group_membership(1), vec![false, true, false, true, false, true, false, true]);
+    }
+
+    /// A random instance of 2 to 10 teams, its constraints with the default `index` since it
+    /// is not serialized.
+    fn random_instance(rng: &mut StdRng) -> Rawdata {
+        let num_teams = 2 * rng.random_range(1..=5);
+        let teams: Vec<Team> = (0..num_teams)
+            .map(|id| Team {
+                id,
+                league: rng.random_range(0..3),
+                name: format!("Team \"{}\" & co", id),
+                team_groups: rng.random_range(-1..3),
+                venue: rng.random_bool(0.3).then(|| rng.random_range(0..num_teams)),
+            })
+            .collect();
+        let distances = (0..num_teams)
+            .flat_map(|team1| (0..num_teams).map(move |team2| (team1, team2)))
+            .map(|(team1, team2)| Distance { dist: rng.random_range(0..10_000), team1, team2 })
+            .collect();
+        let capacity_constraints = (0..rng.random_range(0..4))
+            .map(|_| CapacityConstraints {
+                c_intp: rng.random_range(1..6),
+                c_max: rng.random_range(0..4),
+                c_mode1: if rng.random_bool(0.5) { 'H' } else { 'A' },
+                c_mode2: if rng.random_bool(0.5) { "GAMES" } else { "SLOTS" }.to_string(),
+                c_group_pairs: rng.random_bool(0.2),
+                c_slots: (0..rng.random_range(0..3)).collect(),
+                ..CapacityConstraints::new()
+            })
+            .collect();
+        let separation_constraints = (0..rng.random_range(0..3))
+            .map(|_| SeparationConstraints { c_min: rng.random_range(0..4), c_team_groups: rng.random_range(-1..3), ..SeparationConstraints::new() })
+            .collect();
+
+        Rawdata {
+            instance_name: format!("Random{}", num_teams),
+            teams,
+            slots: (0..2 * (num_teams - 1)).map(|id| Slot { id, name: format!("Slot{}", id) }).collect(),
+            distances,
+            capacity_constraints,
+            separation_constraints,
+            unsupported_constraints: if rng.random_bool(0.5) { vec!["BR1".to_string()] } else { Vec::new() },
+            metadata: Metadata { phased: rng.random_bool(0.5), game_mode: "C".to_string(), ..Metadata::default() },
+            declared_teams: rng.random_bool(0.5).then_some(num_teams as usize),
+            declared_slots: None,
+        }
+    }
+
+    #[test]
+    fn random_instances_survive_a_json_round_trip() {
+        let mut rng = StdRng::seed_from_u64(149);
+        for _ in 0..100 {
+            let instance = random_instance(&mut rng);
+            let json = serde_json::to_string(&instance).unwrap();
+            let parsed: Rawdata = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, instance);
+            for constraint in &instance.capacity_constraints {
+                assert_eq!(serde_json::from_str::<CapacityConstraints>(&serde_json::to_string(constraint).unwrap()).unwrap(), *constraint);
+            }
+        }
+    }
+
+    #[test]
+    fn inverted_constraint_bounds_are_reported_with_their_index() {
+        let mut raw_data = nl8();
+        assert!(raw_data.constraint_bound_errors().is_empty());
+
+        raw_data.capacity_constraints[1].c_intp = 0;
+        raw_data.separation_constraints[0].c_min = raw_data.separation_constraints[0].c_max + 1;
+        assert_eq!(
+            raw_data.constraint_bound_errors(),
+            vec![
+                "Capacity constraint #1 has a window of 0 slots (at least 1 expected)".to_string(),
+                "Separation constraint #2 has min 15 greater than max 14".to_string(),
+            ]
+        );
+    }
 }
@@ -0,0 +1,407 @@
+// Std library
+use std::cell::RefCell;
+use std::rc::Rc;
+
+// Local modules
+use crate::data_set::{Distance, Rawdata, Team};
+
+/// Teams and distances of an instance with the matrix built from them.
+type CachedMatrix = (Vec<Team>, Vec<Distance>, Rc<DistanceMatrix>);
+
+thread_local! {
+    /// Matrix built by the last `DistanceMatrix::for_data` of the current thread.
+    static CACHED_MATRIX: RefCell<Option<CachedMatrix>> = const { RefCell::new(None) };
+}
+
+/// Explicit mapping between the team ids of an instance and the venues indexing its distances.
+///
+/// The map is built from the teams of the instance (see `Rawdata::venue_of`), never from the
+/// order of a permutation: solutions refer to teams by id, and ids are turned into venues only
+/// here. Several teams may share a venue.
+///
+/// # Example
+/// ```
+/// let data = XmlManager::read_xml("NL8.xml").unwrap();
+/// let index_map = IndexMap::from_rawdata(&data);
+/// assert_eq!(index_map.venue_index(3), 3);
+/// assert_eq!(index_map.teams_at(3), &[3]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IndexMap {
+    venues: Vec<usize>,
+    teams: Vec<Vec<usize>>,
+}
+
+impl IndexMap {
+    /// Builds the map of an instance, with at least one venue per team.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing the teams.
+    ///
+    /// # Returns
+    /// An `IndexMap` where team `t` plays at venue `data.venue_of(t)`.
+    pub fn from_rawdata(data: &Rawdata) -> Self {
+        let venues: Vec<usize> = (0..data.n_teams()).map(|team| data.venue_of(team)).collect();
+        Self::from_venues(venues)
+    }
+
+    /// Builds the map where each team plays at the venue of its own id.
+    ///
+    /// # Arguments
+    /// * `num_teams` - The number of teams.
+    pub fn identity(num_teams: usize) -> Self {
+        Self::from_venues((0..num_teams).collect())
+    }
+
+    fn from_venues(venues: Vec<usize>) -> Self {
+        let num_venues = venues.iter().map(|venue| venue + 1).max().unwrap_or(0).max(venues.len());
+        let mut teams = vec![Vec::new(); num_venues];
+        for (team, &venue) in venues.iter().enumerate() {
+            teams[venue].push(team);
+        }
+
+        Self { venues, teams }
+    }
+
+    /// Returns the venue index of a team.
+    ///
+    /// # Panics
+    /// Panics if `team` is not a team id of the instance.
+    pub fn venue_index(&self, team: usize) -> usize {
+        match self.venues.get(team) {
+            Some(&venue) => venue,
+            None => panic!("team {} out of bounds for an instance of {} teams", team, self.venues.len()),
+        }
+    }
+
+    /// Returns the ids of the teams playing at a venue, empty for an unused or unknown venue.
+    pub fn teams_at(&self, venue: usize) -> &[usize] {
+        self.teams.get(venue).map_or(&[], |teams| teams.as_slice())
+    }
+
+    /// Returns the number of teams of the map.
+    pub fn n_teams(&self) -> usize {
+        self.venues.len()
+    }
+
+    /// Returns the number of venues of the map (at least the number of teams).
+    pub fn n_venues(&self) -> usize {
+        self.teams.len()
+    }
+}
+
+/// Square matrix of the traveling distances between the teams of an instance.
+///
+/// The matrix is indexed by team id: `get(from, to)` is the distance traveled from the venue of
+/// team `from` to the venue of team `to`. The distances are stored between venues and every
+/// lookup translates the team ids through the `IndexMap` of the instance, so a position in a
+/// permutation can never be used as a venue by mistake. Building it in one place keeps the
+/// venue mapping and the symmetric fill out of the evaluation code.
+///
+/// # Example
+/// ```
+/// let data = XmlManager::read_xml("NL8.xml").unwrap();
+/// let matrix = DistanceMatrix::from_rawdata(&data);
+/// assert_eq!(matrix.dim(), data.n_teams());
+/// assert_eq!(matrix.get(0, 2), matrix.get(2, 0));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DistanceMatrix {
+    values: Vec<Vec<i32>>,
+    index_map: IndexMap,
+    missing: Vec<(usize, usize)>,
+}
+
+impl DistanceMatrix {
+    /// Builds the distance matrix of an instance.
+    ///
+    /// The `distances` of the instance are read between venues, and the teams are mapped to
+    /// their venues by `IndexMap::from_rawdata`. A pair listed in one direction only is
+    /// filled symmetrically (its reverse gets the same distance), pairs that are not listed
+    /// at all are `0` and are recorded in `missing_pairs` (teams sharing a venue are never
+    /// missing).
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing the teams and the `distances` list.
+    ///
+    /// # Returns
+    /// A `DistanceMatrix` of dimension `data.n_teams()`.
+    ///
+    /// # Example
+    /// ```
+    /// let matrix = DistanceMatrix::from_rawdata(&data);
+    /// println!("Distance: {}", matrix.get(0, 2));
+    /// ```
+    pub fn from_rawdata(data: &Rawdata) -> Self {
+        let index_map = IndexMap::from_rawdata(data);
+        let num_venues = index_map.n_venues();
+        let mut venue_distances = vec![vec![0i32; num_venues]; num_venues];
+        let mut listed = vec![vec![false; num_venues]; num_venues];
+
+        for distance in &data.distances {
+            let (from, to) = (distance.team1 as usize, distance.team2 as usize);
+            venue_distances[from][to] = distance.dist;
+            listed[from][to] = true;
+        }
+
+        for from in 0..num_venues {
+            for to in 0..num_venues {
+                if listed[from][to] && !listed[to][from] {
+                    venue_distances[to][from] = venue_distances[from][to];
+                }
+            }
+        }
+
+        let mut missing = Vec::new();
+        for from in 0..index_map.n_teams() {
+            for to in 0..index_map.n_teams() {
+                let (from_venue, to_venue) = (index_map.venue_index(from), index_map.venue_index(to));
+                if from_venue != to_venue && !listed[from_venue][to_venue] && !listed[to_venue][from_venue] {
+                    missing.push((from, to));
+                }
+            }
+        }
+
+        Self {
+            values: venue_distances,
+            index_map,
+            missing,
+        }
+    }
+
+    /// Returns the distance matrix of an instance, reusing the last one built on this thread.
+    ///
+    /// The matrix only depends on the teams and the distances of the instance. When they are
+    /// equal to the ones of the previous call, the same matrix is returned, otherwise it is
+    /// built with `from_rawdata` and replaces the cached one.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing the teams and the `distances` list.
+    ///
+    /// # Returns
+    /// A shared `DistanceMatrix` of dimension `data.n_teams()`.
+    ///
+    /// # Example
+    /// ```
+    /// let matrix = DistanceMatrix::for_data(&data);
+    /// assert!(Rc::ptr_eq(&matrix, &DistanceMatrix::for_data(&data)));
+    /// ```
+    pub fn for_data(data: &Rawdata) -> Rc<DistanceMatrix> {
+        CACHED_MATRIX.with(|cached| {
+            let mut cached = cached.borrow_mut();
+            match cached.as_ref() {
+                Some((teams, distances, matrix)) if *teams == data.teams && *distances == data.distances => Rc::clone(matrix),
+                _ => {
+                    let matrix = Rc::new(Self::from_rawdata(data));
+                    *cached = Some((data.teams.clone(), data.distances.clone(), Rc::clone(&matrix)));
+                    matrix
+                }
+            }
+        })
+    }
+
+    /// Returns the ordered team pairs `(from, to)` that have no distance in the instance.
+    ///
+    /// Their distance is `0`, so a trip between them is free of charge, which is rarely
+    /// intended: the instance is most likely incomplete.
+    ///
+    /// # Example
+    /// ```
+    /// let matrix = DistanceMatrix::from_rawdata(&data);
+    /// for (from, to) in matrix.missing_pairs() {
+    ///     println!("No distance from {} to {}", from, to);
+    /// }
+    /// ```
+    pub fn missing_pairs(&self) -> &[(usize, usize)] {
+        &self.missing
+    }
+
+    /// Returns the distance traveled from team `from` to team `to`.
+    ///
+    /// Both team ids are translated to their venues by the `IndexMap` of the matrix.
+    ///
+    /// # Arguments
+    /// * `from` - The team id of the origin.
+    /// * `to` - The team id of the destination.
+    ///
+    /// # Returns
+    /// The distance (`i32`).
+    ///
+    /// # Panics
+    /// Panics if `from` or `to` is not lower than `dim()`.
+    pub fn get(&self, from: usize, to: usize) -> i32 {
+        let dim = self.dim();
+        assert!(
+            from < dim && to < dim,
+            "distance ({}, {}) out of bounds for a {}x{} matrix",
+            from,
+            to,
+            dim,
+            dim
+        );
+        self.values[self.index_map.venue_index(from)][self.index_map.venue_index(to)]
+    }
+
+    /// Returns the number of teams (rows and columns) of the matrix.
+    pub fn dim(&self) -> usize {
+        self.index_map.n_teams()
+    }
+
+    /// Returns the mapping between the team ids and the venues of the matrix.
+    pub fn index_map(&self) -> &IndexMap {
+        &self.index_map
+    }
+}
+
+impl From<Vec<Vec<i32>>> for DistanceMatrix {
+    /// Wraps a raw `matrix[from][to]` vector, as written by hand or read from another tool.
+    /// Each team plays at its own venue (`IndexMap::identity`).
+    ///
+    /// # Panics
+    /// Panics if the matrix is not square.
+    fn from(values: Vec<Vec<i32>>) -> Self {
+        let dim = values.len();
+        if let Some(row) = values.iter().position(|row| row.len() != dim) {
+            panic!(
+                "distance matrix is not square: row {} has {} columns, expected {}",
+                row,
+                values[row].len(),
+                dim
+            );
+        }
+        Self {
+            index_map: IndexMap::identity(dim),
+            values,
+            missing: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{ConstructionMethod, Solution};
+    use crate::test_support::temp_path;
+    use crate::xml_manager::XmlManager;
+
+    #[test]
+    fn permuted_solutions_travel_between_the_venues_of_the_team_ids() {
+        let mut data = XmlManager::read_xml("NL8.xml").unwrap();
+        // Teams 0 and 5 share the venue of team 5
+        data.teams[0].venue = Some(5);
+        let matrix = DistanceMatrix::from_rawdata(&data);
+        assert_eq!(matrix.index_map().teams_at(5), &[0, 5]);
+        assert!(matrix.index_map().teams_at(0).is_empty());
+
+        let listed = |from: usize, to: usize| {
+            data.distances
+                .iter()
+                .find(|distance| distance.team1 as usize == from && distance.team2 as usize == to)
+                .map_or(0, |distance| distance.dist)
+        };
+        let permutation: Vec<i32> = vec![6, 2, 7, 0, 4, 1, 5, 3];
+        let solution = Solution::regenerate(&data, std::slice::from_ref(&permutation), 3, ConstructionMethod::Florian, None).unwrap();
+
+        let travel = |venue: &dyn Fn(usize) -> usize| -> i32 {
+            (0..data.n_teams())
+                .map(|team| {
+                    let mut location = team;
+                    solution.solution.iter().map(|row| {
+                        let next = if row[team].home_game { team } else { row[team].opponent as usize };
+                        let leg = listed(venue(location), venue(next));
+                        location = next;
+                        leg
+                    }).sum::<i32>()
+                })
+                .sum()
+        };
+        let expected = travel(&|team| data.venue_of(team));
+        let by_position = travel(&|team| permutation.iter().position(|&id| id as usize == team).unwrap());
+
+        let per_team: i32 = solution.per_team_distances(&matrix).iter().sum();
+        assert_eq!(per_team, expected);
+        assert_ne!(per_team, by_position);
+    }
+
+    #[test]
+    fn for_data_reuses_the_matrix_of_the_same_instance() {
+        let data = XmlManager::read_xml("NL8.xml").unwrap();
+        let matrix = DistanceMatrix::for_data(&data);
+        assert_eq!(*matrix, DistanceMatrix::from_rawdata(&data));
+        assert!(Rc::ptr_eq(&matrix, &DistanceMatrix::for_data(&data)));
+
+        let mut edited = data.clone();
+        edited.distances[1].dist += 1;
+        let edited_matrix = DistanceMatrix::for_data(&edited);
+        assert!(!Rc::ptr_eq(&matrix, &edited_matrix));
+        assert_eq!(*edited_matrix, DistanceMatrix::from_rawdata(&edited));
+    }
+
+    #[test]
+    fn a_venue_mapping_changes_the_distances_of_the_team() {
+        let data = XmlManager::read_xml("NL8.xml").unwrap();
+        let xml = std::fs::read_to_string("NL8.xml").unwrap();
+        let path = temp_path("venues.xml");
+        std::fs::write(&path, xml.replace(r#"<team id="0" league="0""#, r#"<team id="0" venue="5" league="0""#)).unwrap();
+        let mapped = XmlManager::read_xml(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let (identity, matrix) = (DistanceMatrix::from_rawdata(&data), DistanceMatrix::from_rawdata(&mapped));
+        assert_eq!(mapped.venue_of(0), 5);
+        assert_eq!(matrix.get(0, 1), identity.get(5, 1));
+        assert_eq!(matrix.get(0, 5), 0);
+        assert_eq!(matrix.get(2, 3), identity.get(2, 3));
+
+        let solution = Solution::regenerate(&data, &[(0..8).collect()], 1, ConstructionMethod::Florian, None).unwrap();
+        let total = |matrix: &DistanceMatrix| solution.per_team_distances(matrix).iter().sum::<i32>();
+        assert_ne!(total(&matrix), total(&identity));
+    }
+
+    #[test]
+    fn distances_listed_one_way_are_filled_symmetrically() {
+        let data = XmlManager::read_xml("NL8.xml").unwrap();
+        let mut one_way = data.clone();
+        one_way.distances.retain(|distance| distance.team1 < distance.team2);
+        let (full, filled) = (DistanceMatrix::from_rawdata(&data), DistanceMatrix::from_rawdata(&one_way));
+
+        assert_eq!(filled.dim(), 8);
+        assert!(filled.missing_pairs().is_empty());
+        for from in 0..filled.dim() {
+            for to in 0..filled.dim() {
+                assert_eq!(filled.get(from, to), filled.get(to, from));
+                assert_eq!(filled.get(from, to), full.get(from, to));
+            }
+        }
+
+        let raw = DistanceMatrix::from(vec![vec![0, 3], vec![5, 0]]);
+        assert_eq!((raw.dim(), raw.get(0, 1), raw.get(1, 0)), (2, 3, 5));
+    }
+
+    #[test]
+    #[should_panic(expected = "distance (8, 0) out of bounds for a 8x8 matrix")]
+    fn a_distance_out_of_bounds_panics() {
+        let matrix = DistanceMatrix::from_rawdata(&XmlManager::read_xml("NL8.xml").unwrap());
+        matrix.get(8, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "distance matrix is not square: row 1 has 1 columns, expected 2")]
+    fn a_matrix_that_is_not_square_panics() {
+        let _ = DistanceMatrix::from(vec![vec![0, 3], vec![5]]);
+    }
+
+    #[test]
+    fn a_distance_missing_both_ways_is_recorded() {
+        let mut data = XmlManager::read_xml("NL8.xml").unwrap();
+        data.distances.retain(|distance| (distance.team1, distance.team2) != (2, 5) && (distance.team1, distance.team2) != (5, 2));
+        let matrix = DistanceMatrix::from_rawdata(&data);
+
+        assert_eq!(matrix.missing_pairs(), &[(2, 5), (5, 2)]);
+        assert_eq!(matrix.get(2, 5), 0);
+        // Listed one way only, the distance is filled and not missing
+        data.distances.push(Distance { dist: 120, team1: 5, team2: 2 });
+        let filled = DistanceMatrix::from_rawdata(&data);
+        assert!(filled.missing_pairs().is_empty());
+        assert_eq!(filled.get(2, 5), 120);
+    }
+}
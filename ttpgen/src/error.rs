@@ -0,0 +1,70 @@
+// Std library
+use std::fmt;
+use std::io;
+
+/// Errors returned by the framework.
+///
+/// # Variants
+/// * `Io` - A file or directory could not be read or written.
+/// * `Xml` - An instance file is not valid XML.
+/// * `Json` - A json file could not be serialized or deserialized.
+/// * `Validation` - The content of a file does not match what is expected (e.g. a solution
+///   with a wrong shape for the instance).
+/// * `Plot` - A histogram could not be drawn or saved.
+///
+/// # Example
+/// ```
+/// match XmlManager::read_xml("missing.xml") {
+///     Ok(raw_data) => println!("Instance name: {}", raw_data.instance_name),
+///     Err(error) => eprintln!("{}", error),
+/// }
+/// ```
+#[derive(Debug)]
+pub enum TtpError {
+    Io(io::Error),
+    Xml(roxmltree::Error),
+    Json(serde_json::Error),
+    Validation(String),
+    Plot(String),
+}
+
+impl fmt::Display for TtpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TtpError::Io(error) => write!(f, "I/O error: {}", error),
+            TtpError::Xml(error) => write!(f, "XML error: {}", error),
+            TtpError::Json(error) => write!(f, "JSON error: {}", error),
+            TtpError::Validation(message) => write!(f, "Validation error: {}", message),
+            TtpError::Plot(message) => write!(f, "Plot error: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for TtpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TtpError::Io(error) => Some(error),
+            TtpError::Xml(error) => Some(error),
+            TtpError::Json(error) => Some(error),
+            TtpError::Validation(_) | TtpError::Plot(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for TtpError {
+    fn from(error: io::Error) -> Self {
+        TtpError::Io(error)
+    }
+}
+
+impl From<roxmltree::Error> for TtpError {
+    fn from(error: roxmltree::Error) -> Self {
+        TtpError::Xml(error)
+    }
+}
+
+impl From<serde_json::Error> for TtpError {
+    fn from(error: serde_json::Error) -> Self {
+        TtpError::Json(error)
+    }
+}
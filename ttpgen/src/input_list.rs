@@ -0,0 +1,138 @@
+// Std library
+use std::fs;
+use std::path::Path;
+
+// Local modules
+use crate::error::TtpError;
+
+/// One instance of an `--input-list` file, with its per-line overrides.
+///
+/// # Fields
+/// * `path` - The path of the XML instance file.
+/// * `seed` - The seed of this instance (`seed=<n>`), the `--seed` of the run otherwise.
+/// * `permutations` - The number of permutations of this instance (`permutations=<n>`), the
+///   `--permutations` of the run otherwise.
+/// * `name` - The instance name used in the outputs (`name=<str>`, as `--name`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputListEntry {
+    pub path: String,
+    pub seed: Option<u64>,
+    pub permutations: Option<i32>,
+    pub name: Option<String>,
+}
+
+impl InputListEntry {
+    /// Returns the name of the output subdirectories of the entry, `<file stem>_<seed>`.
+    ///
+    /// # Arguments
+    /// * `seed` - The seed the instance is run with.
+    ///
+    /// # Returns
+    /// A `String` such as `NL8_42` for `instances/NL8.xml` with seed `42`.
+    pub fn output_name(&self, seed: u64) -> String {
+        let stem = Path::new(&self.path).file_stem().and_then(|stem| stem.to_str()).unwrap_or("instance");
+        format!("{}_{}", stem, seed)
+    }
+}
+
+/// Reads an `--input-list` file.
+///
+/// Each line holds the path of an instance, optionally followed by whitespace-separated
+/// `key=value` overrides among `seed`, `permutations` and `name`. Empty lines and lines
+/// starting with `#` are skipped.
+///
+/// # Arguments
+/// * `path` - The path of the list file.
+///
+/// # Returns
+/// A `Result` with the entries in file order, or a `TtpError`:
+/// - `Io` if the file cannot be read.
+/// - `Validation` naming the line of an unknown key or an invalid value, or if the list has no entry.
+///
+/// # Example
+/// Example input:
+/// ```text
+/// # instance        overrides
+/// instances/NL8.xml seed=42
+/// instances/NL10.xml seed=7 permutations=100
+/// ```
+pub fn read_input_list(path: &str) -> Result<Vec<InputListEntry>, TtpError> {
+    let content = fs::read_to_string(path)?;
+    let mut entries = Vec::new();
+
+    for (line_number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut tokens = line.split_whitespace();
+        let mut entry = InputListEntry {
+            path: tokens.next().unwrap_or_default().to_string(),
+            seed: None,
+            permutations: None,
+            name: None,
+        };
+        let invalid = |token: &str| {
+            TtpError::Validation(format!(
+                "{}:{}: invalid override '{}' (expected seed=<n>, permutations=<n> or name=<str>)",
+                path,
+                line_number + 1,
+                token
+            ))
+        };
+
+        for token in tokens {
+            match token.split_once('=') {
+                Some(("seed", value)) => entry.seed = Some(value.parse().map_err(|_| invalid(token))?),
+                Some(("permutations", value)) => entry.permutations = Some(value.parse().map_err(|_| invalid(token))?),
+                Some(("name", value)) if !value.is_empty() => entry.name = Some(value.to_string()),
+                _ => return Err(invalid(token)),
+            }
+        }
+
+        entries.push(entry);
+    }
+
+    if entries.is_empty() {
+        return Err(TtpError::Validation(format!("{}: no instance listed", path)));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_path;
+
+    /// Reads a list file written with `content`.
+    fn read_list(name: &str, content: &str) -> Result<Vec<InputListEntry>, TtpError> {
+        let path = temp_path(name);
+        fs::write(&path, content).unwrap();
+        let entries = read_input_list(&path);
+        fs::remove_file(&path).unwrap();
+        entries
+    }
+
+    #[test]
+    fn a_two_line_list_is_read_with_its_overrides() {
+        let entries = read_list("two_lines.txt", "# instance  overrides\n\ninstances/NL8.xml seed=42\n  instances/NL10.xml permutations=100 name=NL10b  \n").unwrap();
+
+        assert_eq!(
+            entries,
+            vec![
+                InputListEntry { path: "instances/NL8.xml".to_string(), seed: Some(42), permutations: None, name: None },
+                InputListEntry { path: "instances/NL10.xml".to_string(), seed: None, permutations: Some(100), name: Some("NL10b".to_string()) },
+            ]
+        );
+        assert_eq!(entries[0].output_name(42), "NL8_42");
+    }
+
+    #[test]
+    fn an_invalid_override_or_an_empty_list_is_rejected() {
+        let error = read_list("invalid.txt", "NL8.xml\nNL8.xml seed=abc\n").unwrap_err().to_string();
+        assert!(error.contains(":2: invalid override 'seed=abc'"), "{}", error);
+        assert!(read_list("unknown.txt", "NL8.xml threads=4\n").is_err());
+        assert!(read_list("empty.txt", "# nothing to run\n").unwrap_err().to_string().contains("no instance listed"));
+    }
+}
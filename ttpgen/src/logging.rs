@@ -1,11 +1,71 @@
 // Std library
+use std::cell::Cell;
 use std::fs::OpenOptions;
 use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 // External crates
 use chrono::Local;
 use env_logger::{Builder, Target};
-use log::{LevelFilter};
+use log::{Level, LevelFilter};
+
+/// Number of warnings (and errors) logged since the logger was initialized.
+static WARNING_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    /// Whether the info-level messages of the current thread are muted (see `quietly`).
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns the number of warnings (and errors) logged so far.
+///
+/// The warnings are counted even when logging is disabled, so `--fail-on-warning`
+/// works with or without `--log`.
+///
+/// # Example
+/// ```
+/// init_logger("experiment.log", false);
+/// warn!("Something looks wrong");
+/// assert_eq!(warning_count(), 1);
+/// ```
+pub fn warning_count() -> usize {
+    WARNING_COUNT.load(Ordering::Relaxed)
+}
+
+/// Counts a log record if it is a warning or an error.
+pub(crate) fn count_warning(level: Level) {
+    if level <= Level::Warn {
+        WARNING_COUNT.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Returns whether a record of this level is muted on the current thread (see `quietly`).
+pub(crate) fn is_muted(level: Level) -> bool {
+    level > Level::Warn && QUIET.with(Cell::get)
+}
+
+/// Runs a closure with the info-level messages of the current thread muted.
+///
+/// Warnings and errors are still logged (and counted by `warning_count`). The messages of
+/// the other threads are not affected, and the previous state is restored when the closure
+/// returns.
+///
+/// # Arguments
+/// * `f` - The closure to run.
+///
+/// # Returns
+/// The value returned by `f`.
+///
+/// # Example
+/// ```
+/// let solution = quietly(|| Solution::generate_florian_solution(&data, 0, true));
+/// ```
+pub fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    let previous = QUIET.with(|quiet| quiet.replace(true));
+    let result = f();
+    QUIET.with(|quiet| quiet.set(previous));
+    result
+}
 
 /// Initializes the logger to write messages to console and the file.
 ///
@@ -27,6 +87,14 @@ use log::{LevelFilter};
 /// ```
 pub fn init_logger(log_file: &str, enable: bool) {
     if !enable{
+        // Nothing is written, warnings are only counted (see `warning_count`)
+        Builder::new()
+            .format(|_buf, record| {
+                count_warning(record.level());
+                Ok(())
+            })
+            .filter_level(LevelFilter::Warn)
+            .init();
         return;
     }
 
@@ -38,6 +106,10 @@ pub fn init_logger(log_file: &str, enable: bool) {
 
     Builder::new()
         .format(move |_buf, record| {
+            count_warning(record.level());
+            if is_muted(record.level()) {
+                return Ok(());
+            }
             let timestamp = Local::now().format("%H:%M:%S");
             let line = format!("[{}][{}] {}\n", timestamp, record.level(), record.args());
 
@@ -50,4 +122,21 @@ pub fn init_logger(log_file: &str, enable: bool) {
         .filter_level(LevelFilter::Info)
         .target(Target::Stdout)
         .init();
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warnings_and_errors_are_counted() {
+        // No logger is installed in the tests, so only this test counts
+        let before = warning_count();
+        count_warning(Level::Info);
+        count_warning(Level::Debug);
+        assert_eq!(warning_count(), before);
+
+        count_warning(Level::Warn);
+        count_warning(Level::Error);
+        assert_eq!(warning_count(), before + 2);
+    }
+}
@@ -1,20 +1,47 @@
 // External crates
-use log::info;
+use log::{info, warn};
 use clap::Parser;
 
+// Std library
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::Path;
+use std::time::Duration;
+
 // Local modules / crates
 use crate::data_set::Rawdata;
-use crate::statistics::Statistics;
-use cli::Cli;
-use solution::Solution;
+use crate::distance_matrix::DistanceMatrix;
+use crate::error::TtpError;
+use crate::input_list::read_input_list;
+use crate::manifest::RunManifest;
+use crate::naming::NameTemplate;
+use crate::results::{save_comparison_csv, save_results_csv, save_run_summary_csv, BaselineComparison, MethodComparison, RunFailure, RunResult, SolutionResult};
+use crate::statistics::{Statistics, StatsSummary};
+use cli::{Cli, Command};
+use solution::{save_to_file, ConstructionMethod, EvaluationOptions, GenerationOptions, Solution, SolutionKey, ETA_WARMUP};
 use xml_manager::XmlManager;
 
 mod xml_manager;
 mod data_set;
+mod distance_matrix;
 mod solution;
 mod logging;
 mod statistics;
 mod cli;
+mod manifest;
+mod naming;
+mod results;
+mod violation_report;
+mod error;
+mod input_list;
+mod profiling;
+#[cfg(test)]
+mod test_support;
+
+/// Maximum number of solution pairs compared by `--diversity`.
+const DIVERSITY_PAIRS: usize = 1000;
 
 fn main() {
 
@@ -24,21 +51,845 @@ fn main() {
     info!("Logger initialized");
 
     info!("{:?}", args);
+    info!("Seed: {}", args.seed);
+
+    if args.profile {
+        profiling::enable();
+    }
+
+    run(&args);
+
+    if args.profile {
+        profiling::log_profile();
+    }
+
+    exit_on_error(warning_gate(args.fail_on_warning, logging::warning_count()));
+}
+
+/// Decides whether the run fails because of the warnings it emitted (`--fail-on-warning`).
+///
+/// # Arguments
+/// * `fail_on_warning` - Whether the warnings fail the run.
+/// * `count` - The number of warnings (and errors) logged during the run.
+///
+/// # Returns
+/// `Ok(())` if the run succeeds, or `TtpError::Validation` with the number of warnings.
+fn warning_gate(fail_on_warning: bool, count: usize) -> Result<(), TtpError> {
+    if fail_on_warning && count > 0 {
+        return Err(TtpError::Validation(format!("{} warning(s) emitted, failing the run (--fail-on-warning)", count)));
+    }
+    Ok(())
+}
+
+/// Runs the framework (or the requested subcommand) with the parsed command-line arguments.
+///
+/// # Arguments
+/// * `args` - A reference to the parsed command-line arguments.
+fn run(args: &Cli) {
+    Statistics::set_precision(args.stats_precision as usize);
+
+    if let Some(command) = &args.command {
+        run_command(command);
+        info!("Framework execution completed");
+        return;
+    }
+
+    if let Some(list) = &args.input_list {
+        run_input_list(args, list);
+    } else {
+        exit_on_error(run_instance(args));
+    }
 
+    info!("Framework execution completed");
+}
+
+/// Runs the pipeline on every instance of an `--input-list` file and writes the combined
+/// summary csv (`--input-list-csv`).
+///
+/// Each instance runs with the command-line arguments, with the seed, number of permutations
+/// and name of its line when given. Its solutions, permutations and statistics are written
+/// to a `<file stem>_<seed>` subdirectory of the output directories, which also holds its
+/// `--run-json` and `--results-csv` files. An instance that fails (unreadable file, `--strict`
+/// check, failed self-check, ...) is logged and recorded with its error in the summary, and
+/// the list goes on with the next instance.
+///
+/// # Arguments
+/// * `args` - A reference to the parsed command-line arguments.
+/// * `list` - The path of the list file (see `read_input_list`).
+fn run_input_list(args: &Cli, list: &str) {
+    let entries = exit_on_error(read_input_list(list));
+    info!("Input list: {} instances listed in {}", entries.len(), list);
+
+    let mut results = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        let mut entry_args = args.clone();
+        entry_args.input = Some(entry.path.clone());
+        entry_args.seed = entry.seed.unwrap_or(args.seed);
+        entry_args.permutations = entry.permutations.unwrap_or(args.permutations);
+        entry_args.name = entry.name.clone().or_else(|| args.name.clone());
+
+        let subdirectory = entry.output_name(entry_args.seed);
+        entry_args.output_solutions = format!("{}/{}", args.output_solutions, subdirectory);
+        entry_args.output_permutations = format!("{}/{}", args.output_permutations, subdirectory);
+        entry_args.output_statistics = format!("{}/{}", args.output_statistics, subdirectory);
+        let in_statistics = |path: &String| {
+            let file_name = Path::new(path).file_name().map_or_else(|| path.clone(), |name| name.to_string_lossy().into_owned());
+            format!("{}/{}", entry_args.output_statistics, file_name)
+        };
+        entry_args.run_json = args.run_json.as_ref().map(in_statistics);
+        entry_args.results_csv = args.results_csv.as_ref().map(in_statistics);
+
+        info!("Input list: running {} ({} of {}) with seed {}", entry.path, index + 1, entries.len(), entry_args.seed);
+        let outcome = (|| {
+            fs::create_dir_all(&entry_args.output_statistics)?;
+            if args.save {
+                fs::create_dir_all(&entry_args.output_solutions)?;
+                fs::create_dir_all(&entry_args.output_permutations)?;
+            }
+            run_instance(&entry_args)
+        })();
+        match outcome {
+            Ok(result) => results.extend(result.map(Ok)),
+            Err(error) => {
+                warn!("Input list: {} failed, continuing with the next instance: {}", entry.path, error);
+                results.push(Err(RunFailure {
+                    input: entry.path.clone(),
+                    seed: entry_args.seed,
+                    permutations: entry_args.permutations,
+                    error: error.to_string(),
+                }));
+            }
+        }
+    }
+
+    info!("Writing input list summary to {}", args.input_list_csv);
+    exit_on_error(save_run_summary_csv(&results, &args.input_list_csv));
+}
+
+/// Runs the pipeline on the instance of `--input`: generation, evaluation, exports and statistics.
+///
+/// # Arguments
+/// * `args` - A reference to the parsed command-line arguments.
+///
+/// # Returns
+/// The `RunResult` of the run, or `None` if the run only explained a solution (`--explain`)
+/// or compared methods (`--compare-methods`).
+fn run_instance(args: &Cli) -> Result<Option<RunResult>, TtpError> {
     info!("Loading instance file");
-    let raw_data_set : Rawdata = XmlManager::read_xml(&*args.input);
+    let mut raw_data_set : Rawdata = XmlManager::read_xml(args.input.as_deref().unwrap())?;
+    raw_data_set.resolve_instance_name(args.name.as_deref(), args.input.as_deref().unwrap());
+    info!("Instance metadata: {:?}", raw_data_set.metadata);
+    if !raw_data_set.metadata.game_mode.is_empty() && !raw_data_set.metadata.is_time_constrained() {
+        warn!("Instance compactness is '{}', but the schedules are built and evaluated as time-constrained (every team plays in every slot)", raw_data_set.metadata.game_mode);
+    }
+    if !raw_data_set.unsupported_constraints.is_empty() {
+        if args.strict_constraints {
+            return Err(TtpError::Validation(format!("Unsupported constraint types in instance: {:?}", raw_data_set.unsupported_constraints)));
+        }
+        warn!("Unsupported constraint types ignored: {:?}", raw_data_set.unsupported_constraints);
+    }
+    let bound_errors = raw_data_set.constraint_bound_errors();
+    if !bound_errors.is_empty() {
+        if args.strict_constraints {
+            return Err(TtpError::Validation(format!("Malformed constraints in instance: {}", bound_errors.join("; "))));
+        }
+        for error in &bound_errors {
+            warn!("{}, its violations are not meaningful", error);
+        }
+    }
+    let baseline = match args.baseline.as_deref() {
+        Some(path) => {
+            info!("Loading baseline run from {}", path);
+            Some(RunResult::load(path)?)
+        }
+        None => None,
+    };
+
+    if !args.disable_constraints.is_empty() {
+        info!("Disabled constraint families: {:?}", args.disable_constraints);
+    }
+    if let Some(window) = args.capacity_window {
+        info!("Capacity windows counted in {:?} for every constraint", window);
+    }
+    let mut evaluation = EvaluationOptions {
+        disabled_constraints: args.disable_constraints.clone(),
+        max_meetings: args.max_meetings as usize,
+        graduated_penalties: args.graduated_penalties,
+        ca2_group_pairs: args.ca2_group_pairs,
+        capacity_window: args.capacity_window,
+        partial_round_robin: false,
+    };
+
+    let mut manifest = RunManifest::new(&raw_data_set, args);
+    info!("Instance fingerprint: {}", manifest.instance_fingerprint);
+
+    let names = NameTemplate::new(&args.name_template, &raw_data_set.instance_name, args.seed, ConstructionMethod::Florian);
+
+    if !args.subset_teams.is_empty() {
+        if let Some(id) = args.subset_teams.iter().find(|id| !raw_data_set.teams.iter().any(|team| team.id == **id)) {
+            return Err(TtpError::Validation(format!("--subset-teams: team {} is not a team of instance {}", id, raw_data_set.instance_name)));
+        }
+        raw_data_set = raw_data_set.subset_teams(&args.subset_teams);
+        info!("Subset of teams {:?}: {} teams, {} slots", args.subset_teams, raw_data_set.n_teams(), raw_data_set.n_slots());
+    }
+
+    if args.single_rr {
+        raw_data_set.restrict_to_single_round_robin();
+        evaluation.max_meetings = 1;
+        info!("Single round-robin: generating and evaluating the first {} slots", raw_data_set.n_slots());
+    }
+
+    for constraint in &raw_data_set.capacity_constraints {
+        if constraint.c_intp as usize > raw_data_set.n_slots() {
+            warn!("Capacity constraint #{} has a window of {} slots but the instance has {} slots, it will be skipped", constraint.index, constraint.c_intp, raw_data_set.n_slots());
+        }
+    }
 
     info!("Generating traveling distance matrix");
-    let traveling_distance_matrix = Solution::generate_traveling_distance_matrix(&raw_data_set);
+    let traveling_distance_matrix = DistanceMatrix::for_data(&raw_data_set);
+    let missing_pairs = traveling_distance_matrix.missing_pairs();
+    if !missing_pairs.is_empty() {
+        if args.strict {
+            return Err(TtpError::Validation(format!("Missing distances in instance for {} team pairs: {:?}", missing_pairs.len(), missing_pairs)));
+        }
+        warn!("{} team pairs have no distance in the instance, their travel counts as 0: {:?}", missing_pairs.len(), missing_pairs);
+    }
+    let index_map = traveling_distance_matrix.index_map();
+    for venue in 0..index_map.n_venues() {
+        if index_map.teams_at(venue).len() > 1 {
+            info!("Teams {:?} share venue {}, they travel 0 between each other", index_map.teams_at(venue), venue);
+        }
+    }
+    let negative_distances = raw_data_set.negative_distance_errors();
+    if !negative_distances.is_empty() {
+        if args.strict {
+            return Err(TtpError::Validation(format!("Negative distances in instance: {}", negative_distances.join("; "))));
+        }
+        for error in &negative_distances {
+            warn!("{}, the total distances are not meaningful", error);
+        }
+    }
+    let zero_pairs = raw_data_set.zero_distance_pairs();
+    if !zero_pairs.is_empty() {
+        warn!("{} pairs of distinct teams have a distance of 0 (or an unparseable one): {:?}", zero_pairs.len(), zero_pairs);
+    }
+
+    if let Some(path) = &args.evaluate_csv {
+        info!("Loading schedule from {}", path);
+        let solution = Solution::from_opponents_csv(path, &raw_data_set)?;
+        print_solution_details(&raw_data_set, &evaluation, &solution);
+        return Ok(None);
+    }
+
+    let mut permutations = match &args.permutations_file {
+        Some(path) => {
+            info!("Loading permutations from {}", path);
+            Solution::load_permutations(path)?.permutations
+        }
+        None => {
+            info!("Generating permutations");
+            Solution::generate_random_permutations(&raw_data_set,args.permutations,args.seed,&args.output_permutations, args.save, &names)?
+        }
+    };
 
-    info!("Generating permutations");
-    let permutations = Solution::generate_random_permutations(&raw_data_set,args.permutations,args.seed,&*args.output_permutations, args.save);
+    if args.dedup_perms {
+        permutations = Solution::dedup_equivalent_permutations(&raw_data_set, &permutations, ConstructionMethod::Florian);
+    }
+
+    if let Some(id) = args.explain {
+        explain_solution(args, &raw_data_set, &evaluation, &permutations, &names, id)?;
+        return Ok(None);
+    }
+
+    if !args.compare_methods.is_empty() {
+        compare_methods(args, &raw_data_set, &evaluation, &traveling_distance_matrix, &permutations)?;
+        return Ok(None);
+    }
 
     info!("Generating solutions");
-    let (_, distances) = Solution::generate_all_solutions(&raw_data_set, &traveling_distance_matrix, permutations,&*args.output_solutions, args.save);
+    if !args.permutation_indices.is_empty() {
+        info!("Building only the permutations {:?} of {}", args.permutation_indices, permutations.len());
+    }
+    let options = GenerationOptions {
+        path: args.output_solutions.clone(),
+        save: args.save,
+        resume: args.resume,
+        method: ConstructionMethod::Florian,
+        only_distance: args.only_distance,
+        shuffle_seed: args.shuffle_within.then_some(args.seed),
+        tie_break_seed: args.random_tie_breaks.then_some(args.seed),
+        limit_per_permutation: args.limit_per_permutation.map(|limit| limit as usize),
+        sample_seed: args.seed,
+        permutation_indices: (!args.permutation_indices.is_empty()).then(|| args.permutation_indices.clone()),
+        progress_interval: args.progress_to_log,
+        timeout: args.timeout.map(Duration::from_secs_f64),
+        eta_warmup: args.eta.then_some(ETA_WARMUP),
+        compact_json: args.compact_json,
+        chunk_size: args.chunk_save.map(|size| size as usize),
+        tag: args.tag.clone(),
+        summary_only: args.summary_only,
+        split_by_feasibility: args.keep_infeasible_separate,
+        names: names.clone(),
+        evaluation: evaluation.clone(),
+        improvements: args.improve.clone(),
+    };
+    // The self-check rebuilds the pool from the exact permutations of the run
+    let check_permutations = args.self_check.then(|| permutations.clone());
+    let (mut solutions, mut distances, mut feasible) = Solution::generate_all_solutions(&raw_data_set, &traveling_distance_matrix, permutations, &options)?;
 
-    Statistics::generate_statistics(&distances);
+    if let Some(check_permutations) = check_permutations {
+        info!("Running reproducibility self-check");
+        if args.permutations_file.is_none() {
+            let regenerated = Solution::generate_random_permutations(&raw_data_set, args.permutations, args.seed, "", false, &names)?;
+            if regenerated != check_permutations {
+                return Err(TtpError::Validation(format!("Self-check failed: seed {} produced different permutations", args.seed)));
+            }
+        }
+        let check_options = GenerationOptions {
+            only_distance: args.only_distance,
+            shuffle_seed: options.shuffle_seed,
+            tie_break_seed: options.tie_break_seed,
+            limit_per_permutation: options.limit_per_permutation,
+            sample_seed: options.sample_seed,
+            permutation_indices: options.permutation_indices.clone(),
+            tag: options.tag.clone(),
+            summary_only: options.summary_only,
+            evaluation: evaluation.clone(),
+            improvements: options.improvements.clone(),
+            ..Default::default()
+        };
+        let (check_solutions, check_distances, _) = Solution::generate_all_solutions(&raw_data_set, &traveling_distance_matrix, check_permutations, &check_options)?;
 
-    info!("Framework execution completed");
+        let best = Solution::best_solution(&solutions, &distances);
+        let check_best = Solution::best_solution(&check_solutions, &check_distances);
+        if check_distances != distances || check_best != best {
+            return Err(TtpError::Validation(format!("Self-check failed: two runs with seed {} produced different solutions", args.seed)));
+        }
+        info!("Self-check passed: {} solutions reproduced identically", distances.len());
+    }
+
+    if let Some(slots) = args.eval_slots {
+        info!("Evaluating the first {} slots of each solution", slots);
+        solutions = solutions.iter().map(|solution| solution.truncate_slots(slots as usize)).collect();
+        // The meetings after the kept slots are dropped, so the pairs may meet fewer times
+        evaluation.partial_round_robin = true;
+        (distances, feasible) = solutions
+            .iter()
+            .map(|solution| {
+                let (distance, capacity, separation, round_robin) = Solution::evaluate_solution(&raw_data_set, &evaluation, &traveling_distance_matrix, solution);
+                (distance as i128, Solution::is_feasible(capacity, separation, round_robin))
+            })
+            .unzip();
+    }
+
+    if args.save && args.opponents_csv {
+        info!("Exporting opponents grids");
+        for solution in &solutions {
+            let file = names.file_name(&format!("opponents_{}", solution.id), Some(solution.id), "csv");
+            solution.save_opponents_csv(&format!("{}/{}", args.output_solutions, file), args.signed_opponents)?;
+        }
+    }
+
+    if args.save && args.heatmap_csv {
+        info!("Exporting travel heatmaps");
+        for solution in &solutions {
+            let file = names.file_name(&format!("heatmap_{}", solution.id), Some(solution.id), "csv");
+            solution.save_heatmap_csv(&raw_data_set, &traveling_distance_matrix, &format!("{}/{}", args.output_solutions, file))?;
+        }
+    }
+
+    if args.save && args.round_listing {
+        info!("Exporting round listings");
+        for solution in &solutions {
+            let file = names.file_name(&format!("rounds_{}", solution.id), Some(solution.id), "txt");
+            fs::write(format!("{}/{}", args.output_solutions, file), solution.to_round_listing(&raw_data_set))?;
+        }
+    }
+
+    if args.save && args.xml_solutions {
+        info!("Exporting RobinX solution files");
+        for (solution, distance) in solutions.iter().zip(&distances) {
+            let infeasibility = Solution::violation_report(&raw_data_set, &evaluation, solution).violations.len();
+            let file = names.file_name(&format!("solution_{}", solution.id), Some(solution.id), "xml");
+            XmlManager::write_solution_xml(&raw_data_set, solution, *distance, infeasibility, &format!("{}/{}", args.output_solutions, file))?;
+        }
+    }
+
+    if let Some(path) = &args.results_csv {
+        info!("Writing results to {}", path);
+        let results: Vec<SolutionResult> = solutions
+            .iter()
+            .map(|solution| SolutionResult::from_solution(&raw_data_set, &evaluation, &traveling_distance_matrix, solution))
+            .collect();
+        save_results_csv(&results, path, args.append_results)?;
+    }
+
+    let feasible_distances = Statistics::filter_feasible(&distances, &feasible);
+    if args.only_distance {
+        info!("Solutions generated: {} | Feasible solutions: not computed (--only-distance)", distances.len());
+    } else {
+        info!("Solutions generated: {} | Feasible solutions: {}", distances.len(), feasible_distances.len());
+    }
+
+    if raw_data_set.metadata.phased && !solutions.is_empty() {
+        let phased_violations: Vec<usize> = solutions.iter().map(|solution| solution.phased_violations(&raw_data_set)).collect();
+        info!(
+            "Phased instance: {} phased solutions | Fewest phased violations: {} | Most phased violations: {}",
+            phased_violations.iter().filter(|&&count| count == 0).count(),
+            phased_violations.iter().min().unwrap(),
+            phased_violations.iter().max().unwrap()
+        );
+    }
+
+    manifest.solutions = distances.len();
+    manifest.feasible_solutions = feasible_distances.len();
+    manifest.infeasible_solutions = feasible.iter().filter(|&&is_feasible| !is_feasible).count();
+    if args.save {
+        save_to_file(&manifest, &format!("{}/{}", args.output_solutions, names.file_name("manifest", None, "json")), false)?;
+    }
+
+    if let Some(sort_output) = &args.sort_output {
+        let key = match sort_output.as_str() {
+            "penalty" => SolutionKey::Penalty,
+            "breaks" => SolutionKey::Breaks,
+            "weighted" => SolutionKey::Weighted(args.lambda),
+            _ => SolutionKey::Distance,
+        };
+        info!("Renaming saved solutions by {} rank", sort_output);
+        Solution::rank_saved_solutions(
+            &solutions,
+            key,
+            &raw_data_set,
+            &evaluation,
+            &traveling_distance_matrix,
+            &args.output_solutions,
+            &names,
+        )?;
+    }
+
+    if args.stats_feasible_only {
+        info!("Computing statistics over feasible solutions only");
+    }
+    if args.only_distance {
+        info!("Computing statistics over distances only, constraint violations were not computed");
+    }
+    let stats_distances = if args.stats_feasible_only { &feasible_distances } else { &distances };
+
+    if stats_distances.is_empty() {
+        warn!("No solution to compute statistics on, skipping statistics");
+    } else if args.count_only {
+        let feasible_count = if args.only_distance { None } else { Some(feasible_distances.len()) };
+        Statistics::generate_count_statistics(stats_distances, feasible_count);
+    } else if args.approx_stats {
+        Statistics::generate_running_statistics(stats_distances);
+    } else {
+        let histogram_feasible = (args.histogram_feasibility && stats_distances.len() == feasible.len()).then_some(feasible.as_slice());
+        Statistics::generate_statistics(stats_distances, histogram_feasible, &args.output_statistics, &names.file_name("dist_histogram", None, "png"))?;
+        for format in &args.stats_output {
+            Statistics::save_summary_table(stats_distances, format, &args.output_statistics)?;
+        }
+    }
+
+    if (args.lambda != 0.0 || args.mu != 0.0) && !solutions.is_empty() {
+        let (best_objective, best_id) = solutions
+            .iter()
+            .map(|solution| (Solution::weighted_objective(&raw_data_set, &evaluation, &traveling_distance_matrix, solution, args.lambda, args.mu), solution.id))
+            .min_by(|a, b| a.0.total_cmp(&b.0))
+            .unwrap();
+        info!("Best weighted objective (lambda: {}, mu: {}): {} | Solution id: {}", args.lambda, args.mu, best_objective, best_id);
+    }
+
+    if args.verbose_eval && let Some((best_solution, best_distance)) = Solution::best_solution(&solutions, &distances) {
+        info!("Best solution: {} | Distance: {} | Hash: {:016x}", best_solution.id, best_distance, best_solution.content_hash());
+        info!("{}", Solution::violation_report(&raw_data_set, &evaluation, best_solution));
+    }
+
+    if args.analyze_breaks && !solutions.is_empty() {
+        info!("Analyzing breaks and trips");
+        let (breaks, trips) = Solution::analyze_breaks(&solutions);
+        Statistics::generate_named_statistics("Breaks", &breaks, &args.output_statistics, &names.file_name("breaks_histogram", None, "png"))?;
+        Statistics::generate_named_statistics("Longest Trip", &trips, &args.output_statistics, &names.file_name("trips_histogram", None, "png"))?;
+    }
+
+    if args.analyze_constraints && !solutions.is_empty() {
+        info!("Analyzing constraint violations");
+        let per_family = Solution::analyze_constraints(&raw_data_set, &evaluation, &solutions);
+        Statistics::generate_constraint_statistics(&per_family, &args.output_statistics)?;
+    }
+
+    if args.diversity
+        && let Some((mean, pairs)) = Solution::mean_pairwise_hamming(&solutions, DIVERSITY_PAIRS, args.seed)
+    {
+        let cells = raw_data_set.n_slots() * raw_data_set.n_teams();
+        info!(
+            "Diversity | Mean Hamming distance: {} over {} pairs ({}% of the {} cells)",
+            Statistics::format_float(mean),
+            pairs,
+            Statistics::format_float(mean * 100.0 / cells as f64),
+            cells
+        );
+    }
+
+    let baseline_comparison = baseline.and_then(|baseline| {
+        if baseline.manifest.instance_fingerprint != manifest.instance_fingerprint {
+            warn!("Baseline run was generated on a different instance ({}), the comparison may not be meaningful", baseline.manifest.instance_name);
+        }
+        let Some(baseline_statistics) = baseline.statistics else {
+            warn!("Baseline run has no statistics, skipping the baseline comparison");
+            return None;
+        };
+        if stats_distances.is_empty() {
+            return None;
+        }
+
+        let comparison = BaselineComparison::new(&baseline_statistics, &StatsSummary::from_data(stats_distances));
+        info!(
+            "Baseline comparison | Mean delta: {} | Median delta: {} | Best delta: {}",
+            Statistics::format_float(comparison.mean_delta),
+            Statistics::format_float(comparison.median_delta),
+            comparison.best_delta
+        );
+        info!(
+            "Baseline comparison | Difference of means 95% CI: [{}, {}] | {}",
+            Statistics::format_float(comparison.ci_low),
+            Statistics::format_float(comparison.ci_high),
+            if comparison.better {
+                "significantly shorter than the baseline"
+            } else if comparison.ci_low > 0.0 {
+                "significantly longer than the baseline"
+            } else {
+                "no significant difference"
+            }
+        );
+        Some(comparison)
+    });
+
+    // The violation summary is only written by --run-json, so the constraints are not analyzed without it
+    let per_family = if args.run_json.is_none() || args.only_distance {
+        Vec::new()
+    } else {
+        Solution::analyze_constraints(&raw_data_set, &evaluation, &solutions)
+    };
+    let mut run_result = RunResult::new(&manifest, stats_distances, &solutions, &distances, &per_family);
+    run_result.baseline = baseline_comparison;
+    if let Some(path) = &args.run_json {
+        save_to_file(&run_result, path, false)?;
+        info!("Run result saved to {}", path);
+    }
+
+    Ok(Some(run_result))
+}
+
+/// Generates the pool of each construction method requested by `--compare-methods` on the
+/// same permutations, logs their statistics and writes the comparison csv. With exactly two
+/// methods, both distance distributions are also drawn on `compare_dist_histogram.png`.
+///
+/// Solutions are not saved in this mode, since the ids of the methods would collide.
+///
+/// # Arguments
+/// * `args` - A reference to the parsed command-line arguments.
+/// * `raw_data_set` - A reference to the `Rawdata` of the instance.
+/// * `evaluation` - A reference to the `EvaluationOptions` of the run.
+/// * `traveling_distance_matrix` - A reference to the distance matrix of the instance.
+/// * `permutations` - The team permutations shared by all methods.
+fn compare_methods(args: &Cli, raw_data_set: &Rawdata, evaluation: &EvaluationOptions, traveling_distance_matrix: &DistanceMatrix, permutations: &[Vec<i32>]) -> Result<(), TtpError> {
+    let mut comparisons = Vec::new();
+    let mut pools: Vec<(ConstructionMethod, Vec<i128>)> = Vec::new();
+
+    for method in &args.compare_methods {
+        info!("Generating solutions with the {} method", method);
+        let options = GenerationOptions {
+            method: *method,
+            tie_break_seed: args.random_tie_breaks.then_some(args.seed),
+            summary_only: args.summary_only,
+            evaluation: evaluation.clone(),
+            improvements: args.improve.clone(),
+            ..Default::default()
+        };
+        let (solutions, distances, feasible) = Solution::generate_all_solutions(raw_data_set, traveling_distance_matrix, permutations.to_vec(), &options)?;
+        if solutions.is_empty() {
+            warn!("No solution generated with the {} method", method);
+            continue;
+        }
+
+        Statistics::generate_named_statistics(&format!("{} distance", method), &distances, &args.output_statistics, &format!("{}_dist_histogram.png", method))?;
+
+        let comparison = MethodComparison::from_pool(raw_data_set, *method, &solutions, &distances, &feasible);
+        info!("Method {} | Solutions: {} | Feasible rate: {} | Mean distance: {} | Best distance: {} | Mean breaks: {}",
+            method, comparison.solutions, comparison.feasible_rate, comparison.mean_distance, comparison.best_distance, comparison.mean_breaks);
+        comparisons.push(comparison);
+        pools.push((*method, distances));
+    }
+
+    if let [(method_a, distances_a), (method_b, distances_b)] = pools.as_slice() {
+        let (label_a, label_b) = (method_a.to_string(), method_b.to_string());
+        Statistics::plot_histogram_compare(distances_a, distances_b, (&label_a, &label_b), &format!("{}/compare_dist_histogram.png", args.output_statistics))?;
+    }
+
+    info!("Writing methods comparison to {}", args.compare_csv);
+    save_comparison_csv(&comparisons, &args.compare_csv)?;
+    Ok(())
+}
+
+/// Prints the details of a single solution requested by `--explain`.
+///
+/// The solution is loaded from `solution_<id>.json` (named after `names`) in the solutions directory (or its
+/// `feasible/` and `infeasible/` subdirectories) when it was saved, otherwise it is rebuilt
+/// from the permutations with `Solution::regenerate`. It is then evaluated and printed with
+/// its grid, distance, per-team distances, breaks, longest trip and violation report.
+///
+/// # Arguments
+/// * `args` - A reference to the parsed command-line arguments.
+/// * `raw_data_set` - A reference to the `Rawdata` of the instance.
+/// * `evaluation` - A reference to the `EvaluationOptions` of the run.
+/// * `permutations` - The permutations of the run.
+/// * `names` - A reference to the `NameTemplate` the solutions were saved with.
+/// * `id` - The id of the solution to explain.
+fn explain_solution(args: &Cli, raw_data_set: &Rawdata, evaluation: &EvaluationOptions, permutations: &[Vec<i32>], names: &NameTemplate, id: i32) -> Result<(), TtpError> {
+    let file = names.file_name(&format!("solution_{}", id), Some(id), "json");
+    let saved = ["", "feasible/", "infeasible/"]
+        .iter()
+        .map(|subdirectory| format!("{}/{}{}", args.output_solutions, subdirectory, file))
+        .find(|path| Path::new(path).exists());
+
+    let solution = match saved {
+        Some(path) => {
+            info!("Loading solution {} from {}", id, path);
+            let file = File::open(&path)?;
+            let solution: Solution = serde_json::from_reader(BufReader::new(file))?;
+            solution.validate(raw_data_set).map_err(TtpError::Validation)?;
+            solution
+        }
+        None => {
+            info!("Solution {} was not saved, regenerating it", id);
+            let tie_break_seed = args.random_tie_breaks.then_some(args.seed);
+            let mut solution = Solution::regenerate(raw_data_set, permutations, id, ConstructionMethod::Florian, tie_break_seed).ok_or_else(|| {
+                TtpError::Validation(format!("no solution with id {} (the pool has {} solutions)", id, 2 * raw_data_set.n_teams() * permutations.len()))
+            })?;
+            solution.improve(raw_data_set, &DistanceMatrix::for_data(raw_data_set), &args.improve);
+            solution
+        }
+    };
+
+    let solution = if args.relabel.is_empty() {
+        solution
+    } else {
+        let mut labels = args.relabel.clone();
+        labels.sort_unstable();
+        if !labels.iter().copied().eq(0..raw_data_set.n_teams() as i32) {
+            return Err(TtpError::Validation(format!("--relabel must be a permutation of 0..{}", raw_data_set.n_teams())));
+        }
+        info!("Relabeling the teams with {:?}", args.relabel);
+        solution.apply_permutation(&args.relabel)
+    };
+
+    print_solution_details(raw_data_set, evaluation, &solution);
+    Ok(())
+}
+
+/// Prints a solution with its grid, distance, per-team distances, breaks, longest trip and
+/// violation report (`--explain` and `--evaluate-csv`).
+///
+/// # Arguments
+/// * `raw_data_set` - A reference to the `Rawdata` of the instance.
+/// * `evaluation` - A reference to the `EvaluationOptions` of the run.
+/// * `solution` - A reference to the `Solution` to print.
+fn print_solution_details(raw_data_set: &Rawdata, evaluation: &EvaluationOptions, solution: &Solution) {
+    // The matrix built by the run is reused from the cache of `DistanceMatrix::for_data`
+    let (distance, capacity, separation, round_robin) = solution.evaluate_with(raw_data_set, evaluation);
+    let traveling_distance_matrix = DistanceMatrix::for_data(raw_data_set);
+
+    println!("{}", Solution::solution_to_string(solution, raw_data_set));
+    println!("Distance: {}", distance);
+    println!("Feasible: {}", Solution::is_feasible(capacity, separation, round_robin));
+    println!("Per-team distances: {:?}", solution.per_team_distances(&traveling_distance_matrix));
+    println!("Per-slot travel: {:?}", solution.per_slot_travel(&traveling_distance_matrix));
+    println!("Breaks: {}", solution.count_breaks());
+    println!("Longest trip: {}", solution.longest_trip());
+    println!("{}", Solution::violation_report(raw_data_set, evaluation, solution));
+}
+
+/// Runs a subcommand of the framework.
+///
+/// # Arguments
+/// * `command` - A reference to the parsed subcommand.
+fn run_command(command: &Command) {
+    match command {
+        Command::Permutations { input, count, seed, out } => {
+            info!("Loading instance file");
+            let raw_data_set : Rawdata = exit_on_error(XmlManager::read_xml(input));
+
+            info!("Generating permutations");
+            let permutations = exit_on_error(Solution::generate_random_permutations(&raw_data_set, *count, *seed, "", false, &NameTemplate::default()));
+            exit_on_error(Solution::save_permutations(&raw_data_set, &permutations, *seed, out));
+            info!("Saved {} permutations to {}", permutations.len(), out);
+        }
+        Command::EvaluateDir { instance, solutions_dir, results_csv, output_statistics } => {
+            info!("Loading instance file");
+            let raw_data_set : Rawdata = exit_on_error(XmlManager::read_xml(instance));
+            let traveling_distance_matrix = Solution::generate_traveling_distance_matrix(&raw_data_set);
+            if !traveling_distance_matrix.missing_pairs().is_empty() {
+                warn!("{} team pairs have no distance in the instance, their travel counts as 0", traveling_distance_matrix.missing_pairs().len());
+            }
+
+            // Solutions are read one file at a time, only their evaluation is kept
+            info!("Loading solutions from {}", solutions_dir);
+            let mut results: Vec<SolutionResult> = Vec::new();
+            let mut schedules = HashSet::new();
+            for solution in Solution::iter_solutions(solutions_dir, &raw_data_set) {
+                let solution = exit_on_error(solution);
+                let result = SolutionResult::from_solution(&raw_data_set, &EvaluationOptions::default(), &traveling_distance_matrix, &solution);
+                info!("Solution {} | Distance: {} | Feasible: {} | Hash: {:016x}", result.id, result.distance, result.feasible, solution.content_hash());
+                schedules.insert(solution.content_hash());
+                results.push(result);
+            }
+            results.sort_by_key(|result| result.id);
+
+            if schedules.len() < results.len() {
+                warn!("{} of the {} solutions repeat an earlier schedule", results.len() - schedules.len(), results.len());
+            }
+
+            info!("Writing results to {}", results_csv);
+            exit_on_error(save_results_csv(&results, results_csv, false));
+
+            let distances: Vec<i128> = results.iter().map(|result| result.distance as i128).collect();
+            let feasible_count = results.iter().filter(|result| result.feasible).count();
+            info!("Solutions evaluated: {} | Feasible solutions: {}", results.len(), feasible_count);
+
+            if distances.is_empty() {
+                warn!("No solution found in {}, skipping statistics", solutions_dir);
+            } else {
+                exit_on_error(Statistics::generate_statistics(&distances, None, output_statistics, "dist_histogram.png"));
+            }
+        }
+        Command::Merge { inputs, out, output_statistics } => {
+            let results: Vec<RunResult> = inputs
+                .iter()
+                .map(|path| {
+                    info!("Loading run result from {}", path);
+                    exit_on_error(RunResult::load(path))
+                })
+                .collect();
+
+            let fingerprints: HashSet<&str> = results.iter().map(|result| result.manifest.instance_fingerprint.as_str()).collect();
+            if fingerprints.len() > 1 {
+                warn!("The merged runs were generated on {} different instances", fingerprints.len());
+            }
+            let merged = exit_on_error(Statistics::merge_runs(&results));
+            info!(
+                "Merged {} runs | Solutions: {} | Mean: {} | Median: {} | Std Dev: {} | Min-Max: ({}, {})",
+                results.len(),
+                merged.count,
+                Statistics::format_float(merged.mean),
+                Statistics::format_float(merged.median),
+                Statistics::format_float(merged.std_dev),
+                merged.min,
+                merged.max
+            );
+
+            let distances: Vec<i128> = results.iter().flat_map(|result| result.distances.iter().copied()).collect();
+            if distances.len() == merged.count {
+                exit_on_error(Statistics::generate_statistics(&distances, None, output_statistics, "dist_histogram.png"));
+            } else {
+                warn!("Some runs do not include their distances, the median and quartiles are approximated and no histogram is drawn");
+            }
+
+            exit_on_error(save_to_file(&merged, out, false));
+            info!("Merged statistics saved to {}", out);
+        }
+    }
+}
+
+/// Unwraps the result of a framework step, or prints the error and exits with status 1.
+///
+/// # Arguments
+/// * `result` - The `Result` of the step.
+///
+/// # Returns
+/// The value of the step if it succeeded.
+fn exit_on_error<T, E: Display>(result: Result<T, E>) -> T {
+    result.unwrap_or_else(|error| {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{captured_logs, temp_path};
+
+    /// Runs the pipeline on `input` with the extra arguments, its statistics written to a
+    /// temporary directory, and returns its result with the messages it logged.
+    fn run_instance_on(input: &str, name: &str, extra_args: &[&str]) -> (Result<Option<RunResult>, TtpError>, Vec<String>) {
+        let directory = temp_path(name);
+        fs::create_dir_all(&directory).unwrap();
+        let mut arguments = vec!["ttpgen", "--input", input, "--permutations", "2", "--summary-only", "--output-statistics", &directory];
+        arguments.extend_from_slice(extra_args);
+        let args = Cli::try_parse_from(arguments).unwrap();
+
+        let mut result = None;
+        let logs = captured_logs(|| result = Some(run_instance(&args)));
+        fs::remove_dir_all(&directory).unwrap();
+        (result.unwrap(), logs)
+    }
+
+    #[test]
+    fn the_self_check_passes_on_the_deterministic_pipeline() {
+        let (result, logs) = run_instance_on("NL8.xml", "self_check", &["--self-check"]);
+        let result = result.unwrap().unwrap();
+        assert_eq!(result.manifest.solutions, 2 * 16);
+        assert_eq!(result.statistics.unwrap().count, 2 * 16);
+        assert!(logs.contains(&"Self-check passed: 32 solutions reproduced identically".to_string()));
+    }
+
+    #[test]
+    fn the_violation_report_is_logged_only_with_verbose_evaluation() {
+        let report_lines = |extra_args: &[&str]| {
+            let (result, logs) = run_instance_on("NL8.xml", &format!("verbose_eval{}", extra_args.len()), extra_args);
+            result.unwrap();
+            logs.into_iter().filter(|line| line.starts_with("Violation report: ")).collect::<Vec<String>>()
+        };
+
+        assert!(report_lines(&[]).is_empty());
+        // The Florian solutions of NL8 are feasible
+        assert_eq!(report_lines(&["--verbose-eval"]), vec!["Violation report: 0 capacity, 0 separation, 0 round-robin".to_string()]);
+    }
+
+    #[test]
+    fn a_warning_fails_the_run_only_with_fail_on_warning() {
+        let input = temp_path("zero_distance.xml");
+        let xml = fs::read_to_string("NL8.xml").unwrap();
+        fs::write(&input, xml.replace(r#"<distance dist="1020" team1="4" team2="2"/>"#, r#"<distance dist="0" team1="4" team2="2"/>"#)).unwrap();
+        let (result, logs) = run_instance_on(&input, "zero_distance_statistics", &["--fail-on-warning"]);
+        fs::remove_file(&input).unwrap();
+
+        assert!(result.unwrap().is_some());
+        assert!(logs.iter().any(|message| message.starts_with("1 pairs of distinct teams have a distance of 0")));
+        let count = logging::warning_count();
+        assert!(count > 0);
+        assert!(warning_gate(false, count).is_ok());
+        let error = warning_gate(true, count).unwrap_err().to_string();
+        assert_eq!(error, format!("Validation error: {} warning(s) emitted, failing the run (--fail-on-warning)", count));
+        assert!(warning_gate(true, 0).is_ok());
+    }
+
+    #[test]
+    fn the_window_warning_counts_the_slots_left_by_the_subset() {
+        let window_warnings = |logs: &[String]| logs.iter().filter(|message| message.contains("it will be skipped")).cloned().collect::<Vec<_>>();
+
+        let (result, logs) = run_instance_on("NL8.xml", "full_window_statistics", &[]);
+        assert!(result.is_ok());
+        assert!(window_warnings(&logs).is_empty());
 
+        let (result, logs) = run_instance_on("NL8.xml", "subset_window_statistics", &["--subset-teams", "0,1"]);
+        assert!(result.is_ok());
+        assert_eq!(
+            window_warnings(&logs),
+            vec![
+                "Capacity constraint #0 has a window of 4 slots but the instance has 2 slots, it will be skipped".to_string(),
+                "Capacity constraint #1 has a window of 4 slots but the instance has 2 slots, it will be skipped".to_string(),
+            ]
+        );
+    }
 }
@@ -0,0 +1,108 @@
+// External crates
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+
+// Local modules
+use crate::cli::Cli;
+use crate::data_set::Rawdata;
+use crate::violation_report::ConstraintFamily;
+
+/// Describes a framework execution so that runs can be compared afterwards.
+///
+/// The manifest records the input instance (including its fingerprint, so accidental
+/// input changes between runs can be detected), the generation parameters and the size
+/// of the generated pool. It is saved as `manifest.json` next to the solutions.
+///
+/// # Fields
+/// * `instance_name` - The name of the problem instance.
+/// * `instance_file` - The path of the XML instance file.
+/// * `instance_fingerprint` - The SHA-256 fingerprint of the instance (see `Rawdata::fingerprint`).
+/// * `seed` - The seed used for generating the permutations.
+/// * `permutations` - The number of permutations requested.
+/// * `solutions` - The number of generated solutions.
+/// * `feasible_solutions` - The number of generated solutions without hard violations.
+/// * `infeasible_solutions` - The number of generated solutions with hard violations.
+/// * `split_by_feasibility` - True if the solutions were saved in the `feasible/` and
+///   `infeasible/` subdirectories (`--keep-infeasible-separate`), holding
+///   `feasible_solutions` and `infeasible_solutions` files.
+/// * `disabled_constraints` - The constraint families skipped by the evaluation (`--disable-constraints`).
+/// * `tag` - The free-form label of the run (`--tag`), if any.
+/// * `timestamp` - Local date and time at which the manifest was created.
+///
+/// # Example
+/// ```
+/// let mut manifest = RunManifest::new(&raw_data, &args);
+/// manifest.solutions = distances.len();
+/// save_to_file(&manifest, "solutions/manifest.json", false).unwrap();
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RunManifest {
+    pub instance_name: String,
+    pub instance_file: String,
+    pub instance_fingerprint: String,
+    pub seed: u64,
+    pub permutations: i32,
+    pub solutions: usize,
+    pub feasible_solutions: usize,
+    #[serde(default)]
+    pub infeasible_solutions: usize,
+    #[serde(default)]
+    pub split_by_feasibility: bool,
+    #[serde(default)]
+    pub disabled_constraints: Vec<ConstraintFamily>,
+    #[serde(default)]
+    pub tag: Option<String>,
+    pub timestamp: String,
+}
+
+impl RunManifest {
+    /// Creates a new `RunManifest` from the parsed instance and the command-line arguments.
+    ///
+    /// The pool counters (`solutions`, `feasible_solutions`, `infeasible_solutions`) are
+    /// initialized to `0` and are expected to be filled once generation is completed.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` of the instance being solved.
+    /// * `args` - A reference to the parsed command-line arguments.
+    ///
+    /// # Returns
+    /// A `RunManifest` describing the run.
+    pub fn new(data: &Rawdata, args: &Cli) -> Self {
+        Self {
+            instance_name: data.instance_name.clone(),
+            instance_file: args.input.clone().unwrap_or_default(),
+            instance_fingerprint: data.fingerprint(),
+            seed: args.seed,
+            permutations: args.permutations,
+            solutions: 0,
+            feasible_solutions: 0,
+            infeasible_solutions: 0,
+            split_by_feasibility: args.keep_infeasible_separate,
+            disabled_constraints: args.disable_constraints.clone(),
+            tag: args.tag.clone(),
+            timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use crate::xml_manager::XmlManager;
+
+    #[test]
+    fn the_tag_of_the_run_is_recorded_in_the_manifest() {
+        let data = XmlManager::read_xml("NL8.xml").unwrap();
+        let tagged = Cli::try_parse_from(["ttpgen", "--input", "NL8.xml", "--tag", "exp-1"]).unwrap();
+        let manifest = RunManifest::new(&data, &tagged);
+
+        assert_eq!(manifest.tag.as_deref(), Some("exp-1"));
+        let json = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(json["tag"], "exp-1");
+        assert_eq!(serde_json::from_value::<RunManifest>(json).unwrap(), manifest);
+
+        let untagged = Cli::try_parse_from(["ttpgen", "--input", "NL8.xml"]).unwrap();
+        assert_eq!(RunManifest::new(&data, &untagged).tag, None);
+    }
+}
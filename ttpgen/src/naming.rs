@@ -0,0 +1,148 @@
+// Local modules
+use crate::solution::ConstructionMethod;
+
+/// Placeholders accepted by `--name-template`.
+pub const PLACEHOLDERS: [&str; 5] = ["name", "instance", "seed", "id", "method"];
+
+/// Template reproducing the default output file names.
+pub const DEFAULT_NAME_TEMPLATE: &str = "{name}";
+
+/// Builds the names of the output files from a `--name-template`.
+///
+/// The template is applied to the stem of each output file (e.g. `solution_12`, `chunk_3`,
+/// `permutation` or `dist_histogram`), the extension is kept. Its placeholders are:
+/// * `{name}` - The default stem of the file.
+/// * `{instance}` - The name of the instance.
+/// * `{seed}` - The seed of the run.
+/// * `{id}` - The id of the solution, empty for files that do not hold a single solution.
+/// * `{method}` - The construction method (`florian`, `circle`).
+///
+/// # Example
+/// ```
+/// let names = NameTemplate::new("{instance}_s{seed}_{name}", "NL8", 42, ConstructionMethod::Florian);
+/// assert_eq!(names.file_name("solution_12", Some(12), "json"), "NL8_s42_solution_12.json");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NameTemplate {
+    template: String,
+    instance: String,
+    seed: u64,
+    method: ConstructionMethod,
+}
+
+impl Default for NameTemplate {
+    fn default() -> Self {
+        Self {
+            template: DEFAULT_NAME_TEMPLATE.to_string(),
+            instance: String::new(),
+            seed: 0,
+            method: ConstructionMethod::default(),
+        }
+    }
+}
+
+impl NameTemplate {
+    /// Creates the file names of a run.
+    ///
+    /// # Arguments
+    /// * `template` - The template, checked with `NameTemplate::validate`.
+    /// * `instance` - The name of the instance.
+    /// * `seed` - The seed of the run.
+    /// * `method` - The construction method of the run.
+    ///
+    /// # Returns
+    /// A `NameTemplate` rendering the file names of the run.
+    pub fn new(template: &str, instance: &str, seed: u64, method: ConstructionMethod) -> Self {
+        Self {
+            template: template.to_string(),
+            instance: instance.to_string(),
+            seed,
+            method,
+        }
+    }
+
+    /// Checks that a template only uses known placeholders.
+    ///
+    /// Braces must be balanced and every `{...}` must be one of `PLACEHOLDERS`. The template
+    /// must contain `{name}`, so every output keeps a distinct name and the solution files can
+    /// still be recognized when they are read back.
+    ///
+    /// # Arguments
+    /// * `template` - The template to check.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or an error message describing the problem.
+    pub fn validate(template: &str) -> Result<(), String> {
+        let mut rest = template;
+        let mut has_name = false;
+
+        while let Some(open) = rest.find(['{', '}']) {
+            if rest[open..].starts_with('}') {
+                return Err(format!("unbalanced '}}' in name template '{}'", template));
+            }
+            let Some(close) = rest[open..].find('}') else {
+                return Err(format!("unbalanced '{{' in name template '{}'", template));
+            };
+            let placeholder = &rest[open + 1..open + close];
+            if !PLACEHOLDERS.contains(&placeholder) {
+                return Err(format!(
+                    "unknown placeholder '{{{}}}' in name template '{}' (expected one of {})",
+                    placeholder,
+                    template,
+                    PLACEHOLDERS.map(|known| format!("{{{}}}", known)).join(", ")
+                ));
+            }
+            has_name |= placeholder == "name";
+            rest = &rest[open + close + 1..];
+        }
+
+        if !has_name {
+            return Err(format!("name template '{}' must contain {{name}}", template));
+        }
+        Ok(())
+    }
+
+    /// Renders the name of an output file.
+    ///
+    /// # Arguments
+    /// * `name` - The default stem of the file (e.g. `solution_12`).
+    /// * `id` - The id of the solution held by the file, if any.
+    /// * `extension` - The extension of the file, without the dot.
+    ///
+    /// # Returns
+    /// The file name (`String`), `<name>.<extension>` with the default template.
+    pub fn file_name(&self, name: &str, id: Option<i32>, extension: &str) -> String {
+        let stem = self
+            .template
+            .replace("{instance}", &self.instance)
+            .replace("{seed}", &self.seed.to_string())
+            .replace("{id}", &id.map(|id| id.to_string()).unwrap_or_default())
+            .replace("{method}", &self.method.to_string())
+            .replace("{name}", name);
+        format!("{}.{}", stem, extension)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_substituted_in_the_file_names() {
+        assert_eq!(NameTemplate::default().file_name("solution_12", Some(12), "json"), "solution_12.json");
+
+        let names = NameTemplate::new("{instance}_{method}_s{seed}_{name}_{id}", "NL8", 42, ConstructionMethod::Circle);
+        assert_eq!(names.file_name("solution_12", Some(12), "json"), "NL8_circle_s42_solution_12_12.json");
+        assert_eq!(names.file_name("dist_histogram", None, "png"), "NL8_circle_s42_dist_histogram_.png");
+    }
+
+    #[test]
+    fn templates_with_unknown_placeholders_or_without_the_name_are_rejected() {
+        assert!(NameTemplate::validate(DEFAULT_NAME_TEMPLATE).is_ok());
+        assert!(NameTemplate::validate("{instance}_{name}").is_ok());
+        assert!(NameTemplate::validate("{instance}_{nmae}").unwrap_err().contains("unknown placeholder '{nmae}'"));
+        assert!(NameTemplate::validate("{instance}").unwrap_err().contains("must contain {name}"));
+        assert!(NameTemplate::validate("{name").unwrap_err().contains("unbalanced '{'"));
+        assert!(NameTemplate::validate("name}").unwrap_err().contains("unbalanced '}'"));
+    }
+}
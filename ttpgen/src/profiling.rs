@@ -0,0 +1,193 @@
+// Std library
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+// External crates
+use log::info;
+
+/// Whether the scoped timers record their durations (`--profile`).
+static PROFILING: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// Total time and number of calls recorded for each scope of the current thread.
+    static SCOPES: RefCell<HashMap<&'static str, ScopeTiming>> = RefCell::new(HashMap::new());
+    /// Scopes currently entered on the current thread, the innermost last, with the time
+    /// spent so far in their nested scopes.
+    static STACK: RefCell<Vec<(&'static str, Duration)>> = const { RefCell::new(Vec::new()) };
+    /// Self time (without the nested scopes) recorded for each call stack of the current
+    /// thread, keyed by the `;`-separated scope names from the outermost one.
+    static STACKS: RefCell<HashMap<String, Duration>> = RefCell::new(HashMap::new());
+}
+
+/// Time spent in a profiled scope.
+///
+/// # Fields
+/// * `total` - The total time spent in the scope over all its calls.
+/// * `calls` - The number of times the scope was entered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScopeTiming {
+    pub total: Duration,
+    pub calls: u64,
+}
+
+/// Turns the scoped timers on, they record nothing until this is called.
+pub fn enable() {
+    PROFILING.store(true, Ordering::Relaxed);
+}
+
+/// Measures the time spent in a scope, from its creation until it is dropped.
+///
+/// The duration is added to the total of the scope name when the timer is dropped. The timers
+/// alive on the thread form a stack, so the time is also recorded under the call stack of the
+/// scope (see `format_profile_report`). When profiling is not enabled, creating a timer does
+/// not even read the clock.
+///
+/// # Example
+/// ```
+/// fn evaluate() {
+///     let _timer = ScopedTimer::new("evaluate");
+///     // ...
+/// }
+/// ```
+pub struct ScopedTimer {
+    scope: &'static str,
+    start: Option<Instant>,
+}
+
+impl ScopedTimer {
+    /// Starts a timer for a scope.
+    ///
+    /// # Arguments
+    /// * `scope` - The name under which the duration is recorded.
+    ///
+    /// # Returns
+    /// A `ScopedTimer` recording its scope when dropped.
+    pub fn new(scope: &'static str) -> Self {
+        let start = PROFILING.load(Ordering::Relaxed).then(Instant::now);
+        if start.is_some() {
+            STACK.with(|stack| stack.borrow_mut().push((scope, Duration::ZERO)));
+        }
+        Self { scope, start }
+    }
+}
+
+impl Drop for ScopedTimer {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            let elapsed = start.elapsed();
+            SCOPES.with(|scopes| {
+                let mut scopes = scopes.borrow_mut();
+                let timing = scopes.entry(self.scope).or_default();
+                timing.total += elapsed;
+                timing.calls += 1;
+            });
+
+            STACK.with(|stack| {
+                let mut stack = stack.borrow_mut();
+                let path: Vec<&str> = stack.iter().map(|(scope, _)| *scope).collect();
+                let nested = stack.pop().map_or(Duration::ZERO, |(_, nested)| nested);
+                if let Some((_, parent_nested)) = stack.last_mut() {
+                    *parent_nested += elapsed;
+                }
+                STACKS.with(|stacks| *stacks.borrow_mut().entry(path.join(";")).or_default() += elapsed.saturating_sub(nested));
+            });
+        }
+    }
+}
+
+/// Returns the timings recorded on the current thread, the most expensive scope first.
+///
+/// # Returns
+/// A vector of `(scope, timing)` pairs, sorted by decreasing total time (then by name).
+pub fn profile_report() -> Vec<(&'static str, ScopeTiming)> {
+    let mut report: Vec<(&'static str, ScopeTiming)> =
+        SCOPES.with(|scopes| scopes.borrow().iter().map(|(scope, timing)| (*scope, *timing)).collect());
+    report.sort_by(|a, b| b.1.total.cmp(&a.1.total).then(a.0.cmp(b.0)));
+    report
+}
+
+/// Formats the recorded timings in the folded stack format read by flamegraph tools.
+///
+/// Each line is `<outer>;<inner> <microseconds>`: the scopes of a call stack from the
+/// outermost one, and the self time spent in the innermost scope, without its nested
+/// scopes. A nested scope is thus counted once, and the values of all the lines add up to
+/// the time spent in the outermost scopes. The lines are sorted by decreasing time (then
+/// by stack).
+///
+/// # Returns
+/// The report (`String`), empty if nothing was recorded.
+///
+/// # Example
+/// Example output:
+/// ```text
+/// generate_all_solutions;log_solution;check_constraints 20417
+/// generate_all_solutions;save_to_file 9875
+/// generate_all_solutions 1230
+/// ```
+pub fn format_profile_report() -> String {
+    let mut stacks: Vec<(String, Duration)> =
+        STACKS.with(|stacks| stacks.borrow().iter().map(|(path, time)| (path.clone(), *time)).collect());
+    stacks.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    stacks
+        .iter()
+        .map(|(path, time)| format!("{} {}\n", path, time.as_micros()))
+        .collect()
+}
+
+/// Logs the total time, the number of calls and the mean time per call of each scope.
+pub fn log_profile() {
+    let report = profile_report();
+    if report.is_empty() {
+        info!("Profile: no scope recorded");
+        return;
+    }
+
+    info!("Profile (total | calls | mean per call):");
+    for (scope, timing) in &report {
+        info!(
+            "  {}: {:.3} ms | {} | {:.3} ms",
+            scope,
+            timing.total.as_secs_f64() * 1000.0,
+            timing.calls,
+            timing.total.as_secs_f64() * 1000.0 / timing.calls as f64
+        );
+    }
+    info!("Profile (folded):\n{}", format_profile_report().trim_end());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn nested_scopes_are_folded_under_their_caller() {
+        enable();
+        {
+            let _outer = ScopedTimer::new("outer");
+            thread::sleep(Duration::from_millis(2));
+            for _ in 0..2 {
+                let _inner = ScopedTimer::new("inner");
+                thread::sleep(Duration::from_millis(3));
+            }
+        }
+
+        let report = format_profile_report();
+        let lines: HashMap<&str, u128> = report
+            .lines()
+            .map(|line| {
+                let (path, micros) = line.rsplit_once(' ').unwrap();
+                (path, micros.parse().unwrap())
+            })
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines["outer;inner"] >= 6000);
+        assert!(lines["outer"] >= 2000 && lines["outer"] < lines["outer;inner"]);
+
+        let outer_total = profile_report().iter().find(|(scope, _)| *scope == "outer").unwrap().1.total.as_micros();
+        let folded_total: u128 = lines.values().sum();
+        assert!(outer_total - folded_total <= 1);
+    }
+}
@@ -0,0 +1,747 @@
+// Std library
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+// External crates
+use serde::{Deserialize, Serialize};
+
+// Local modules
+use crate::data_set::Rawdata;
+use crate::distance_matrix::DistanceMatrix;
+use crate::error::TtpError;
+use crate::manifest::RunManifest;
+use crate::solution::{ConstructionMethod, EvaluationOptions, Solution};
+use crate::statistics::{ConstraintSummary, Statistics, StatsSummary};
+use crate::violation_report::ConstraintFamily;
+
+/// Header of the results csv file. The column order of `SolutionResult::to_csv_row` must match it.
+pub const RESULTS_HEADER: &str =
+    "instance,id,distance,capacity_violations,separation_violations,round_robin_respect,feasible,tag";
+
+/// Evaluation results of a single solution, written as one row of the results csv.
+///
+/// # Fields
+/// * `instance_name` - The name of the problem instance.
+/// * `id` - The id of the evaluated solution.
+/// * `distance` - The total traveling distance of the solution.
+/// * `capacity_violations` - Total number of capacity constraint violations.
+/// * `separation_violations` - Total number of separation constraint violations.
+/// * `round_robin_respect` - True if all pairs of teams respect the round-robin.
+/// * `feasible` - True if the solution has no hard violations.
+/// * `tag` - The `tag` of the solution, empty if it has none.
+///
+/// # Example
+/// ```
+/// let result = SolutionResult::from_solution(&data, &EvaluationOptions::default(), &distance_matrix, &solution);
+/// println!("{}", result.to_csv_row());
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SolutionResult {
+    pub instance_name: String,
+    pub id: i32,
+    pub distance: i32,
+    pub capacity_violations: i32,
+    pub separation_violations: i32,
+    pub round_robin_respect: bool,
+    pub feasible: bool,
+    pub tag: String,
+}
+
+impl SolutionResult {
+    /// Evaluates a solution and stores its results.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    /// * `solution` - A reference to the `Solution` to evaluate.
+    ///
+    /// # Returns
+    /// A `SolutionResult` with the evaluation of the solution.
+    pub fn from_solution(
+        data: &Rawdata,
+        evaluation: &EvaluationOptions,
+        traveling_distance_matrix: &DistanceMatrix,
+        solution: &Solution,
+    ) -> Self {
+        let (distance, capacity_violations, separation_violations, round_robin_respect) =
+            Solution::evaluate_solution(data, evaluation, traveling_distance_matrix, solution);
+
+        Self {
+            instance_name: data.instance_name.clone(),
+            id: solution.id,
+            distance,
+            capacity_violations,
+            separation_violations,
+            round_robin_respect,
+            feasible: Solution::is_feasible(
+                capacity_violations,
+                separation_violations,
+                round_robin_respect,
+            ),
+            tag: solution.tag.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Formats the result as a csv row, following the column order of `RESULTS_HEADER`.
+    ///
+    /// # Returns
+    /// A `String` with the comma separated values (without line break).
+    pub fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{}",
+            self.instance_name,
+            self.id,
+            self.distance,
+            self.capacity_violations,
+            self.separation_violations,
+            self.round_robin_respect,
+            self.feasible,
+            self.tag
+        )
+    }
+}
+
+/// Saves a list of solution results to a csv file.
+///
+/// Without `append`, the file is overwritten with the header followed by one row per result.
+/// With `append`, rows are added at the end of an existing file: the header is written only
+/// if the file is missing or empty, and the existing header must match `RESULTS_HEADER`. Only
+/// the header line and the last byte of the existing file are read: a newline is added first
+/// if the file does not end with one, so the rows never continue its last line.
+///
+/// # Arguments
+/// * `results` - A slice of `SolutionResult` to write.
+/// * `path` - A string slice specifying the file path.
+/// * `append` - If `true`, append to an existing file instead of overwriting it.
+///
+/// # Returns
+/// A `Result` indicating success (`Ok(())`) or failure (`Err`) with an I/O error. An error
+/// of kind `InvalidData` is returned if the existing header does not match.
+///
+/// # Example
+/// ```
+/// save_results_csv(&results, "results.csv", true).expect("Failed to save results");
+/// ```
+pub fn save_results_csv(results: &[SolutionResult], path: &str, append: bool) -> std::io::Result<()> {
+    let mut write_header = true;
+    let mut missing_newline = false;
+
+    if append && Path::new(path).exists() {
+        let mut existing = File::open(path)?;
+        if let Some(header) = BufReader::new(&existing).lines().next() {
+            let header = header?;
+            if header.trim_end() != RESULTS_HEADER {
+                return Err(Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Header of {} does not match the results columns: {}", path, header),
+                ));
+            }
+            write_header = false;
+
+            let mut last = [0u8];
+            existing.seek(SeekFrom::End(-1))?;
+            existing.read_exact(&mut last)?;
+            missing_newline = last[0] != b'\n';
+        }
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)?;
+
+    let mut output = String::new();
+    if missing_newline {
+        output.push('\n');
+    }
+    if write_header {
+        output.push_str(RESULTS_HEADER);
+        output.push('\n');
+    }
+    for result in results {
+        output.push_str(&result.to_csv_row());
+        output.push('\n');
+    }
+
+    file.write_all(output.as_bytes())
+}
+
+/// Header of the methods comparison csv file. The column order of `MethodComparison::to_csv_row` must match it.
+pub const COMPARISON_HEADER: &str =
+    "instance,method,solutions,feasible_rate,mean_distance,best_distance,mean_breaks";
+
+/// Summary of the pool generated by one construction method, written as one row of the comparison csv.
+///
+/// # Fields
+/// * `instance_name` - The name of the problem instance.
+/// * `method` - The construction method that generated the pool.
+/// * `solutions` - The number of generated solutions.
+/// * `feasible_rate` - The fraction of solutions without hard violations (between 0 and 1).
+/// * `mean_distance` - The mean traveling distance of the pool.
+/// * `best_distance` - The shortest traveling distance of the pool.
+/// * `mean_breaks` - The mean number of breaks per solution.
+///
+/// # Example
+/// ```
+/// let comparison = MethodComparison::from_pool(&data, ConstructionMethod::Circle, &solutions, &distances, &feasible);
+/// println!("{}", comparison.to_csv_row());
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MethodComparison {
+    pub instance_name: String,
+    pub method: ConstructionMethod,
+    pub solutions: usize,
+    pub feasible_rate: f64,
+    pub mean_distance: f64,
+    pub best_distance: i128,
+    pub mean_breaks: f64,
+}
+
+impl MethodComparison {
+    /// Summarizes the pool generated by a construction method.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` of the instance.
+    /// * `method` - The `ConstructionMethod` that generated the pool.
+    /// * `solutions` - The generated solutions.
+    /// * `distances` - The traveling distance of each solution.
+    /// * `feasible` - The feasibility of each solution.
+    ///
+    /// # Returns
+    /// A `MethodComparison` with the summary of the pool.
+    ///
+    /// # Panics
+    /// This function will panic if the pool is empty.
+    pub fn from_pool(
+        data: &Rawdata,
+        method: ConstructionMethod,
+        solutions: &[Solution],
+        distances: &[i128],
+        feasible: &[bool],
+    ) -> Self {
+        let (breaks, _) = Solution::analyze_breaks(solutions);
+        let feasible_count = feasible.iter().filter(|is_feasible| **is_feasible).count();
+
+        Self {
+            instance_name: data.instance_name.clone(),
+            method,
+            solutions: solutions.len(),
+            feasible_rate: feasible_count as f64 / solutions.len() as f64,
+            mean_distance: Statistics::mean(distances),
+            best_distance: Statistics::min_max(distances).0,
+            mean_breaks: Statistics::mean(&breaks),
+        }
+    }
+
+    /// Formats the comparison as a csv row, following the column order of `COMPARISON_HEADER`.
+    ///
+    /// # Returns
+    /// A `String` with the comma separated values (without line break).
+    pub fn to_csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{}",
+            self.instance_name,
+            self.method,
+            self.solutions,
+            Statistics::format_float(self.feasible_rate),
+            Statistics::format_float(self.mean_distance),
+            self.best_distance,
+            Statistics::format_float(self.mean_breaks)
+        )
+    }
+}
+
+/// Saves the comparison of several construction methods to a csv file (overwritten).
+///
+/// # Arguments
+/// * `comparisons` - A slice of `MethodComparison`, one per method.
+/// * `path` - A string slice specifying the file path.
+///
+/// # Returns
+/// A `Result` indicating success (`Ok(())`) or failure (`Err`) with an I/O error.
+///
+/// # Example
+/// ```
+/// save_comparison_csv(&comparisons, "comparison.csv").expect("Failed to save comparison");
+/// ```
+pub fn save_comparison_csv(comparisons: &[MethodComparison], path: &str) -> std::io::Result<()> {
+    let mut output = String::from(COMPARISON_HEADER);
+    output.push('\n');
+    for comparison in comparisons {
+        output.push_str(&comparison.to_csv_row());
+        output.push('\n');
+    }
+
+    fs::write(path, output)
+}
+
+/// Id and traveling distance of the shortest solution of a run.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BestSolution {
+    pub id: i32,
+    pub distance: i128,
+}
+
+/// Machine-readable summary of a whole run, saved by `--run-json`.
+///
+/// # Fields
+/// * `manifest` - The `RunManifest` of the run (instance, parameters and pool counters).
+/// * `statistics` - The summary statistics of the distances, `None` if there was no value.
+/// * `distances` - The distances the statistics were computed on, kept so that several runs
+///   can be merged exactly (`Statistics::merge_runs`). Empty in files written before it existed.
+/// * `best_solution` - The shortest solution of the pool, `None` for an empty pool.
+/// * `constraint_violations` - The violation summary of each constraint family, empty if the
+///   constraints were not evaluated (`--only-distance`).
+/// * `baseline` - The comparison against the baseline run (`--baseline`), if any.
+///
+/// # Example
+/// ```
+/// let run_result = RunResult::new(&manifest, &distances, &solutions, &distances, &per_family);
+/// save_to_file(&run_result, "run.json", false).unwrap();
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct RunResult {
+    pub manifest: RunManifest,
+    pub statistics: Option<StatsSummary>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub distances: Vec<i128>,
+    pub best_solution: Option<BestSolution>,
+    pub constraint_violations: Vec<ConstraintSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline: Option<BaselineComparison>,
+}
+
+impl RunResult {
+    /// Assembles the summary of a run.
+    ///
+    /// # Arguments
+    /// * `manifest` - A reference to the `RunManifest` of the run, with its counters filled.
+    /// * `stats_distances` - The distances the statistics are computed on.
+    /// * `solutions` - The generated solutions.
+    /// * `distances` - The traveling distance of each solution, in the same order.
+    /// * `per_family` - The violations of each solution by family, as returned by
+    ///   `Solution::analyze_constraints` (families without values are skipped).
+    ///
+    /// # Returns
+    /// A `RunResult` describing the run.
+    pub fn new(
+        manifest: &RunManifest,
+        stats_distances: &[i128],
+        solutions: &[Solution],
+        distances: &[i128],
+        per_family: &[(ConstraintFamily, Vec<i128>)],
+    ) -> Self {
+        Self {
+            manifest: manifest.clone(),
+            statistics: (!stats_distances.is_empty()).then(|| StatsSummary::from_data(stats_distances)),
+            distances: stats_distances.to_vec(),
+            best_solution: Solution::best_solution(solutions, distances)
+                .map(|(solution, distance)| BestSolution { id: solution.id, distance }),
+            constraint_violations: per_family
+                .iter()
+                .filter(|(_, violations)| !violations.is_empty())
+                .map(|(family, violations)| ConstraintSummary::from_violations(*family, violations))
+                .collect(),
+            baseline: None,
+        }
+    }
+
+    /// Loads a run summary written by `--run-json`.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the json file.
+    ///
+    /// # Returns
+    /// A `Result` with the `RunResult` stored in the file, or a `TtpError` if the file
+    /// cannot be opened or deserialized.
+    ///
+    /// # Example
+    /// ```
+    /// let baseline = RunResult::load("baseline.json").unwrap();
+    /// println!("Baseline run on {}", baseline.manifest.instance_name);
+    /// ```
+    pub fn load(path: &str) -> Result<RunResult, TtpError> {
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+/// Header of the combined summary csv of `--input-list`. The column order of `RunResult::to_summary_row`
+/// and `RunFailure::to_summary_row` must match it.
+pub const RUN_SUMMARY_HEADER: &str =
+    "instance,input,seed,permutations,solutions,feasible_solutions,mean_distance,median_distance,std_dev,best_distance,best_id,error";
+
+impl RunResult {
+    /// Formats the run as a row of the combined summary csv, following the column order of
+    /// `RUN_SUMMARY_HEADER`. The statistics and best solution cells are empty for an empty pool,
+    /// and the error cell is always empty.
+    ///
+    /// # Returns
+    /// A `String` with the comma separated values (without line break).
+    pub fn to_summary_row(&self) -> String {
+        let (mean, median, std_dev) = self.statistics.as_ref().map_or_else(Default::default, |statistics| {
+            (
+                Statistics::format_float(statistics.mean),
+                Statistics::format_float(statistics.median),
+                Statistics::format_float(statistics.std_dev),
+            )
+        });
+        let (best_distance, best_id) = self
+            .best_solution
+            .as_ref()
+            .map_or_else(Default::default, |best| (best.distance.to_string(), best.id.to_string()));
+
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},",
+            self.manifest.instance_name,
+            self.manifest.instance_file,
+            self.manifest.seed,
+            self.manifest.permutations,
+            self.manifest.solutions,
+            self.manifest.feasible_solutions,
+            mean,
+            median,
+            std_dev,
+            best_distance,
+            best_id
+        )
+    }
+}
+
+/// Instance of an `--input-list` whose run failed.
+///
+/// # Fields
+/// * `input` - The path of the instance file.
+/// * `seed` - The seed of the run.
+/// * `permutations` - The number of permutations of the run.
+/// * `error` - The message of the error that stopped the run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunFailure {
+    pub input: String,
+    pub seed: u64,
+    pub permutations: i32,
+    pub error: String,
+}
+
+impl RunFailure {
+    /// Formats the failure as a row of the combined summary csv, following the column order of
+    /// `RUN_SUMMARY_HEADER`. Only the input, seed, permutations and error cells are filled, the
+    /// error being quoted.
+    ///
+    /// # Returns
+    /// A `String` with the comma separated values (without line break).
+    pub fn to_summary_row(&self) -> String {
+        format!(
+            ",{},{},{},,,,,,,,\"{}\"",
+            self.input,
+            self.seed,
+            self.permutations,
+            self.error.replace('"', "\"\"")
+        )
+    }
+}
+
+/// Saves the combined summary of the runs of an `--input-list` to a csv file (overwritten).
+///
+/// # Arguments
+/// * `results` - A slice with the `RunResult` of each completed run or the `RunFailure` of each
+///   failed one, in list order.
+/// * `path` - A string slice specifying the file path.
+///
+/// # Returns
+/// A `Result` indicating success (`Ok(())`) or failure (`Err`) with an I/O error.
+///
+/// # Example
+/// ```
+/// save_run_summary_csv(&results, "input_list_summary.csv").expect("Failed to save summary");
+/// ```
+pub fn save_run_summary_csv(results: &[Result<RunResult, RunFailure>], path: &str) -> std::io::Result<()> {
+    let mut output = String::from(RUN_SUMMARY_HEADER);
+    output.push('\n');
+    for result in results {
+        match result {
+            Ok(result) => output.push_str(&result.to_summary_row()),
+            Err(failure) => output.push_str(&failure.to_summary_row()),
+        }
+        output.push('\n');
+    }
+
+    fs::write(path, output)
+}
+
+/// Two-sided 95% quantile of the standard normal distribution, used by `BaselineComparison`.
+const CONFIDENCE_Z: f64 = 1.96;
+
+/// Comparison of the distances of a run against a baseline run (`--baseline`).
+///
+/// Deltas are `run - baseline`, so a negative delta means shorter distances. The difference
+/// of means comes with a 95% confidence interval from the normal approximation, using the
+/// sample variances of both runs.
+///
+/// # Fields
+/// * `mean_delta`, `median_delta` - The differences of the mean and median distances.
+/// * `best_delta` - The difference of the shortest distances.
+/// * `ci_low`, `ci_high` - The bounds of the confidence interval of `mean_delta`.
+/// * `better` - `true` if the whole interval is below `0`: the run is significantly shorter.
+///
+/// # Example
+/// ```
+/// let comparison = BaselineComparison::new(&baseline_summary, &summary);
+/// println!("Mean delta: {}", comparison.mean_delta);
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct BaselineComparison {
+    pub mean_delta: f64,
+    pub median_delta: f64,
+    pub best_delta: i128,
+    pub ci_low: f64,
+    pub ci_high: f64,
+    pub better: bool,
+}
+
+impl BaselineComparison {
+    /// Compares the distance statistics of a run against the ones of a baseline run.
+    ///
+    /// # Arguments
+    /// * `baseline` - The `StatsSummary` of the baseline run.
+    /// * `run` - The `StatsSummary` of the current run.
+    ///
+    /// # Returns
+    /// A `BaselineComparison` with the deltas and the confidence interval of the mean delta.
+    pub fn new(baseline: &StatsSummary, run: &StatsSummary) -> Self {
+        // StatsSummary holds the population variance, the interval uses the sample variance
+        let sample_variance = |summary: &StatsSummary| {
+            if summary.count > 1 {
+                summary.variance * summary.count as f64 / (summary.count - 1) as f64
+            } else {
+                0.0
+            }
+        };
+        let std_error = (sample_variance(baseline) / baseline.count as f64 + sample_variance(run) / run.count as f64).sqrt();
+
+        let mean_delta = run.mean - baseline.mean;
+        let (ci_low, ci_high) = (mean_delta - CONFIDENCE_Z * std_error, mean_delta + CONFIDENCE_Z * std_error);
+
+        Self {
+            mean_delta,
+            median_delta: run.median - baseline.median,
+            best_delta: run.min - baseline.min,
+            ci_low,
+            ci_high,
+            better: ci_high < 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{save_to_file, GenerationOptions};
+    use crate::test_support::temp_path;
+    use crate::xml_manager::XmlManager;
+
+    fn result(id: i32) -> SolutionResult {
+        SolutionResult {
+            instance_name: "NL8".to_string(),
+            id,
+            distance: 39721,
+            capacity_violations: 0,
+            separation_violations: 1,
+            round_robin_respect: true,
+            feasible: false,
+            tag: String::new(),
+        }
+    }
+
+    #[test]
+    fn results_are_appended_after_the_existing_rows() {
+        let path = temp_path("append.csv");
+        // Pre-seeded file whose last row has no trailing newline
+        fs::write(&path, format!("{}\n{}", RESULTS_HEADER, result(1).to_csv_row())).unwrap();
+
+        save_results_csv(&[result(2), result(3)], &path, true).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines, vec![RESULTS_HEADER.to_string(), result(1).to_csv_row(), result(2).to_csv_row(), result(3).to_csv_row()]);
+        assert!(content.ends_with('\n'));
+
+        fs::write(&path, "instance,id\n").unwrap();
+        let error = save_results_csv(&[result(2)], &path, true).unwrap_err();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn failure_row_follows_the_summary_header() {
+        let failure = RunFailure {
+            input: "missing.xml".to_string(),
+            seed: 42,
+            permutations: 10,
+            error: "I/O error: \"missing.xml\", not found".to_string(),
+        };
+        let row = failure.to_summary_row();
+
+        assert_eq!(row, ",missing.xml,42,10,,,,,,,,\"I/O error: \"\"missing.xml\"\", not found\"");
+        let (cells, error) = row.split_once('"').unwrap();
+        assert_eq!(cells.split(',').count(), RUN_SUMMARY_HEADER.split(',').count());
+        assert!(error.ends_with('"'));
+    }
+
+    #[test]
+    fn a_directory_of_solutions_is_scored_file_by_file() {
+        let data = XmlManager::read_xml("NL8.xml").unwrap();
+        let matrix = DistanceMatrix::from_rawdata(&data);
+        let permutation: Vec<i32> = (0..data.n_teams() as i32).collect();
+        let feasible = Solution::regenerate(&data, std::slice::from_ref(&permutation), 1, ConstructionMethod::Florian, None).unwrap();
+        let mut infeasible = Solution::regenerate(&data, &[permutation], 2, ConstructionMethod::Florian, None).unwrap();
+        // Team 0 plays every game at home
+        for row in &mut infeasible.solution {
+            let opponent = row[0].opponent as usize;
+            row[0].home_game = true;
+            row[opponent].home_game = false;
+        }
+
+        let directory = temp_path("evaluate_dir");
+        fs::create_dir_all(&directory).unwrap();
+        for solution in [&infeasible, &feasible] {
+            save_to_file(solution, &format!("{}/solution_{}.json", directory, solution.id), false).unwrap();
+        }
+        let results: Vec<SolutionResult> = Solution::iter_solutions(&directory, &data)
+            .map(|solution| SolutionResult::from_solution(&data, &EvaluationOptions::default(), &matrix, &solution.unwrap()))
+            .collect();
+        let csv = format!("{}/results.csv", directory);
+        save_results_csv(&results, &csv, false).unwrap();
+        let content = fs::read_to_string(&csv).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(results.iter().map(|result| (result.id, result.feasible)).collect::<Vec<_>>(), vec![(1, true), (2, false)]);
+        assert!(results[1].capacity_violations > 0);
+        assert_eq!(content.lines().count(), 3);
+        assert_eq!(content.lines().nth(1).unwrap(), results[0].to_csv_row());
+    }
+
+    #[test]
+    fn both_methods_generate_a_pool_of_every_permutation() {
+        let data = XmlManager::read_xml("NL8.xml").unwrap();
+        let matrix = DistanceMatrix::from_rawdata(&data);
+        let permutations = vec![(0..8).collect::<Vec<i32>>(), vec![7, 6, 5, 4, 3, 2, 1, 0]];
+        let mut comparisons = Vec::new();
+        for method in [ConstructionMethod::Florian, ConstructionMethod::Circle] {
+            let options = GenerationOptions { method, summary_only: true, ..Default::default() };
+            let (solutions, distances, feasible) = Solution::generate_all_solutions(&data, &matrix, permutations.clone(), &options).unwrap();
+            comparisons.push(MethodComparison::from_pool(&data, method, &solutions, &distances, &feasible));
+        }
+
+        let path = temp_path("compare.csv");
+        save_comparison_csv(&comparisons, &path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // Each team fixed with an upward and a downward pattern, for each permutation
+        assert!(comparisons.iter().all(|comparison| comparison.solutions == 2 * 8 * permutations.len()));
+        assert_eq!(comparisons.iter().map(|comparison| comparison.method).collect::<Vec<_>>(), vec![ConstructionMethod::Florian, ConstructionMethod::Circle]);
+        assert_eq!(content.lines().collect::<Vec<_>>(), vec![COMPARISON_HEADER.to_string(), comparisons[0].to_csv_row(), comparisons[1].to_csv_row()]);
+    }
+
+    #[test]
+    fn the_tag_of_the_run_is_written_in_the_results() {
+        let data = XmlManager::read_xml("NL8.xml").unwrap();
+        let matrix = DistanceMatrix::from_rawdata(&data);
+        let options = GenerationOptions { tag: Some("exp-1".to_string()), summary_only: true, ..Default::default() };
+        let (solutions, _, _) = Solution::generate_all_solutions(&data, &matrix, vec![(0..8).collect()], &options).unwrap();
+        let results: Vec<SolutionResult> = solutions
+            .iter()
+            .map(|solution| SolutionResult::from_solution(&data, &EvaluationOptions::default(), &matrix, solution))
+            .collect();
+
+        let path = temp_path("tagged.csv");
+        save_results_csv(&results, &path, false).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert!(RESULTS_HEADER.ends_with(",tag"));
+        assert_eq!(content.lines().count(), solutions.len() + 1);
+        assert!(content.lines().skip(1).all(|row| row.ends_with(",exp-1")));
+    }
+
+    /// The run result of the Florian pool of the identity permutation on NL8.
+    fn nl8_run_result() -> RunResult {
+        let data = XmlManager::read_xml("NL8.xml").unwrap();
+        let matrix = DistanceMatrix::from_rawdata(&data);
+        let options = GenerationOptions { summary_only: true, ..Default::default() };
+        let (solutions, distances, feasible) = Solution::generate_all_solutions(&data, &matrix, vec![(0..8).collect()], &options).unwrap();
+        let args = <crate::cli::Cli as clap::Parser>::try_parse_from(["ttpgen", "--input", "NL8.xml", "--permutations", "1"]).unwrap();
+        let mut manifest = RunManifest::new(&data, &args);
+        manifest.solutions = solutions.len();
+        manifest.feasible_solutions = feasible.iter().filter(|is_feasible| **is_feasible).count();
+        let per_family = Solution::analyze_constraints(&data, &EvaluationOptions::default(), &solutions);
+
+        RunResult::new(&manifest, &distances, &solutions, &distances, &per_family)
+    }
+
+    #[test]
+    fn the_run_json_has_the_summary_of_the_run() {
+        let run_result = nl8_run_result();
+        let path = temp_path("run.json");
+        save_to_file(&run_result, &path, false).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let loaded = RunResult::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let mut keys: Vec<&str> = json.as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(keys, ["best_solution", "constraint_violations", "distances", "manifest", "statistics"]);
+        assert_eq!(json["manifest"]["solutions"], 16);
+        assert_eq!(json["best_solution"]["distance"], run_result.distances.iter().min().copied().unwrap() as i64);
+        assert_eq!(json["constraint_violations"].as_array().unwrap().len(), 3);
+        assert_eq!(loaded, run_result);
+    }
+
+    #[test]
+    fn a_run_compared_against_itself_has_zero_deltas() {
+        let summary = nl8_run_result().statistics.unwrap();
+        let itself = BaselineComparison::new(&summary, &summary);
+        assert_eq!((itself.mean_delta, itself.median_delta, itself.best_delta), (0.0, 0.0, 0));
+        assert!(itself.ci_low < 0.0 && itself.ci_high > 0.0 && itself.ci_low == -itself.ci_high);
+        assert!(!itself.better);
+
+        let shorter = StatsSummary { mean: summary.mean - 1000.0, median: summary.median - 1000.0, min: summary.min - 1000, ..summary.clone() };
+        let comparison = BaselineComparison::new(&summary, &shorter);
+        assert_eq!((comparison.mean_delta, comparison.best_delta), (-1000.0, -1000));
+        assert!(comparison.better);
+    }
+
+    #[test]
+    fn merged_runs_are_summarized_as_one_pool() {
+        let first = nl8_run_result();
+        let mut second = first.clone();
+        second.distances = first.distances.iter().map(|distance| distance + 1000).collect();
+        second.statistics = Some(StatsSummary::from_data(&second.distances));
+        let all_distances: Vec<i128> = first.distances.iter().chain(&second.distances).copied().collect();
+
+        let merged = Statistics::merge_runs(&[first.clone(), second.clone()]).unwrap();
+        assert_eq!(merged.count, 32);
+        assert_eq!(merged, StatsSummary::from_data(&all_distances));
+
+        // Without the distances, the moments of the runs are combined
+        let (mut first, mut second) = (first, second);
+        first.distances.clear();
+        second.distances.clear();
+        let combined = Statistics::merge_runs(&[first.clone(), second.clone()]).unwrap();
+        assert_eq!((combined.count, combined.min, combined.max), (32, merged.min, merged.max));
+        assert!((combined.mean - merged.mean).abs() < 1e-9 && (combined.variance - merged.variance).abs() < 1e-6);
+
+        // Runs without statistics cannot be merged
+        first.statistics = None;
+        second.statistics = None;
+        let error = Statistics::merge_runs(&[first, second]).unwrap_err().to_string();
+        assert_eq!(error, "Validation error: None of the 2 runs has statistics to merge");
+    }
+}
@@ -1,33 +1,50 @@
 // Std library
-use std::collections::{HashMap, HashSet};
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
 use std::fs::{self, File};
 use std::hash::{Hash};
 use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 // External crates
+use chrono::Local;
 use indicatif::{ProgressBar, ProgressStyle};
-use log::info;
-use rand::SeedableRng;
+use log::{info, warn};
+use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
-use rand::seq::SliceRandom;
+use rand::seq::{index, SliceRandom};
 use serde::{Deserialize, Serialize};
 use serde_json::from_reader;
+use sha2::{Digest, Sha256};
 
 // Local modules
-use crate::data_set::{Rawdata, Team};
+use crate::data_set::{CapacityConstraints, CapacityWindow, ConstraintRef, Rawdata, Team, ALL_TEAMS_GROUP};
+use crate::distance_matrix::DistanceMatrix;
+use crate::error::TtpError;
+use crate::logging;
+use crate::naming::NameTemplate;
+use crate::profiling::ScopedTimer;
+use crate::statistics::Statistics;
+use crate::violation_report::{ConstraintFamily, Violation, ViolationReport};
 
 /// Saves any serializable data to a json file.
 ///
 /// This function serializes the provided data structure into a json format
 /// and writes it to the specified file path. It works with any type
-/// that implements `serde::Serialize`.
+/// that implements `serde::Serialize`. The json is pretty printed unless `compact`
+/// is set, both forms are read back the same way.
 ///
 /// # Arguments
 /// * `data` - A reference to the data to serialize and save.
 /// * `path` - A string slice specifying the file path.
+/// * `compact` - If `true`, the json is written on a single line without indentation.
 ///
 /// # Returns
-/// A `Result` indicating success (`Ok(())`) or failure (`Err`) with an I/O error.
+/// A `Result` indicating success (`Ok(())`) or failure (`Err`) with a `TtpError`
+/// (`Io` if the file cannot be created, `Json` if the data cannot be serialized).
 ///
 /// # Example
 /// ```
@@ -39,11 +56,16 @@ use crate::data_set::{Rawdata, Team};
 /// }
 ///
 /// let data = Example { id: 1, name: "Test".to_string() };
-/// save_to_file(&data, "output/example.json").expect("Failed to save file");
+/// save_to_file(&data, "output/example.json", false).expect("Failed to save file");
 /// ```
-pub fn save_to_file<T: Serialize>(data: &T, path: &str) -> std::io::Result<()> {
+pub fn save_to_file<T: Serialize>(data: &T, path: &str, compact: bool) -> Result<(), TtpError> {
+    let _timer = ScopedTimer::new("save_to_file");
     let file = File::create(path)?;
-    serde_json::to_writer_pretty(file, data)?;
+    if compact {
+        serde_json::to_writer(file, data)?;
+    } else {
+        serde_json::to_writer_pretty(file, data)?;
+    }
     Ok(())
 }
 
@@ -91,13 +113,135 @@ pub struct Game {
 ///     ],
 /// };
 /// ```
-#[derive(Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Permutations {
     pub seed: u64,
     pub instance_name: String,
     pub permutations: Vec<Vec<i32>>,
 }
 
+/// Construction method used to build a schedule from an ordered list of teams.
+///
+/// # Variants
+/// * `Florian` - Florian's method (`generate_florian_solution`): all pairings of a round share
+///   the same home/away orientation, which alternates every round.
+/// * `Circle` - The classic circle method with a mirrored second half (`generate_circle_solution`).
+///
+/// # Example
+/// ```
+/// let method: ConstructionMethod = "circle".parse().unwrap();
+/// assert_eq!(method.to_string(), "circle");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConstructionMethod {
+    #[default]
+    Florian,
+    Circle,
+}
+
+impl fmt::Display for ConstructionMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ConstructionMethod::Florian => "florian",
+            ConstructionMethod::Circle => "circle",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for ConstructionMethod {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "florian" => Ok(ConstructionMethod::Florian),
+            "circle" => Ok(ConstructionMethod::Circle),
+            other => Err(format!("Unknown construction method '{}' (expected florian or circle)", other)),
+        }
+    }
+}
+
+/// Improvement applied to each constructed schedule before it is evaluated and saved.
+///
+/// The improvements keep the pairings of every slot, so the schedule stays a valid
+/// round-robin. They are applied in the order they are listed (see `Solution::improve`).
+///
+/// # Variants
+/// * `Orientation` - Flips games to balance the home/away count of each team
+///   (`repair_orientation`).
+/// * `RoundSwaps` - Swaps whole slots while it shortens the total distance
+///   (`descend_round_swaps`).
+/// * `HomeAway` - Flips the orientation of the games of a pair of teams while it shortens
+///   the total distance (`optimize_home_away`).
+///
+/// # Example
+/// ```
+/// let improvement: Improvement = "orientation".parse().unwrap();
+/// assert_eq!(improvement.to_string(), "orientation");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Improvement {
+    Orientation,
+    RoundSwaps,
+    HomeAway,
+}
+
+impl fmt::Display for Improvement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Improvement::Orientation => "orientation",
+            Improvement::RoundSwaps => "round-swaps",
+            Improvement::HomeAway => "home-away",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Improvement {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "orientation" => Ok(Improvement::Orientation),
+            "round-swaps" => Ok(Improvement::RoundSwaps),
+            "home-away" => Ok(Improvement::HomeAway),
+            other => Err(format!("Unknown improvement '{}' (expected orientation, round-swaps or home-away)", other)),
+        }
+    }
+}
+
+/// Key used to rank the solutions of a pool (lower is better for every key).
+///
+/// * `Distance` - The total traveling distance.
+/// * `Penalty` - The penalty of the constraint violations (see `Solution::penalty_cost`).
+/// * `Breaks` - The number of breaks (see `Solution::count_breaks`).
+/// * `Weighted(lambda)` - The distance plus `lambda` times the penalty (see `Solution::weighted_objective`).
+///
+/// # Example
+/// ```
+/// Solution::sort_by_key(&mut solutions, SolutionKey::Weighted(1000.0), &data, &EvaluationOptions::default(), &matrix);
+/// assert_eq!(SolutionKey::Breaks.to_string(), "breaks");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SolutionKey {
+    Distance,
+    Penalty,
+    Breaks,
+    Weighted(f64),
+}
+
+impl fmt::Display for SolutionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SolutionKey::Distance => "dist",
+            SolutionKey::Penalty => "penalty",
+            SolutionKey::Breaks => "breaks",
+            SolutionKey::Weighted(_) => "weighted",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// A simple wrapper around `ProgressBar` for logging progress.
 ///
 /// # Example
@@ -111,8 +255,16 @@ pub struct Permutations {
 /// ```
 pub struct ProgressBarLog {
     bar: ProgressBar,
+    total: u64,
+    position: Cell<u64>,
+    log_interval: Option<u64>,
+    eta_warmup: Option<u64>,
+    started: Instant,
 }
 
+/// Number of solutions timed by `--eta` before the remaining time is projected.
+pub const ETA_WARMUP: u64 = 200;
+
 /// A simple wrapper around `ProgressBar` for logging progress.
 ///
 /// `ProgressBarLog` provides a convenient interface to create and manipulate a progress bar
@@ -145,12 +297,94 @@ impl ProgressBarLog {
                 )
                 .progress_chars("%>="),
         );
-        Self { bar }
+        Self { bar, total, position: Cell::new(0), log_interval: None, eta_warmup: None, started: Instant::now() }
+    }
+
+    /// Creates a new `ProgressBarLog` that also logs the progress every `interval` steps.
+    ///
+    /// The bar only draws on the terminal, so the `info!` lines keep the progress in the log
+    /// file of headless runs. The last step is always logged.
+    ///
+    /// # Arguments
+    /// * `total` - The total number of steps to complete.
+    /// * `interval` - The number of steps between two progress lines, `None` to never log.
+    ///
+    /// # Example
+    /// ```
+    /// let progress = ProgressBarLog::with_log_interval(1000, Some(100));
+    /// progress.inc();
+    /// ```
+    pub fn with_log_interval(total: u64, interval: Option<u64>) -> Self {
+        Self { log_interval: interval.filter(|interval| *interval > 0), ..Self::new(total) }
+    }
+
+    /// Enables a one-time estimate of the remaining time after `warmup` steps.
+    ///
+    /// Once `warmup` steps are done, the average time per step so far is projected over the
+    /// remaining steps and logged with the expected completion time. The bar keeps its own
+    /// display afterwards. Nothing is logged if the run has at most `warmup` steps.
+    ///
+    /// # Arguments
+    /// * `warmup` - The number of steps timed before estimating, `None` to never estimate.
+    ///
+    /// # Example
+    /// ```
+    /// let progress = ProgressBarLog::new(100_000).with_eta(Some(ETA_WARMUP));
+    /// progress.inc();
+    /// ```
+    pub fn with_eta(self, warmup: Option<u64>) -> Self {
+        Self { eta_warmup: warmup.filter(|warmup| *warmup > 0), started: Instant::now(), ..self }
     }
 
     /// Increments the progress bar by one step.
+    ///
+    /// With a log interval, a `processed X/Y (Z%)` line is logged every `interval` steps.
+    /// With an ETA warmup, the projected remaining time is logged once the warmup is done.
     pub fn inc(&self) {
         self.bar.inc(1);
+
+        let position = self.position.get() + 1;
+        self.position.set(position);
+
+        if let Some(line) = self.progress_line(position) {
+            info!("{}", line);
+        }
+
+        if let Some(line) = self.eta_line(position) {
+            info!("{}", line);
+        }
+    }
+
+    /// Returns the progress line logged after `position` steps, `None` between two intervals.
+    fn progress_line(&self, position: u64) -> Option<String> {
+        let interval = self.log_interval?;
+        (position.is_multiple_of(interval) || position == self.total).then(|| {
+            format!(
+                "Progress: processed {}/{} ({}%)",
+                position,
+                self.total,
+                (position * 100).checked_div(self.total).unwrap_or(100)
+            )
+        })
+    }
+
+    /// Returns the estimate of the remaining time logged once `position` reaches the warmup.
+    fn eta_line(&self, position: u64) -> Option<String> {
+        if self.eta_warmup != Some(position) || position >= self.total {
+            return None;
+        }
+
+        let elapsed = self.started.elapsed();
+        let remaining = elapsed.mul_f64((self.total - position) as f64 / position as f64);
+        let completion = Local::now() + chrono::Duration::from_std(remaining).unwrap_or_default();
+        Some(format!(
+            "ETA: ~{} remaining for {} solutions (estimated over the first {} in {}) | Expected completion: {}",
+            format_duration(remaining),
+            self.total - position,
+            position,
+            format_duration(elapsed),
+            completion.format("%Y-%m-%d %H:%M:%S")
+        ))
     }
 
     #[allow(dead_code)]
@@ -159,6 +393,18 @@ impl ProgressBarLog {
         self.bar.finish();
     }
 
+    /// Stops the progress bar before the total is reached.
+    ///
+    /// The length of the bar is set to the steps done so far, so the bar shows what was
+    /// processed instead of jumping to the total.
+    ///
+    /// # Arguments
+    /// * `msg` - The message displayed alongside the stopped bar.
+    pub fn stop(&self, msg: &str) {
+        self.bar.set_length(self.position.get());
+        self.bar.finish_with_message(msg);
+    }
+
     #[allow(dead_code)]
     /// Sets a custom message to display alongside the progress bar.
     ///
@@ -169,6 +415,20 @@ impl ProgressBarLog {
     }
 }
 
+/// Formats a duration as hours, minutes and seconds (`2h 05m 12s`, `4m 03s`, `17.3s`).
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+    if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}.{:01}s", seconds, duration.subsec_millis() / 100)
+    }
+}
+
 /// Represents a solution for the scheduling problem.
 ///
 /// Each `Solution` contains an `id` identifying the solution,
@@ -178,12 +438,14 @@ impl ProgressBarLog {
 /// # Fields
 /// * `id` - A unique identifier for the solution.
 /// * `solution` - A 2D vector of `Game` instances representing the schedule matrix.
+/// * `tag` - Free-form label of the run that generated the solution (`--tag`), not written if `None`.
 ///
 /// # Example
 /// ```
 /// let solution = Solution {
 ///     id: 1,
 ///     solution: vec![vec![Game { home_game: true, opponent: 2 }]],
+///     tag: None,
 /// };
 /// println!("Solution ID: {}", solution.id);
 /// ```
@@ -191,8 +453,178 @@ impl ProgressBarLog {
 pub struct Solution {
     pub id: i32,
     pub solution: Vec<Vec<Game>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+/// Number of meetings of each pair of teams in a double round-robin.
+pub const DEFAULT_MAX_MEETINGS: usize = 2;
+
+/// Runtime settings of the evaluation of a solution (see `Solution::violation_report`).
+///
+/// They come from the command line, not from the instance, so they are kept out of `Rawdata`
+/// and its fingerprint.
+///
+/// # Fields
+/// * `disabled_constraints` - The constraint families skipped by the evaluation.
+/// * `max_meetings` - The number of times each pair of teams is expected to meet
+///   (`DEFAULT_MAX_MEETINGS` by default).
+/// * `graduated_penalties` - If `true`, separation violations are weighted by their distance to
+///   the bound in `Solution::penalty_cost`.
+/// * `ca2_group_pairs` - If `true`, the CA2 constraints are evaluated with group-pair semantics
+///   (see `Solution::cross_group_games`) instead of per-team windows.
+/// * `capacity_window` - If set, the unit of the window of every capacity constraint, otherwise
+///   the `c_mode2` of each constraint decides (see `EvaluationOptions::window_counts_slots`).
+/// * `partial_round_robin` - If `true`, the round-robin check only requires that no pair meets
+///   more than `max_meetings` times, for the schedules truncated by `Solution::truncate_slots`.
+///
+/// # Example
+/// ```
+/// let evaluation = EvaluationOptions {
+///     max_meetings: 1,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvaluationOptions {
+    pub disabled_constraints: Vec<ConstraintFamily>,
+    pub max_meetings: usize,
+    pub graduated_penalties: bool,
+    pub ca2_group_pairs: bool,
+    pub capacity_window: Option<CapacityWindow>,
+    pub partial_round_robin: bool,
+}
+
+impl Default for EvaluationOptions {
+    fn default() -> Self {
+        Self {
+            disabled_constraints: Vec::new(),
+            max_meetings: DEFAULT_MAX_MEETINGS,
+            graduated_penalties: false,
+            ca2_group_pairs: false,
+            capacity_window: None,
+            partial_round_robin: false,
+        }
+    }
+}
+
+impl EvaluationOptions {
+    /// Returns `true` if a constraint family is evaluated, i.e. not listed in `disabled_constraints`.
+    ///
+    /// # Example
+    /// ```
+    /// let evaluation = EvaluationOptions { disabled_constraints: vec![ConstraintFamily::Separation], ..Default::default() };
+    /// assert!(!evaluation.constraint_enabled(ConstraintFamily::Separation));
+    /// ```
+    pub fn constraint_enabled(&self, family: ConstraintFamily) -> bool {
+        !self.disabled_constraints.contains(&family)
+    }
+
+    /// Returns `true` if the window of a capacity constraint counts consecutive slots, `false`
+    /// if it counts consecutive games of the team.
+    ///
+    /// The unit forced by `capacity_window` wins, otherwise the `c_mode2` of the constraint
+    /// decides (see `CapacityConstraints::counts_slots`).
+    ///
+    /// # Arguments
+    /// * `constraint` - A reference to the capacity constraint.
+    ///
+    /// # Example
+    /// ```
+    /// let raw_data = XmlManager::read_xml("NL8.xml").unwrap();
+    /// let evaluation = EvaluationOptions { capacity_window: Some(CapacityWindow::Slots), ..Default::default() };
+    /// assert!(evaluation.window_counts_slots(&raw_data.capacity_constraints[0]));
+    /// ```
+    pub fn window_counts_slots(&self, constraint: &CapacityConstraints) -> bool {
+        match self.capacity_window {
+            Some(CapacityWindow::Slots) => true,
+            Some(CapacityWindow::Games) => false,
+            None => constraint.counts_slots(),
+        }
+    }
+}
+
+/// Options of `Solution::generate_all_solutions`.
+///
+/// # Fields
+/// * `path` - The directory where solutions are saved (and looked up when resuming).
+/// * `save` - If `true`, each solution is saved as `solution_<id>.json` in `path`.
+/// * `resume` - If `true`, ids whose `solution_<id>.json` already exists in `path` are skipped
+///   (neither generated, evaluated nor returned). Ids are assigned deterministically, so an
+///   interrupted run can be continued with the same arguments.
+/// * `method` - The `ConstructionMethod` used to build each schedule.
+/// * `only_distance` - If `true`, only the traveling distance is evaluated: the constraint
+///   checks are skipped and the feasibility of the pool is not computed.
+/// * `shuffle_seed` - If set, the `(direction, fixed_team)` pairs of each permutation are
+///   visited in an order shuffled from this seed instead of upward first and `0..n`. The id of
+///   a solution still only depends on its permutation, direction and fixed team.
+/// * `tie_break_seed` - If set, the construction methods break their ties randomly, from a
+///   generator derived from this seed and the solution id (see `Solution::construction_rng`).
+///   Otherwise ties are broken the same way for every solution.
+/// * `limit_per_permutation` - If set, only `limit_per_permutation` of the `(direction, fixed_team)`
+///   pairs of each permutation are built, sampled from `sample_seed` (all pairs if it is not
+///   lower than `2 * n`). The sampled pairs keep their ids and their generation order.
+/// * `sample_seed` - The seed of the `limit_per_permutation` sampling.
+/// * `permutation_indices` - If set, only the permutations at these positions (from `0`) are
+///   built. The other permutations still draw their shuffled and sampled order, so the selected
+///   ones get the same ids, order and tie-breaks as in a run over every permutation.
+/// * `progress_interval` - If set, the progress is also logged every `progress_interval` solutions.
+/// * `timeout` - If set, the generation stops once `timeout` has elapsed. The solutions built so
+///   far are returned (and saved) as a complete pool.
+/// * `compact_json` - If `true` (with `save`), the solutions are saved as compact json.
+/// * `chunk_size` - If set (with `save`), the solutions are saved by batches of `chunk_size` in
+///   `chunk_<n>.json` files (a json array each, `n` from `1`) instead of one file per solution.
+///   Only one batch is kept in memory before being written.
+/// * `eta_warmup` - If set, the remaining time is estimated and logged once after `eta_warmup`
+///   solutions (see `ProgressBarLog::with_eta`).
+/// * `tag` - If set, the `tag` of every generated solution.
+/// * `summary_only` - If `true`, the construction, schedule and evaluation of each solution are
+///   not logged, only the phases, warnings and summary of the generation.
+/// * `split_by_feasibility` - If `true` (with `save`), each solution is saved in the `feasible/`
+///   or `infeasible/` subdirectory of `path` depending on its hard violations. Both
+///   subdirectories are created, and they are the ones looked up when resuming.
+/// * `names` - The `NameTemplate` of the saved solution and chunk files (default names by default).
+/// * `evaluation` - The `EvaluationOptions` of the constraint checks.
+/// * `improvements` - The improvements applied to each schedule after its construction, before
+///   it is evaluated and saved (see `Solution::improve`).
+///
+/// # Example
+/// ```
+/// let options = GenerationOptions {
+///     path: "solutions".to_string(),
+///     save: true,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GenerationOptions {
+    pub path: String,
+    pub save: bool,
+    pub resume: bool,
+    pub method: ConstructionMethod,
+    pub only_distance: bool,
+    pub shuffle_seed: Option<u64>,
+    pub tie_break_seed: Option<u64>,
+    pub limit_per_permutation: Option<usize>,
+    pub sample_seed: u64,
+    pub permutation_indices: Option<Vec<usize>>,
+    pub progress_interval: Option<u64>,
+    pub timeout: Option<Duration>,
+    pub eta_warmup: Option<u64>,
+    pub compact_json: bool,
+    pub chunk_size: Option<usize>,
+    pub tag: Option<String>,
+    pub summary_only: bool,
+    pub split_by_feasibility: bool,
+    pub names: NameTemplate,
+    pub evaluation: EvaluationOptions,
+    pub improvements: Vec<Improvement>,
 }
 
+/// Pool returned by `Solution::generate_all_solutions`: the generated solutions, their
+/// traveling distances and their feasibility, in the same order.
+pub type SolutionPool = (Vec<Solution>, Vec<i128>, Vec<bool>);
+
 impl Solution {
 
     /// Creates a new, empty `Solution` instance initialized with default game values.
@@ -205,8 +637,8 @@ impl Solution {
     /// # Arguments
     /// * `data` - A reference to the `Rawdata` structure containing teams and slots
     ///   information. The size of the solution matrix is derived from:
-    ///   - `data.teams.len()`
-    ///   - `data.slots.len()`
+    ///   - `data.n_teams()`
+    ///   - `data.n_slots()`
     ///
     /// # Returns
     /// A `Solution` struct with:
@@ -217,58 +649,50 @@ impl Solution {
     /// ```
     /// let data = Rawdata::generate_example();
     /// let solution = Solution::new(&data);
-    /// assert_eq!(solution.solution.len(), data.slots.len());
-    /// assert_eq!(solution.solution[0].len(), data.teams.len());
+    /// assert_eq!(solution.solution.len(), data.n_slots());
+    /// assert_eq!(solution.solution[0].len(), data.n_teams());
     /// ```
     pub fn new(data: &Rawdata) -> Solution {
         Solution {
             id: -1,
+            tag: None,
             solution: vec![
                 vec![
                     Game {
                         home_game: false,
                         opponent: -1
                     };
-                    data.teams.len()
+                    data.n_teams()
                 ];
-                data.slots.len()
+                data.n_slots()
             ],
         }
     }
 
-    /// Generates a traveling distance matrix based on the distance in `Rawdata`.
+    /// Generates the traveling distance matrix of an instance.
     ///
-    /// This function constructs a 2D matrix where each cell `(i, j)` represents the
-    /// distance traveled from team `i` to team `j`. The matrix is initialized with
-    /// zeros and populated using the `distances` list contained inside `Rawdata`.
+    /// Shorthand for `DistanceMatrix::from_rawdata`: distances are read between venues (see
+    /// `Rawdata::venue_of`, venue and team coincide without a venue mapping) and the matrix is
+    /// then indexed by team, so every evaluation keeps looking distances up by team.
     ///
     /// # Arguments
     /// * `data` - A reference to the `Rawdata` structure containing team distance
     ///   relationships. `data.distances` is expected to list distances between pairs
-    ///   of teams.
+    ///   of venues.
     ///
     /// # Returns
-    /// A 2D vector (`Vec<Vec<i32>>`) where:
-    /// - The row index corresponds to the origin team
-    /// - The column index corresponds to the destination team
-    /// - Each cell contains the travel distance between them
+    /// A `DistanceMatrix` where `get(from, to)` is the travel distance from team `from`
+    /// to team `to`.
     ///
     /// # Example
     /// ```
     /// let data = Rawdata::generate_example();
     /// let distance_matrix = generate_traveling_distance_matrix(&data);
     ///
-    /// println!("Distance: {}", distance_matrix[0][2]);
+    /// println!("Distance: {}", distance_matrix.get(0, 2));
     /// ```
-    pub fn generate_traveling_distance_matrix(data: &Rawdata) -> Vec<Vec<i32>> {
-        let mut traveling_distance_matrix = vec![vec![0i32; data.teams.len()]; data.teams.len()];
-
-        for distance in &data.distances {
-            traveling_distance_matrix[distance.team1 as usize][distance.team2 as usize] =
-                distance.dist;
-        }
-
-        traveling_distance_matrix
+    pub fn generate_traveling_distance_matrix(data: &Rawdata) -> DistanceMatrix {
+        DistanceMatrix::from_rawdata(data)
     }
 
     #[allow(dead_code)]
@@ -299,7 +723,7 @@ impl Solution {
     ///     println!("No duplicates.");
     /// }
     /// ```
-    pub fn has_duplicate_solutions(solutions: &Vec<Solution>) -> bool {
+    pub fn has_duplicate_solutions(solutions: &[Solution]) -> bool {
         let mut seen = HashSet::new();
 
         for sol in solutions {
@@ -310,649 +734,2976 @@ impl Solution {
         false
     }
 
+    /// Computes a hash of the schedule that is stable across runs and machines.
+    ///
+    /// Unlike the derived `Hash`, whose value depends on the process, the hash is the first
+    /// 8 bytes of the SHA-256 digest of the schedule, read slot by slot and team by team
+    /// (opponent as little-endian `i32`, then `1` for a home game and `0` otherwise). The `id`
+    /// is not hashed, so the same schedule generated in two runs has the same hash.
+    ///
+    /// # Returns
+    /// The hash of the schedule (`u64`).
+    ///
+    /// # Example
+    /// ```
+    /// let first = Solution::generate_florian_solution(&data, 0, true);
+    /// let second = Solution::generate_florian_solution(&data, 0, true);
+    /// assert_eq!(first.content_hash(), second.content_hash());
+    /// println!("Schedule hash: {:016x}", first.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = Sha256::new();
+        for row in &self.solution {
+            for game in row {
+                hasher.update(game.opponent.to_le_bytes());
+                hasher.update([u8::from(game.home_game)]);
+            }
+        }
+
+        let digest = hasher.finalize();
+        u64::from_le_bytes(digest[..8].try_into().expect("SHA-256 digest is 32 bytes"))
+    }
+
     #[allow(dead_code)]
     /// Loads all solution files from a directory and returns them as a vector of `Solution`.
     ///
-    /// This function scans the directory for files whose names follow the pattern
-    /// `solutions_*.json`. Each file is opened, deserialized into a `Solution`,
-    /// and collected into a vector. After loading, the solutions are sorted in ascending
-    /// order based on their `id` field.
+    /// The files are read by `iter_solutions` and the solutions are collected into a vector,
+    /// sorted in ascending order based on their `id` field.
     ///
     /// # Arguments
     /// * `path` - A string slice representing the directory to search for solution files.
+    /// * `data` - A reference to the `Rawdata` of the instance the solutions belong to.
     ///
     /// # Returns
-    /// A vector of `Solution` objects loaded from the directory.
-    ///
-    /// # Panics
-    /// This function will panic if:
-    /// - The directory cannot be read.
-    /// - A file cannot be opened.
-    /// - A JSON file cannot be deserialized into a `Solution`.
+    /// A `Result` with the vector of `Solution` objects loaded from the directory, or a `TtpError`:
+    /// - `Io` if the directory or a file cannot be read.
+    /// - `Json` if a file cannot be deserialized into a `Solution`.
+    /// - `Validation` naming the first file whose solution is invalid (see `validate`).
     ///
     /// # Example
     /// ```
-    /// let solutions = load_solutions("output/solutions/");
+    /// let solutions = load_solutions("output/solutions/", &data).unwrap();
     /// println!("Loaded {} solutions", solutions.len());
     ///
     /// if let Some(first) = solutions.first() {
     ///     println!("First solution ID: {}", first.id);
     /// }
     /// ```
-    pub fn load_solutions(path: &str) -> Vec<Solution> {
-        let mut all_solutions = Vec::new();
-
-        let entries = fs::read_dir(path).expect("Error opening directory");
-
-        for entry in entries {
-            let entry = entry.expect("Error at path");
-            let path = entry.path();
-
-            if path.is_file() {
-                if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
-                    if filename.starts_with("solutions_") && filename.ends_with(".json") {
-                        let file = File::open(&path).expect("Error opening file");
-                        let reader = BufReader::new(file);
-
-                        let solution: Solution =
-                            from_reader(reader).expect("Error deserializing JSON");
-
-                        all_solutions.push(solution);
-                    }
-                }
-            }
-        }
+    pub fn load_solutions(path: &str, data: &Rawdata) -> Result<Vec<Solution>, TtpError> {
+        let mut all_solutions = Self::iter_solutions(path, data).collect::<Result<Vec<_>, _>>()?;
 
         all_solutions.sort_by_key(|s| s.id);
-        all_solutions
+        Ok(all_solutions)
     }
 
-    #[allow(dead_code)]
-    /// Calculates the total traveling distances for a list of solutions.
+    /// Reads the solution files of a directory lazily, one file at a time.
     ///
-    /// This function iterates over each solution, evaluates it using the provided
-    /// traveling distance matrix, and collects the total distances into a vector.
+    /// This function scans the directory for files whose names follow the pattern
+    /// `solution_*.json` (as written by `generate_all_solutions`), `rank_*.json` (as renamed
+    /// by `rank_saved_solutions`) or `solutions_*.json`, each holding one `Solution`. Files
+    /// named `chunk_*.json` (as written with `chunk_size`) hold an array of solutions, read
+    /// as one batch. Only the listing of the directory is kept in memory, a file is opened
+    /// when the iterator reaches it.
+    ///
+    /// Files are visited by increasing number after their prefix (the id of a `solution_`
+    /// file, the batch of a `chunk_` file, the rank of a `rank_` file), so a directory written
+    /// by `generate_all_solutions` yields its solutions by increasing id.
+    ///
+    /// Each solution is checked with `validate` against the instance, so a file with a wrong
+    /// matrix shape or a team not playing once per slot is reported here instead of panicking
+    /// (or being scored as is) during evaluation.
     ///
     /// # Arguments
-    /// * `solutions` - A vector of `Solution` instances to evaluate.
-    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
-    /// * `traveling_distance_matrix` - A reference to a 2D vector where `matrix[i][j]` represents
-    ///   the distance from team `i` to team `j`.
+    /// * `path` - A string slice representing the directory to search for solution files.
+    /// * `data` - A reference to the `Rawdata` of the instance the solutions belong to.
     ///
     /// # Returns
-    /// A vector of `i128` where each element represents the total traveling distance
-    /// of the corresponding solution.
+    /// An iterator over the solutions, each item a `Result` with the `Solution` or a `TtpError`:
+    /// - `Io` if the directory or a file cannot be read (a directory error is the only item).
+    /// - `Json` if a file cannot be deserialized.
+    /// - `Validation` naming the file whose solution is invalid.
     ///
     /// # Example
     /// ```
-    /// let data = Rawdata::generate_example();
-    /// let distance_matrix = vec![vec![0,5,7], vec![5,0,3], vec![7,3,0]];
-    /// let solutions = vec![Solution::generate_example(), Solution::generate_example()];
-    /// let distances = generate_distances(solutions, &data, &distance_matrix);
-    /// println!("All distances: {:?}", distances);
+    /// for solution in Solution::iter_solutions("output/solutions/", &data) {
+    ///     let solution = solution.unwrap();
+    ///     println!("Solution {}", solution.id);
+    /// }
     /// ```
-    pub fn generate_distances(
-        solutions: Vec<Solution>,
-        data: &Rawdata,
-        traveling_distance_matrix: &Vec<Vec<i32>>,
-    ) -> Vec<i128> {
-        let mut all_distances: Vec<i128> = Vec::new();
+    pub fn iter_solutions<'a>(path: &str, data: &'a Rawdata) -> impl Iterator<Item = Result<Solution, TtpError>> + 'a {
+        let (files, error) = match Self::solution_files(path) {
+            Ok(files) => (files, None),
+            Err(error) => (Vec::new(), Some(error)),
+        };
 
-        for solution in solutions {
-            let (distance, _, _, _) =
-                Solution::evaluate_solution(data, traveling_distance_matrix, &solution);
+        error.map(Err).into_iter().chain(files.into_iter().flat_map(move |(path, is_chunk)| {
+            let solutions: Result<Vec<Solution>, TtpError> = File::open(&path).map_err(TtpError::from).and_then(|file| {
+                let reader = BufReader::new(file);
+                if is_chunk {
+                    Ok(from_reader(reader)?)
+                } else {
+                    Ok(vec![from_reader(reader)?])
+                }
+            });
 
-            all_distances.push(distance as i128);
+            match solutions {
+                Ok(solutions) => solutions
+                    .into_iter()
+                    .map(|solution| {
+                        solution
+                            .validate(data)
+                            .map_err(|error| TtpError::Validation(format!("{}: {}", path.display(), error)))?;
+                        Ok(solution)
+                    })
+                    .collect::<Vec<_>>(),
+                Err(error) => vec![Err(error)],
+            }
+        }))
+    }
+
+    /// Lists the solution files of a directory in reading order (see `iter_solutions`).
+    ///
+    /// # Returns
+    /// A `Result` with the `(path, is_chunk)` pair of each file, or a `TtpError::Io` if the
+    /// directory cannot be read.
+    fn solution_files(path: &str) -> Result<Vec<(PathBuf, bool)>, TtpError> {
+        let mut files = Vec::new();
+
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+
+            if path.is_file()
+                && let Some(filename) = path.file_name().and_then(|n| n.to_str())
+                && filename.ends_with(".json")
+                && let Some((start, prefix)) = ["solution_", "solutions_", "rank_", "chunk_"]
+                    .iter()
+                    .filter_map(|prefix| filename.find(*prefix).map(|start| (start, prefix)))
+                    .min()
+            {
+                let number: String = filename[start + prefix.len()..].chars().take_while(char::is_ascii_digit).collect();
+                let key = (number.parse::<u64>().unwrap_or(u64::MAX), filename.to_string());
+                files.push((key, path.clone(), *prefix == "chunk_"));
+            }
         }
 
-        all_distances
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(files.into_iter().map(|(_, path, is_chunk)| (path, is_chunk)).collect())
     }
 
-    /// Logs a solution's schedule and its evaluation metrics.
+    /// Counts the `(slot, team)` cells in which two schedules differ.
     ///
-    /// This function prints a representation of the solution,
-    /// including the total traveling distance, capacity, round-robin and separation
-    /// constraint violations, It also returns the total distance.
+    /// A cell differs if its opponent or its home/away status differs. Changing one game
+    /// changes the cells of both teams, so two schedules differing by one game are at
+    /// distance `2`.
     ///
     /// # Arguments
-    /// * `solution` - A reference to the `Solution` to log.
-    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
-    /// * `traveling_distance_matrix` - A reference to a 2D vector where `matrix[i][j]` represents
-    ///   the distance from team `i` to team `j`.
+    /// * `other` - A reference to the `Solution` to compare with.
     ///
     /// # Returns
-    /// The total traveling distance (`i32`) of the solution.
+    /// The number of differing cells (`usize`), `0` for identical schedules.
+    ///
+    /// # Panics
+    /// Panics if the two solutions do not have the same number of slots and teams.
     ///
     /// # Example
     /// ```
-    /// let data = Rawdata::generate_example();
-    /// let solution = Solution::generate_example();
-    /// let distance = Solution::log_solution(&solution, &data, &vec![vec![0,5,7], vec![5,0,3], vec![7,3,0]]);
-    /// println!("Total distance: {}", distance);
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// assert_eq!(solution.hamming_distance(&solution), 0);
     /// ```
-    fn log_solution(
-        solution: &Solution,
-        data: &Rawdata,
-        traveling_distance_matrix: &Vec<Vec<i32>>,
-    ) -> i32 {
-        let (distance, cap_constraints, sep_constraints, round_robin_respect) =
-            Solution::evaluate_solution(data, traveling_distance_matrix, solution);
-
-        let solution_str = Solution::solution_to_string(solution, data);
-        info!(
-            "Solution:\n{}\nDistance: {}\nCapacity Constraints: {}\nSeparation Constraints: {}\nRound Robin Respect: {}",
-            solution_str, distance, cap_constraints, sep_constraints, round_robin_respect
+    pub fn hamming_distance(&self, other: &Solution) -> usize {
+        assert!(
+            self.n_slots() == other.n_slots() && self.n_teams() == other.n_teams(),
+            "cannot compare a {}x{} schedule with a {}x{} schedule",
+            self.n_slots(),
+            self.n_teams(),
+            other.n_slots(),
+            other.n_teams()
         );
 
-        distance
+        self.solution
+            .iter()
+            .zip(&other.solution)
+            .map(|(row, other_row)| row.iter().zip(other_row).filter(|(game, other_game)| game != other_game).count())
+            .sum()
     }
 
-    /// Generates a complete solution for a given team permutation using Florian's method.
+    /// Computes the mean pairwise Hamming distance of a pool, a measure of its diversity.
     ///
-    /// This function clones the input `Rawdata`, applies the given team permutation, and
-    /// generates a round-robin schedule using `generate_florian_solution`. The resulting
-    /// solution is assigned the provided ID.
+    /// If the pool has at most `max_pairs` pairs of solutions, every pair is compared.
+    /// Otherwise `max_pairs` pairs of distinct solutions are drawn at random (with
+    /// replacement) from `seed`, so the result is reproducible.
     ///
     /// # Arguments
-    /// * `data` - A reference to the `Rawdata` containing the original teams, traveling_distance_matrix and constraints.
-    /// * `perm` - A reference to a vector of `Team` representing the ordered permutation of teams.
-    /// * `fixed_team` - The index of the team to remain fixed during the method rotations.
-    /// * `upward` - If `true`, the home/away pattern follows an upward direction, otherwise downward.
-    /// * `id` - The unique ID to assign to the generated solution.
+    /// * `solutions` - A slice of solutions with the same number of slots and teams.
+    /// * `max_pairs` - The maximum number of compared pairs.
+    /// * `seed` - The seed of the pair sampling.
     ///
     /// # Returns
-    /// A `Solution` struct representing the generated schedule with the specified ID.
+    /// `Some((mean_distance, compared_pairs))`, or `None` if the pool has less than two
+    /// solutions or `max_pairs` is `0`.
     ///
     /// # Example
     /// ```
-    /// let data = Rawdata::generate_example();
-    /// let perm = data.teams.clone();
-    /// let solution = generate_solution(&data, &perm, 0, true, 1);
-    /// println!("{}", solution_to_string(&solution, &data));
+    /// if let Some((mean, pairs)) = Solution::mean_pairwise_hamming(&solutions, 1000, 42) {
+    ///     println!("Mean Hamming distance: {} over {} pairs", mean, pairs);
+    /// }
     /// ```
-    fn generate_solution(
-        data: &Rawdata,
-        perm: &Vec<Team>,
-        fixed_team: usize,
-        upward: bool,
-        id: i32,
-    ) -> Solution {
-        let mut temporary_data = data.clone();
-        temporary_data.teams = perm.clone();
-        let mut solution = Solution::generate_florian_solution(&temporary_data, fixed_team, upward);
-        solution.id = id;
+    pub fn mean_pairwise_hamming(solutions: &[Solution], max_pairs: usize, seed: u64) -> Option<(f64, usize)> {
+        let count = solutions.len();
+        if count < 2 || max_pairs == 0 {
+            return None;
+        }
 
-        solution
+        let pairs: Vec<(usize, usize)> = if count * (count - 1) / 2 <= max_pairs {
+            (0..count).flat_map(|a| (a + 1..count).map(move |b| (a, b))).collect()
+        } else {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..max_pairs)
+                .map(|_| {
+                    let a = rng.random_range(0..count);
+                    let b = (a + rng.random_range(1..count)) % count;
+                    (a, b)
+                })
+                .collect()
+        };
+
+        let total: usize = pairs.iter().map(|&(a, b)| solutions[a].hamming_distance(&solutions[b])).sum();
+        Some((total as f64 / pairs.len() as f64, pairs.len()))
     }
 
-    /// Generates a set of unique random permutations of the team IDs.
-    ///
-    /// This function takes the list of teams from `Rawdata` and generates the requested number of
-    /// unique permutations. Each permutation is randomized and stored in a `Vec<i32>`.
+    /// Computes the value of a ranking key for the solution.
     ///
     /// # Arguments
-    /// * `data` - A reference to the `Rawdata` struct containing the list of teams.
-    /// * `number_permutation` - A reference to an `i32` specifying how many unique permutations
-    ///   should be generated.
+    /// * `key` - The `SolutionKey` to compute.
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
     ///
     /// # Returns
-    /// A vector of vectors (`Vec<Vec<i32>>`), where each inner vector is a unique permutation
-    /// of the team IDs.
+    /// The value of the key (`f64`), lower is better.
     ///
     /// # Example
     /// ```
-    /// let data = Rawdata::generate_example();
-    /// let permutations = generate_random_permutations(&data, &5);
+    /// let breaks = solution.key_value(SolutionKey::Breaks, &data, &EvaluationOptions::default(), &matrix);
     /// ```
-    pub fn generate_random_permutations(
-        data: &Rawdata,
-        number_permutations: i32,
-        seed: u64,
-        path: &str, save: bool,
-    ) -> Vec<Vec<i32>> {
-        let team_ids: Vec<i32> = data.teams.iter().map(|t| t.id).collect();
-
-        let mut rng = StdRng::seed_from_u64(seed);
-        let mut permutations: HashSet<Vec<i32>> = HashSet::new();
-
-        while permutations.len() < number_permutations as usize {
-            let mut perm = team_ids.clone();
-            perm.shuffle(&mut rng);
-            permutations.insert(perm);
+    pub fn key_value(&self, key: SolutionKey, data: &Rawdata, evaluation: &EvaluationOptions, traveling_distance_matrix: &DistanceMatrix) -> f64 {
+        match key {
+            SolutionKey::Distance => Self::evaluate_objective(traveling_distance_matrix, self) as f64,
+            SolutionKey::Penalty => Self::penalty_cost(data, evaluation, self) as f64,
+            SolutionKey::Breaks => self.count_breaks() as f64,
+            SolutionKey::Weighted(lambda) => Self::weighted_objective(data, evaluation, traveling_distance_matrix, self, lambda, 0.0),
         }
+    }
 
-        let vec_perm: Vec<Vec<i32>> = permutations.into_iter().collect();
-
-        if save {
-            let permutations_to_save = Permutations {
-                seed,
-                instance_name: data.instance_name.clone(),
-                permutations: vec_perm.clone(),
-            };
-            save_to_file(&permutations_to_save, &format!("{}/permutation.json", path)).unwrap();
-        }
+    /// Sorts a pool of solutions by a ranking key, in ascending order.
+    ///
+    /// The key of each solution is computed once. The sort is stable, so solutions with the
+    /// same key keep their relative order (the id order for a generated pool).
+    ///
+    /// # Arguments
+    /// * `solutions` - A mutable slice of the `Solution` instances to sort.
+    /// * `key` - The `SolutionKey` to sort by.
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    ///
+    /// # Example
+    /// ```
+    /// Solution::sort_by_key(&mut solutions, SolutionKey::Distance, &data, &EvaluationOptions::default(), &matrix);
+    /// println!("Shortest solution: {}", solutions[0].id);
+    /// ```
+    pub fn sort_by_key(
+        solutions: &mut [Solution],
+        key: SolutionKey,
+        data: &Rawdata,
+        evaluation: &EvaluationOptions,
+        traveling_distance_matrix: &DistanceMatrix,
+    ) {
+        let values: Vec<f64> = solutions
+            .iter()
+            .map(|solution| solution.key_value(key, data, evaluation, traveling_distance_matrix))
+            .collect();
+        let mut order: Vec<usize> = (0..solutions.len()).collect();
+        order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
 
-        vec_perm
+        let sorted: Vec<Solution> = order.iter().map(|&index| solutions[index].clone()).collect();
+        solutions.clone_from_slice(&sorted);
     }
 
-    /// Generates all possible solutions for a given team permutation using Florian's method,
-    /// evaluates their distances, and optionally saves them to disk.
+    /// Renames the saved solution files of a pool by rank of a key.
     ///
-    /// This function iterates over all possible combinations of fixed teams and home/away patterns
-    /// (upward/downward) for a given permutation of teams. Each generated solution is evaluated
-    /// using the traveling distance matrix, logged, and optionally saved as JSON.
+    /// Each `solution_<id>.json` in `path` is renamed to `rank_<rank>_<key>_<value>.json`
+    /// (`rank_0001_dist_<distance>.json` for the shortest solution with `SolutionKey::Distance`),
+    /// ranked with `sort_by_key` (ties keep the id order). The files are not rewritten, so the
+    /// original `id` is kept inside the JSON. Both names go through `names`, so the files saved
+    /// with a `--name-template` are found and keep its pattern.
     ///
     /// # Arguments
-    /// * `data` - A reference to the `Rawdata` containing teams, slots, and constraints.
-    /// * `traveling_distance_matrix` - A reference to a 2D vector where `matrix[i][j]` represents
+    /// * `solutions` - A slice of the saved `Solution` instances.
+    /// * `key` - The `SolutionKey` to rank by.
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
     ///   the distance from team `i` to team `j`.
-    /// * `permutation` - A vector of vect of team IDs representing the order in which teams are considered.
-    /// * `path` - A string slice representing the directory path where solutions will be saved if `SAVE_ENABLED` is true.
+    /// * `path` - A string slice representing the directory where the solutions were saved.
+    /// * `names` - A reference to the `NameTemplate` the solutions were saved with.
     ///
     /// # Returns
-    /// A tuple `(solutions, all_distances)`:
-    /// - `solutions` (Vec<Solution>): all generated solution matrices.
-    /// - `all_distances` (Vec<i128>): total traveling distance for each solution.
-    ///
-    /// # Panics
-    /// This function may panic if saving a solution to file fails.
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with a `TtpError` if a
+    /// file cannot be renamed.
     ///
     /// # Example
     /// ```
-    /// let data = Rawdata::generate_example();
-    /// let distance_matrix = vec![vec![0,5,7], vec![5,0,3], vec![7,3,0]];
-    /// let permutation = vec![0,1,2];
-    /// let (solutions, distances) = generate_all_solutions(&data, &distance_matrix, permutation, "output");
-    /// println!("Solutions length {}", solutions.len());
-    /// println!("Distances: {:?}", distances);
+    /// let options = GenerationOptions { path: "out".to_string(), save: true, ..Default::default() };
+    /// let (solutions, _, _) = Solution::generate_all_solutions(&data, &matrix, permutations, &options).unwrap();
+    /// Solution::rank_saved_solutions(&solutions, SolutionKey::Distance, &data, &EvaluationOptions::default(), &matrix, "out", &NameTemplate::default()).unwrap();
     /// ```
-    pub fn generate_all_solutions(
+    pub fn rank_saved_solutions(
+        solutions: &[Solution],
+        key: SolutionKey,
         data: &Rawdata,
-        traveling_distance_matrix: &Vec<Vec<i32>>,
-        permutation: Vec<Vec<i32>>,
+        evaluation: &EvaluationOptions,
+        traveling_distance_matrix: &DistanceMatrix,
         path: &str,
-        save: bool,
-    ) -> (Vec<Solution>, Vec<i128>) {
-        let mut solutions: Vec<Solution> = Vec::new();
-        let mut all_distances: Vec<i128> = Vec::new();
-
-        let mut id_solution = 0;
+        names: &NameTemplate,
+    ) -> Result<(), TtpError> {
+        let mut ranking = solutions.to_vec();
+        Self::sort_by_key(&mut ranking, key, data, evaluation, traveling_distance_matrix);
 
-        let total_perms = 2 * data.teams.len() * permutation.len();
-
-        // Create progress bar
-        let progress = ProgressBarLog::new(total_perms as u64);
-
-        for team in permutation {
-            let teams_ordered: Vec<Team> = team
-                .iter()
-                .filter_map(|id| data.teams.iter().find(|t| t.id == *id))
-                .cloned()
-                .collect();
+        for (rank, solution) in ranking.iter().enumerate() {
+            let ranked = format!("rank_{:04}_{}_{}", rank + 1, key, solution.key_value(key, data, evaluation, traveling_distance_matrix));
+            fs::rename(
+                format!("{}/{}", path, names.file_name(&format!("solution_{}", solution.id), Some(solution.id), "json")),
+                format!("{}/{}", path, names.file_name(&ranked, Some(solution.id), "json")),
+            )?;
+        }
 
-            // Log the permutation
-            info!("Permutation: {:?}", team);
+        Ok(())
+    }
 
-            for direction in [true, false] {
-                for fixed_team in 0..data.teams.len() {
-                    id_solution = id_solution + 1;
-
-                    // Generate solution
-                    let temporary_solution = Solution::generate_solution(
-                        &data,
-                        &teams_ordered,
-                        fixed_team,
-                        direction,
-                        id_solution,
-                    );
-
-                    // Log solution details
-                    let distance_solution = Solution::log_solution(
-                        &temporary_solution,
-                        &data,
-                        &traveling_distance_matrix,
-                    );
-
-                    // Store the solution and the distance
-                    solutions.push(temporary_solution.clone());
-                    all_distances.push(distance_solution as i128);
+    /// Returns the number of slots (rows) of the solution matrix.
+    pub fn n_slots(&self) -> usize {
+        self.solution.len()
+    }
 
-                    // Save to file
-                    if save {
-                        save_to_file(
-                            &temporary_solution,
-                            &format!("{}/solution_{}.json", path, id_solution),
-                        )
-                        .unwrap();
-                    }
+    /// Returns the number of teams of the solution matrix (length of its first row, `0` if empty).
+    pub fn n_teams(&self) -> usize {
+        self.solution.first().map_or(0, |row| row.len())
+    }
 
-                    // Update bar inc
-                    progress.inc();
-                }
-            }
-        }
+    /// Returns the opponent of a team in a slot.
+    ///
+    /// # Arguments
+    /// * `slot` - The slot (row) of the solution.
+    /// * `team` - The team (column) of the solution.
+    ///
+    /// # Returns
+    /// The opponent id, or `None` if the team has a bye (opponent `-1`) or if the slot or the
+    /// team is out of range.
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// assert!(solution.opponent_of(0, 0).is_some());
+    /// assert_eq!(solution.opponent_of(solution.n_slots(), 0), None);
+    /// ```
+    pub fn opponent_of(&self, slot: usize, team: usize) -> Option<i32> {
+        self.solution
+            .get(slot)
+            .and_then(|row| row.get(team))
+            .map(|game| game.opponent)
+            .filter(|&opponent| opponent >= 0)
+    }
 
-        (solutions, all_distances)
+    /// Checks whether a team plays at home in a slot.
+    ///
+    /// # Arguments
+    /// * `slot` - The slot (row) of the solution.
+    /// * `team` - The team (column) of the solution.
+    ///
+    /// # Returns
+    /// `true` if the team plays a home game, `false` for an away game, a bye, or if the slot
+    /// or the team is out of range.
+    pub fn is_home(&self, slot: usize, team: usize) -> bool {
+        self.opponent_of(slot, team).is_some() && self.solution[slot][team].home_game
     }
 
-    /// Generates a schedule using Florian's method construction.
+    /// Reconciles the dimensions of the solution with the instance it is evaluated against.
     ///
-    /// This function constructs a round-robin schedule fixing a team. The `upward`
-    /// flag determines the pattern of home/away assignments for the first match
-    /// of each pairing.
+    /// Construction always follows the instance (`Rawdata::n_teams`, `Rawdata::n_slots`) while
+    /// evaluation follows the solution matrix, so both must agree: the solution must have one
+    /// column per team of the instance and at most one row per slot (a prefix, as built by
+    /// `truncate_slots`, is allowed).
     ///
     /// # Arguments
-    /// * `data` - A reference to `Rawdata` containing team information.
-    /// * `fixed_team` - The index of the team to remain fixed during rotations.
-    /// * `upward` - If `true`, the home team assignment follows an upward pattern; otherwise downward.
+    /// * `data` - A reference to the `Rawdata` the solution is evaluated against.
     ///
     /// # Returns
-    /// A `Solution` struct with the scheduled matches for all slots and teams.
+    /// `Ok(())` if the dimensions agree, otherwise a `TtpError::Validation` describing the mismatch.
     ///
     /// # Example
     /// ```
-    /// let data = Rawdata::generate_example();
-    /// let solution = generate_florian_solution(&data, 0, true);
-    /// println!("{}", solution_to_string(&solution, &data));
+    /// let solution = Solution::new(&data);
+    /// assert!(solution.check_dimensions(&data).is_ok());
     /// ```
-    pub fn generate_florian_solution(data: &Rawdata, fixed_team: usize, upward: bool) -> Solution {
-        info!(
-            "Starting Florian's construction for {} teams | Fixed team: {} | Pattern: {}",
-            data.teams.len(),
-            fixed_team,
-            if upward {
-                "Upward direction"
-            } else {
-                "Downward direction"
-            }
-        );
-
-        let mut solution_matrix = Solution::new(&data);
-
-        let mut teams: Vec<usize> = data
-            .teams
-            .iter()
-            .enumerate()
-            .map(|(_, team)| team.id as usize)
-            .collect();
-
-        let fixed_team = teams.remove(fixed_team);
-        teams.push(fixed_team);
-
-        for round in 0..2 * (data.teams.len() - 1) {
-            info!("Round: {}", round);
-            info!("Teams before rotation: {:?}", teams);
-            for i in 0..(data.teams.len() / 2) {
-                let team_a = teams[i];
-                let team_b = teams[data.teams.len() - 1 - i];
-                let home_first = (round % 2 == 0) == upward;
-
-                if home_first {
-                    solution_matrix.solution[round][team_a] = Game {
-                        home_game: true,
-                        opponent: team_b as i32,
-                    };
-                    solution_matrix.solution[round][team_b] = Game {
-                        home_game: false,
-                        opponent: team_a as i32,
-                    };
-                } else {
-                    solution_matrix.solution[round][team_a] = Game {
-                        home_game: false,
-                        opponent: team_b as i32,
-                    };
-                    solution_matrix.solution[round][team_b] = Game {
-                        home_game: true,
-                        opponent: team_a as i32,
-                    };
-                }
-
-                info!(
-                    "Pairing: Team {} vs Team {} | {} is home",
-                    team_a,
-                    team_b,
-                    if home_first { team_a } else { team_b }
-                );
-            }
-
-            let fixed_team = teams.remove(teams.len() - 1);
-            teams.rotate_right(1);
-            teams.push(fixed_team);
-            info!("Teams after rotation: {:?}", teams);
+    pub fn check_dimensions(&self, data: &Rawdata) -> Result<(), TtpError> {
+        if self.n_teams() != data.n_teams() || self.n_slots() > data.n_slots() {
+            return Err(TtpError::Validation(format!(
+                "solution {} is {} slots x {} teams but the instance has {} slots x {} teams",
+                self.id,
+                self.n_slots(),
+                self.n_teams(),
+                data.n_slots(),
+                data.n_teams()
+            )));
         }
 
-        info!(
-            "Final solution for {} teams | Fixed team: {} | Pattern: {}",
-            data.teams.len(),
-            fixed_team,
-            if upward {
-                "Upward direction"
-            } else {
-                "Downward direction"
-            }
-        );
-
-        solution_matrix
+        Ok(())
     }
 
-    /// Converts a `Solution` matrix into a formatted string representation.
+    /// Checks that the solution matrix has the shape expected by the instance.
     ///
-    /// This function generates a human-readable string showing the schedule of all teams
-    /// for each slot. Each cell shows the opponent ID followed by `H` for a home game or
-    /// `A` for an away game. The output also includes team names and IDs as headers.
+    /// The matrix must have one row per slot, every row must have one game per team,
+    /// and every opponent must be a valid team index (or `-1` for an unassigned game).
     ///
     /// # Arguments
-    /// * `solution_matrix` - A reference to the `Solution` containing the schedule.
-    /// * `data` - A reference to the `Rawdata` struct containing team information.
+    /// * `data` - A reference to the `Rawdata` containing teams and slots.
     ///
     /// # Returns
-    /// A `String` representing the formatted solution.
+    /// `Ok(())` if the shape is valid, otherwise an `Err` describing the first problem found.
     ///
     /// # Example
     /// ```
-    /// let data = Rawdata::generate_example();
-    /// let solution = Solution::generate_example();
-    /// let output_str = solution_to_string(&solution, &data);
-    /// println!("{}", output_str);
-    /// ```
-    /// Example output:
-    /// ```text
-    /// Id: 1
-    ///          ATL:0    NYM:1    PHI:2
-    /// Slot:0    1H       2A       0H
-    /// Slot:1    2H       0A       1H
+    /// let solution = Solution::new(&data);
+    /// assert!(solution.validate_shape(&data).is_ok());
     /// ```
-    pub fn solution_to_string(solution_matrix: &Solution, data: &Rawdata) -> String {
-        let mut output = String::new();
-        output.push_str(&format!("Id: {}\n", solution_matrix.id));
+    pub fn validate_shape(&self, data: &Rawdata) -> Result<(), String> {
+        let num_slots = data.n_slots();
+        let num_teams = data.n_teams();
 
-        output.push_str(&format!("{:>8}", ""));
-        for team_id in 0..data.teams.len() {
-            output.push_str(&format!(
-                "{:>8}",
-                format!("{}:{}", data.teams[team_id].name, data.teams[team_id].id)
+        if self.solution.len() != num_slots {
+            return Err(format!(
+                "solution {} has {} slots, expected {}",
+                self.id,
+                self.solution.len(),
+                num_slots
             ));
         }
-        output.push('\n');
 
-        for (slot_id, row) in solution_matrix.solution.iter().enumerate() {
-            output.push_str(&format!("{:>8}", format!("Slot:{}", slot_id)));
-            for game in row {
-                output.push_str(&format!(
-                    "{:>8}",
-                    format!(
-                        "{}{}",
-                        game.opponent,
-                        if game.home_game { "H" } else { "A" }
-                    )
+        for (slot, row) in self.solution.iter().enumerate() {
+            if row.len() != num_teams {
+                return Err(format!(
+                    "solution {} has {} teams in slot {}, expected {}",
+                    self.id,
+                    row.len(),
+                    slot,
+                    num_teams
+                ));
+            }
+
+            if let Some(team) = row
+                .iter()
+                .position(|game| game.opponent < -1 || game.opponent >= num_teams as i32)
+            {
+                return Err(format!(
+                    "solution {} has an invalid opponent {} for team {} in slot {}",
+                    self.id, row[team].opponent, team, slot
                 ));
             }
-            output.push('\n');
         }
 
-        output
+        Ok(())
     }
 
-    /// Checks all constraints for a solution, including capacity, separation, and round-robin.
-    ///
-    /// 1. **Capacity constraints**: Verifies for each team, within the specified interval (`c_intp`)
-    ///    of consecutive slots, the number of home or away games falls within
-    ///    the minimum (`c_min`) and maximum (`c_max`) allowed.
-    ///
-    /// 2. **Separation constraints**: Ensures that matches between two teams respect the minimum and maximum
-    ///    separation distances defined by each constraint.
+    /// Checks that every team plays exactly one game in every slot.
     ///
-    /// 3. **Round-robin constraints**: Checks that no pair of teams plays against each other more than 4 times (2 pairs of game).
+    /// A cell is reported if it is unassigned (opponent `-1`) while the instance has an even
+    /// number of teams, where no bye is expected, if the team plays against itself, or if its
+    /// opponent does not list it back in the same slot (e.g. two teams booked against the same
+    /// opponent).
     ///
     /// # Arguments
-    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
-    /// * `solution_matrix` - A reference to the `Solution` with the scheduled games.
+    /// * `data` - A reference to the `Rawdata` containing teams and slots.
     ///
     /// # Returns
-    /// A tuple `(capacity_violations, separation_violations, round_robin_respected)`
-    /// - `capacity_violations` (i32): total number of capacity constraint violations.
-    /// - `separation_violations` (i32): total number of separation constraint violations.
-    /// - `round_robin_respected` (bool): true if all pairs of teams respect the round-robin.
+    /// `Ok(())` if every team plays once per slot, otherwise an `Err` with the `(slot, team)`
+    /// cells in error, in slot then team order.
     ///
     /// # Example
     /// ```
-    /// let data = Rawdata::generate_example();
-    /// let solution = Solution::generate_example();
-    /// let (cap_viol, sep_viol, rr_ok) = check_constraints(&data, &solution);
-    /// println!("Capacity violations: {}, Separation violations: {}, Round-robin ok: {}", cap_viol, sep_viol, rr_ok);
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// assert!(solution.check_one_game_per_slot(&data).is_ok());
     /// ```
-    fn check_constraints(data: &Rawdata, solution_matrix: &Solution) -> (i32, i32, bool) {
-        let num_slots = solution_matrix.solution.len();
-        let num_teams = solution_matrix.solution[0].len();
-        let mut capacity_constraints = 0;
-        let mut separation_constraints = 0;
-        let mut round_robin_respect = true;
-
-        // Capacity Constraints:
-
-        for constraint in &data.capacity_constraints {
-            for team in 0..num_teams {
-                for start_slot in 0..=num_slots - constraint.c_intp as usize {
-                    let count = solution_matrix.solution
-                        [start_slot..start_slot + constraint.c_intp as usize]
-                        .iter()
-                        .filter(|slot| {
-                            let game = &slot[team];
-                            match constraint.c_mode1 {
-                                'A' => game.home_game,
-                                'H' => !game.home_game,
-                                _ => false,
-                            }
-                        })
-                        .count();
-
-                    if count < constraint.c_min as usize || count > constraint.c_max as usize {
-                        capacity_constraints += 1;
-                    }
-                }
-            }
-        }
-
-        // Separation Constraints:
-
-        for constraint in &data.separation_constraints {
-            for team in 0..num_teams {
-                let mut last_slot_vs: Vec<Option<usize>> = vec![None; num_teams];
-
-                for slot in 0..num_slots {
-                    let game = &solution_matrix.solution[slot][team];
-                    let opponent = game.opponent as usize;
-
-                    if let Some(last) = last_slot_vs[opponent] {
-                        let distance = slot - last;
-
-                        if distance <= constraint.c_min as usize
-                            || distance > constraint.c_max as usize
-                        {
-                            separation_constraints += 1;
-                        }
-                    }
-
-                    last_slot_vs[opponent] = Some(slot);
-                }
-            }
-        }
-
-        // Round-robin constraints
-
-        let mut match_count: HashMap<(usize, usize), i32> = HashMap::new();
+    pub fn check_one_game_per_slot(&self, data: &Rawdata) -> Result<(), Vec<(usize, usize)>> {
+        let byes_expected = !data.n_teams().is_multiple_of(2);
+        let mut cells = Vec::new();
 
-        for slot in 0..num_slots {
-            for home_team in 0..num_teams {
-                let away_team = solution_matrix.solution[slot][home_team].opponent;
-
-                let key = if home_team < away_team as usize {
-                    (home_team, away_team as usize)
-                } else {
-                    (away_team as usize, home_team)
+        for (slot, row) in self.solution.iter().enumerate() {
+            for (team, game) in row.iter().enumerate() {
+                let single_game = match usize::try_from(game.opponent) {
+                    Ok(opponent) => opponent != team && row.get(opponent).is_some_and(|other| other.opponent == team as i32),
+                    Err(_) => byes_expected,
                 };
-
-                *match_count.entry(key).or_insert(0) += 1;
-            }
-        }
-
-        for ((_, _), count) in &match_count {
-            if *count > 4 {
-                round_robin_respect = false;
+                if !single_game {
+                    cells.push((slot, team));
+                }
             }
         }
 
-        (
-            capacity_constraints,
-            separation_constraints,
-            round_robin_respect,
-        )
+        if cells.is_empty() { Ok(()) } else { Err(cells) }
     }
 
-    /// Calculates the total traveling distance for all teams in a given solution.
+    /// Validates a loaded solution against the instance.
     ///
-    /// This function iterates over all teams and all slots in the solution. For each team,
-    /// it tracks the current location and adds the distance to the next game location.
-    /// Home games do not require traveling, while away games add the distance to the opponent's location.
+    /// Runs `validate_shape`, then `check_one_game_per_slot`.
     ///
     /// # Arguments
-    /// * `traveling_distance_matrix` - A reference to a 2D vector where `matrix[i][j]` represents
-    ///   the distance from team `i` to team `j`.
-    /// * `solution_matrix` - A reference to the `Solution` containing the schedule of games
-    ///   for all slots and teams.
+    /// * `data` - A reference to the `Rawdata` containing teams and slots.
     ///
     /// # Returns
-    /// The total traveling distance for all teams (i32).
-    ///
+    /// `Ok(())` if the solution is valid, otherwise an `Err` describing the problem (the first
+    /// cells in error for `check_one_game_per_slot`).
+    pub fn validate(&self, data: &Rawdata) -> Result<(), String> {
+        self.validate_shape(data)?;
+
+        self.check_one_game_per_slot(data).map_err(|cells| {
+            let listed: Vec<String> = cells
+                .iter()
+                .take(5)
+                .map(|(slot, team)| format!("team {} in slot {}", team, slot))
+                .collect();
+            format!(
+                "solution {} has {} cells without exactly one game: {}{}",
+                self.id,
+                cells.len(),
+                listed.join(", "),
+                if cells.len() > listed.len() { ", ..." } else { "" }
+            )
+        })
+    }
+
+    #[allow(dead_code)]
+    /// Calculates the total traveling distances for a list of solutions.
+    ///
+    /// This function iterates over each solution, evaluates it using the provided
+    /// traveling distance matrix, and collects the total distances into a vector.
+    ///
+    /// # Arguments
+    /// * `solutions` - A vector of `Solution` instances to evaluate.
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    ///
+    /// # Returns
+    /// A vector of `i128` where each element represents the total traveling distance
+    /// of the corresponding solution.
+    ///
+    /// # Example
+    /// ```
+    /// let data = Rawdata::generate_example();
+    /// let distance_matrix = DistanceMatrix::from(vec![vec![0,5,7], vec![5,0,3], vec![7,3,0]]);
+    /// let solutions = vec![Solution::generate_example(), Solution::generate_example()];
+    /// let distances = generate_distances(solutions, &data, &EvaluationOptions::default(), &distance_matrix);
+    /// println!("All distances: {:?}", distances);
+    /// ```
+    pub fn generate_distances(
+        solutions: Vec<Solution>,
+        data: &Rawdata,
+        evaluation: &EvaluationOptions,
+        traveling_distance_matrix: &DistanceMatrix,
+    ) -> Vec<i128> {
+        let mut all_distances: Vec<i128> = Vec::new();
+
+        for solution in solutions {
+            let (distance, _, _, _) =
+                Solution::evaluate_solution(data, evaluation, traveling_distance_matrix, &solution);
+
+            all_distances.push(distance as i128);
+        }
+
+        all_distances
+    }
+
+    /// Logs a solution's schedule and its evaluation metrics.
+    ///
+    /// This function prints a representation of the solution,
+    /// including the total traveling distance, capacity, round-robin and separation
+    /// constraint violations, It also returns the total distance.
+    ///
+    /// # Arguments
+    /// * `solution` - A reference to the `Solution` to log.
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    ///
+    /// # Returns
+    /// A tuple `(total_distance, feasible)`:
+    /// - `total_distance` (i32): total traveling distance of the solution.
+    /// - `feasible` (bool): true if the solution has no hard violations (see `is_feasible`).
+    ///
+    /// # Example
+    /// ```
+    /// let data = Rawdata::generate_example();
+    /// let solution = Solution::generate_example();
+    /// let (distance, feasible) = Solution::log_solution(&solution, &data, &EvaluationOptions::default(), &DistanceMatrix::from(vec![vec![0,5,7], vec![5,0,3], vec![7,3,0]]));
+    /// println!("Total distance: {} | Feasible: {}", distance, feasible);
+    /// ```
+    fn log_solution(
+        solution: &Solution,
+        data: &Rawdata,
+        evaluation: &EvaluationOptions,
+        traveling_distance_matrix: &DistanceMatrix,
+    ) -> (i32, bool) {
+        let (distance, cap_constraints, sep_constraints, round_robin_respect) =
+            Solution::evaluate_solution(data, evaluation, traveling_distance_matrix, solution);
+
+        let solution_str = Solution::solution_to_string(solution, data);
+        info!(
+            "Solution:\n{}\nDistance: {}\nCapacity Constraints: {}\nSeparation Constraints: {}\nRound Robin Respect: {}",
+            solution_str, distance, cap_constraints, sep_constraints, round_robin_respect
+        );
+
+        (
+            distance,
+            Solution::is_feasible(cap_constraints, sep_constraints, round_robin_respect),
+        )
+    }
+
+    /// Checks if an evaluated solution is free of hard violations.
+    ///
+    /// A solution is considered feasible when it has no capacity violations,
+    /// no separation violations and respects the round-robin structure.
+    ///
+    /// # Arguments
+    /// * `capacity_violations` - Total number of capacity constraint violations.
+    /// * `separation_violations` - Total number of separation constraint violations.
+    /// * `round_robin_respect` - True if all pairs of teams respect the round-robin.
+    ///
+    /// # Returns
+    /// `true` if the solution is feasible, `false` otherwise.
+    ///
+    /// # Example
+    /// ```
+    /// let (_, cap_viol, sep_viol, rr_ok) = Solution::evaluate_solution(&data, &EvaluationOptions::default(), &distance_matrix, &solution);
+    /// let feasible = Solution::is_feasible(cap_viol, sep_viol, rr_ok);
+    /// ```
+    pub fn is_feasible(
+        capacity_violations: i32,
+        separation_violations: i32,
+        round_robin_respect: bool,
+    ) -> bool {
+        capacity_violations == 0 && separation_violations == 0 && round_robin_respect
+    }
+
+    /// Generates a complete solution for a given team permutation using a construction method.
+    ///
+    /// This function clones the input `Rawdata`, applies the given team permutation, and
+    /// generates a round-robin schedule using `generate_florian_solution` or
+    /// `generate_circle_solution`. The resulting solution is assigned the provided ID.
+    ///
+    /// The random number generator is handed to the construction method, which draws its
+    /// tie-breaks from it instead of creating its own (the Florian method has no tie to break).
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing the original teams, traveling_distance_matrix and constraints.
+    /// * `perm` - A reference to a vector of `Team` representing the ordered permutation of teams.
+    /// * `fixed_team` - The index of the team to remain fixed during the method rotations.
+    /// * `upward` - If `true`, the home/away pattern follows an upward direction, otherwise downward.
+    /// * `id` - The unique ID to assign to the generated solution.
+    /// * `method` - The `ConstructionMethod` used to build the schedule.
+    /// * `rng` - An optional random number generator for the tie-breaks of the method, the
+    ///   ties are broken deterministically without one.
+    ///
+    /// # Returns
+    /// A `Solution` struct representing the generated schedule with the specified ID.
+    ///
+    /// # Example
+    /// ```
+    /// let data = Rawdata::generate_example();
+    /// let perm = data.teams.clone();
+    /// let solution = generate_solution(&data, &perm, 0, true, 1, ConstructionMethod::Florian, None);
+    /// println!("{}", solution_to_string(&solution, &data));
+    /// ```
+    fn generate_solution(
+        data: &Rawdata,
+        perm: &[Team],
+        fixed_team: usize,
+        upward: bool,
+        id: i32,
+        method: ConstructionMethod,
+        rng: Option<&mut StdRng>,
+    ) -> Solution {
+        let mut temporary_data = data.clone();
+        temporary_data.teams = perm.to_vec();
+        let mut solution = match method {
+            ConstructionMethod::Florian => Solution::generate_florian_solution(&temporary_data, fixed_team, upward),
+            ConstructionMethod::Circle => Solution::generate_circle_solution(&temporary_data, fixed_team, upward, rng),
+        };
+        solution.id = id;
+
+        solution
+    }
+
+    /// Generates a set of unique random permutations of the team IDs.
+    ///
+    /// This function takes the list of teams from `Rawdata` and generates the requested number of
+    /// unique permutations. Each permutation is randomized and stored in a `Vec<i32>`.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` struct containing the list of teams.
+    /// * `number_permutation` - A reference to an `i32` specifying how many unique permutations
+    ///   should be generated.
+    /// * `names` - A reference to the `NameTemplate` of the `permutation.json` file saved in `path`.
+    ///
+    /// # Returns
+    /// A `Result` with a vector of vectors (`Vec<Vec<i32>>`), where each inner vector is a unique
+    /// permutation of the team IDs. The permutations are kept in generation order, so the result
+    /// is fully determined by the seed. A `TtpError` is returned if saving the permutations fails.
+    /// If more than `n!` permutations are requested for `n` teams, the count is capped at `n!`
+    /// with a warning.
+    ///
+    /// # Example
+    /// ```
+    /// let data = Rawdata::generate_example();
+    /// let permutations = generate_random_permutations(&data, 5, 42, "", false, &NameTemplate::default()).unwrap();
+    /// ```
+    pub fn generate_random_permutations(
+        data: &Rawdata,
+        number_permutations: i32,
+        seed: u64,
+        path: &str, save: bool,
+        names: &NameTemplate,
+    ) -> Result<Vec<Vec<i32>>, TtpError> {
+        let team_ids: Vec<i32> = data.teams.iter().map(|t| t.id).collect();
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut seen: HashSet<Vec<i32>> = HashSet::new();
+        let mut vec_perm: Vec<Vec<i32>> = Vec::new();
+
+        // Only n! distinct permutations exist, asking for more would never terminate
+        let mut target = number_permutations.max(0) as usize;
+        let available = (1..=team_ids.len()).try_fold(1usize, |total, k| total.checked_mul(k));
+        if let Some(available) = available
+            && target > available
+        {
+            warn!(
+                "{} permutations requested but {} teams only have {} distinct permutations, generating {}",
+                target,
+                team_ids.len(),
+                available,
+                available
+            );
+            target = available;
+        }
+
+        // Keep the generation order so the same seed always yields the same sequence
+        while vec_perm.len() < target {
+            let mut perm = team_ids.clone();
+            perm.shuffle(&mut rng);
+            if seen.insert(perm.clone()) {
+                vec_perm.push(perm);
+            }
+        }
+
+        if save {
+            Solution::save_permutations(data, &vec_perm, seed, &format!("{}/{}", path, names.file_name("permutation", None, "json")))?;
+        }
+
+        Ok(vec_perm)
+    }
+
+    /// Removes the permutations that generate the same pool as an earlier permutation.
+    ///
+    /// Two permutations are equivalent when they are equal, or when the construction method
+    /// builds the same schedules from both: every direction and fixed team is built (without
+    /// logs, and with the ties of the construction broken the same way for every solution) and
+    /// the `content_hash` of the `2 * n` schedules are compared, in any order. Only the first
+    /// permutation of each pool is kept, in the original order, and the number of removed
+    /// permutations is logged.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing the teams.
+    /// * `permutations` - A slice of team id permutations.
+    /// * `method` - The `ConstructionMethod` the pools are built with.
+    ///
+    /// # Returns
+    /// A `Vec<Vec<i32>>` with the permutations whose pool is not the one of an earlier permutation.
+    ///
+    /// # Example
+    /// ```
+    /// let permutations = vec![vec![0, 1, 2, 3], vec![2, 3, 0, 1], vec![0, 1, 2, 3]];
+    /// let unique = Solution::dedup_equivalent_permutations(&data, &permutations, ConstructionMethod::Florian);
+    /// assert_eq!(unique, vec![vec![0, 1, 2, 3], vec![2, 3, 0, 1]]);
+    /// ```
+    pub fn dedup_equivalent_permutations(data: &Rawdata, permutations: &[Vec<i32>], method: ConstructionMethod) -> Vec<Vec<i32>> {
+        let num_teams = data.n_teams();
+        let mut seen_permutations: HashSet<&Vec<i32>> = HashSet::new();
+        let mut seen_pools: HashSet<Vec<u64>> = HashSet::new();
+        let mut unique = Vec::new();
+
+        for permutation in permutations {
+            if !seen_permutations.insert(permutation) {
+                continue;
+            }
+
+            let teams_ordered: Vec<Team> = permutation
+                .iter()
+                .filter_map(|id| data.teams.iter().find(|team| team.id == *id))
+                .cloned()
+                .collect();
+            let mut pool: Vec<u64> = logging::quietly(|| {
+                Solution::generation_order(num_teams, None)
+                    .into_iter()
+                    .map(|(direction, fixed_team)| {
+                        Solution::generate_solution(data, &teams_ordered, fixed_team, direction, 0, method, None).content_hash()
+                    })
+                    .collect()
+            });
+            pool.sort_unstable();
+
+            if seen_pools.insert(pool) {
+                unique.push(permutation.clone());
+            }
+        }
+
+        info!(
+            "Removed {} permutations generating the same pool as an earlier one ({} left)",
+            permutations.len() - unique.len(),
+            unique.len()
+        );
+
+        unique
+    }
+
+    /// Saves a set of permutations to a json file, along with the seed and the instance name.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` of the instance the permutations belong to.
+    /// * `permutations` - A slice of permutations of team IDs.
+    /// * `seed` - The seed used for generating the permutations.
+    /// * `path` - A string slice specifying the file path.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with a `TtpError`.
+    ///
+    /// # Example
+    /// ```
+    /// let permutations = Solution::generate_random_permutations(&data, 5, 42, "", false).unwrap();
+    /// Solution::save_permutations(&data, &permutations, 42, "perms.json").unwrap();
+    /// ```
+    pub fn save_permutations(
+        data: &Rawdata,
+        permutations: &[Vec<i32>],
+        seed: u64,
+        path: &str,
+    ) -> Result<(), TtpError> {
+        let permutations_to_save = Permutations {
+            seed,
+            instance_name: data.instance_name.clone(),
+            permutations: permutations.to_vec(),
+        };
+        save_to_file(&permutations_to_save, path, false)
+    }
+
+    /// Loads a permutations file previously written by `save_permutations`.
+    ///
+    /// # Arguments
+    /// * `path` - A string slice specifying the file path.
+    ///
+    /// # Returns
+    /// A `Result` with the `Permutations` stored in the file, or a `TtpError` if the file
+    /// cannot be opened or deserialized.
+    ///
+    /// # Example
+    /// ```
+    /// let permutations = Solution::load_permutations("perms.json").unwrap();
+    /// println!("Loaded {} permutations", permutations.permutations.len());
+    /// ```
+    pub fn load_permutations(path: &str) -> Result<Permutations, TtpError> {
+        let file = File::open(path)?;
+        let reader = BufReader::new(file);
+
+        Ok(from_reader(reader)?)
+    }
+
+    /// Generates all possible solutions for a given team permutation using a construction method,
+    /// evaluates their distances, and optionally saves them to disk.
+    ///
+    /// This function iterates over all possible combinations of fixed teams and home/away patterns
+    /// (upward/downward) for a given permutation of teams. Each generated solution is evaluated
+    /// using the traveling distance matrix, logged, and optionally saved as JSON.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing teams, slots, and constraints.
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    /// * `permutation` - A vector of vect of team IDs representing the order in which teams are considered.
+    /// * `options` - The `GenerationOptions` (saving, resuming, construction method, evaluation).
+    ///
+    /// # Returns
+    /// A `Result` with a tuple `(solutions, all_distances, all_feasible)`:
+    /// - `solutions` (Vec<Solution>): all generated solution matrices.
+    /// - `all_distances` (Vec<i128>): total traveling distance for each solution.
+    /// - `all_feasible` (Vec<bool>): feasibility of each solution, parallel to `all_distances`
+    ///   (empty with `only_distance`, since the constraints are not checked).
+    ///
+    /// A `TtpError` is returned if saving a solution to file fails.
+    ///
+    /// # Example
+    /// ```
+    /// let data = Rawdata::generate_example();
+    /// let distance_matrix = DistanceMatrix::from(vec![vec![0,5,7], vec![5,0,3], vec![7,3,0]]);
+    /// let permutation = vec![0,1,2];
+    /// let (solutions, distances, feasible) = generate_all_solutions(&data, &distance_matrix, permutation, &GenerationOptions::default()).unwrap();
+    /// println!("Solutions length {}", solutions.len());
+    /// println!("Distances: {:?}", distances);
+    /// ```
+    pub fn generate_all_solutions(
+        data: &Rawdata,
+        traveling_distance_matrix: &DistanceMatrix,
+        permutation: Vec<Vec<i32>>,
+        options: &GenerationOptions,
+    ) -> Result<SolutionPool, TtpError> {
+        let mut solutions: Vec<Solution> = Vec::new();
+        let mut all_distances: Vec<i128> = Vec::new();
+        let mut all_feasible: Vec<bool> = Vec::new();
+        let mut all_directions: Vec<&str> = Vec::new();
+        let mut all_fixed_teams: Vec<usize> = Vec::new();
+        let mut chunk: Vec<Solution> = Vec::new();
+        let mut chunk_count = 0;
+
+        let mut skipped = 0;
+        let started = Instant::now();
+        let mut timed_out = false;
+
+        if let Some(indices) = &options.permutation_indices
+            && let Some(index) = indices.iter().find(|&&index| index >= permutation.len())
+        {
+            return Err(TtpError::Validation(format!(
+                "permutation index {} out of range, there are {} permutations",
+                index,
+                permutation.len()
+            )));
+        }
+        let selected = |index: usize| options.permutation_indices.as_ref().is_none_or(|indices| indices.contains(&index));
+
+        let num_teams = data.n_teams();
+        let per_permutation = options.limit_per_permutation.map_or(2 * num_teams, |limit| limit.min(2 * num_teams));
+        let total_perms = per_permutation * (0..permutation.len()).filter(|&index| selected(index)).count();
+        let mut shuffle_rng = options.shuffle_seed.map(StdRng::seed_from_u64);
+        let mut sample_rng = StdRng::seed_from_u64(options.sample_seed);
+
+        if options.save && options.split_by_feasibility {
+            fs::create_dir_all(format!("{}/feasible", options.path))?;
+            fs::create_dir_all(format!("{}/infeasible", options.path))?;
+        }
+
+        // Create progress bar
+        let progress = ProgressBarLog::with_log_interval(total_perms as u64, options.progress_interval).with_eta(options.eta_warmup);
+
+        'generation: for (index_permutation, team) in permutation.into_iter().enumerate() {
+            let mut order = Solution::generation_order(num_teams, shuffle_rng.as_mut());
+            if per_permutation < order.len() {
+                let mut picked = index::sample(&mut sample_rng, order.len(), per_permutation).into_vec();
+                picked.sort_unstable();
+                order = picked.into_iter().map(|position| order[position]).collect();
+            }
+
+            // The order is drawn first, so skipping a permutation leaves the next ones unchanged
+            if !selected(index_permutation) {
+                continue;
+            }
+
+            let teams_ordered: Vec<Team> = team
+                .iter()
+                .filter_map(|id| data.teams.iter().find(|t| t.id == *id))
+                .cloned()
+                .collect();
+
+            // Log the permutation
+            info!("Permutation: {:?}", team);
+
+            for (direction, fixed_team) in order {
+                if let Some(timeout) = options.timeout
+                    && started.elapsed() >= timeout
+                {
+                    timed_out = true;
+                    break 'generation;
+                }
+
+                // Ids follow the canonical order (upward first, then by fixed team)
+                let id_solution = 1 + index_permutation * 2 * num_teams
+                    + if direction { 0 } else { num_teams }
+                    + fixed_team;
+
+                // Skip solutions already saved by a previous run
+                let solution_file = options.names.file_name(&format!("solution_{}", id_solution), Some(id_solution as i32), "json");
+                let solution_path = |subdirectory: &str| format!("{}/{}{}", options.path, subdirectory, solution_file);
+                let saved = if options.split_by_feasibility {
+                    Path::new(&solution_path("feasible/")).exists() || Path::new(&solution_path("infeasible/")).exists()
+                } else {
+                    Path::new(&solution_path("")).exists()
+                };
+                if options.resume && saved {
+                    skipped += 1;
+                    progress.inc();
+                    continue;
+                }
+
+                // Generate and improve solution, without the construction steps when only the summary is requested
+                let generate = || {
+                    let mut rng = options.tie_break_seed.map(|seed| Solution::construction_rng(seed, id_solution as i32));
+                    let mut solution = Solution::generate_solution(data, &teams_ordered, fixed_team, direction, id_solution as i32, options.method, rng.as_mut());
+                    solution.improve(data, traveling_distance_matrix, &options.improvements);
+                    solution
+                };
+                let mut temporary_solution = if options.summary_only { logging::quietly(generate) } else { generate() };
+                temporary_solution.tag = options.tag.clone();
+
+                // Each pair meets as often at home as away when the meetings are even
+                if options.evaluation.max_meetings.is_multiple_of(2) {
+                    for (team, balance) in temporary_solution.home_away_balance() {
+                        if balance != 0 {
+                            warn!("Solution {} | Team {} has unbalanced home/away games: {:+}", id_solution, team, balance);
+                        }
+                    }
+                }
+
+                if options.only_distance {
+                    // Travel only, the constraints are not checked
+                    let distance_solution =
+                        Solution::evaluate_objective(traveling_distance_matrix, &temporary_solution);
+                    if !options.summary_only {
+                        info!("Solution {} | Distance: {}", id_solution, distance_solution);
+                    }
+                    all_distances.push(distance_solution as i128);
+                } else {
+                    // Log solution details, unless only the summary is requested
+                    let (distance_solution, feasible_solution) = if options.summary_only {
+                        let (distance, cap_constraints, sep_constraints, round_robin_respect) =
+                            Solution::evaluate_solution(data, &options.evaluation, traveling_distance_matrix, &temporary_solution);
+                        (distance, Solution::is_feasible(cap_constraints, sep_constraints, round_robin_respect))
+                    } else {
+                        Solution::log_solution(&temporary_solution, data, &options.evaluation, traveling_distance_matrix)
+                    };
+
+                    // Store the distance and the feasibility
+                    all_distances.push(distance_solution as i128);
+                    all_feasible.push(feasible_solution);
+                }
+                solutions.push(temporary_solution.clone());
+                all_directions.push(if direction { "upward" } else { "downward" });
+                all_fixed_teams.push(fixed_team);
+
+                // Save to file
+                if options.save && let Some(chunk_size) = options.chunk_size {
+                    chunk.push(temporary_solution.clone());
+                    if chunk.len() >= chunk_size {
+                        chunk_count += 1;
+                        let chunk_file = options.names.file_name(&format!("chunk_{}", chunk_count), None, "json");
+                        save_to_file(&chunk, &format!("{}/{}", options.path, chunk_file), options.compact_json)?;
+                        chunk.clear();
+                    }
+                } else if options.save {
+                    let subdirectory = match all_feasible.last() {
+                        Some(true) if options.split_by_feasibility => "feasible/",
+                        Some(false) if options.split_by_feasibility => "infeasible/",
+                        _ => "",
+                    };
+                    save_to_file(&temporary_solution, &solution_path(subdirectory), options.compact_json)?;
+                }
+
+                // Update bar inc
+                progress.inc();
+            }
+        }
+
+        if timed_out {
+            progress.stop("timed out");
+            info!(
+                "Timeout: generation stopped after {} with {} of {} solutions completed",
+                format_duration(started.elapsed()),
+                solutions.len(),
+                total_perms
+            );
+        }
+
+        if !chunk.is_empty() {
+            chunk_count += 1;
+            let chunk_file = options.names.file_name(&format!("chunk_{}", chunk_count), None, "json");
+            save_to_file(&chunk, &format!("{}/{}", options.path, chunk_file), options.compact_json)?;
+        }
+        if chunk_count > 0 {
+            info!("Saved {} solutions in {} chunk files", solutions.len(), chunk_count);
+        }
+
+        if options.resume {
+            info!("Resume: skipped {} solutions already saved", skipped);
+        }
+
+        if !all_distances.is_empty() {
+            Statistics::log_groups("Direction", &Statistics::summary_over_groups(&all_distances, &all_directions));
+            Statistics::log_groups("Fixed team", &Statistics::summary_over_groups(&all_distances, &all_fixed_teams));
+        }
+
+        if !options.only_distance {
+            match Solution::best_feasible_distance(&solutions, &all_distances, &all_feasible) {
+                Some((id, distance)) => info!("Best feasible solution: {} | Distance: {}", id, distance),
+                None => info!("Best feasible solution: none, no feasible solution was found"),
+            }
+        }
+
+        Ok((solutions, all_distances, all_feasible))
+    }
+
+    /// Returns the order in which the `(direction, fixed_team)` pairs of a permutation are built.
+    ///
+    /// Without a random number generator, the order is all upward pairs then all downward pairs,
+    /// each by increasing fixed team. With one, this order is shuffled, so the same seed always
+    /// yields the same sequence of orders.
+    ///
+    /// # Arguments
+    /// * `num_teams` - The number of teams of the instance.
+    /// * `rng` - An optional seeded random number generator used to shuffle the order.
+    ///
+    /// # Returns
+    /// A `Vec<(bool, usize)>` with the `2 * num_teams` pairs `(upward, fixed_team)`.
+    ///
+    /// # Example
+    /// ```
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// let order = Solution::generation_order(8, Some(&mut rng));
+    /// assert_eq!(order.len(), 16);
+    /// ```
+    pub fn generation_order(num_teams: usize, rng: Option<&mut StdRng>) -> Vec<(bool, usize)> {
+        let mut order: Vec<(bool, usize)> = [true, false]
+            .into_iter()
+            .flat_map(|direction| (0..num_teams).map(move |fixed_team| (direction, fixed_team)))
+            .collect();
+
+        if let Some(rng) = rng {
+            order.shuffle(rng);
+        }
+
+        order
+    }
+
+    /// Rebuilds a single solution of `generate_all_solutions` from its id.
+    ///
+    /// Ids are assigned deterministically (`1 + permutation * 2n + (0 upward, n downward) +
+    /// fixed_team`), so the same permutations and construction method give back the same
+    /// schedule without generating the whole pool.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `permutations` - The permutations the pool was generated from, in the same order.
+    /// * `id` - The id of the solution to rebuild.
+    /// * `method` - The `ConstructionMethod` the pool was generated with.
+    /// * `tie_break_seed` - The `tie_break_seed` of the `GenerationOptions` of the pool.
+    ///
+    /// # Returns
+    /// `Some(Solution)` with the given id, or `None` if the id is out of the range of the pool.
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::regenerate(&data, &permutations, 42, ConstructionMethod::Florian, None).unwrap();
+    /// assert_eq!(solution.id, 42);
+    /// ```
+    pub fn regenerate(
+        data: &Rawdata,
+        permutations: &[Vec<i32>],
+        id: i32,
+        method: ConstructionMethod,
+        tie_break_seed: Option<u64>,
+    ) -> Option<Solution> {
+        let num_teams = data.n_teams();
+        let index = usize::try_from(id).ok()?.checked_sub(1)?;
+        let permutation = permutations.get(index / (2 * num_teams))?;
+        let offset = index % (2 * num_teams);
+
+        let teams_ordered: Vec<Team> = permutation
+            .iter()
+            .filter_map(|team_id| data.teams.iter().find(|t| t.id == *team_id))
+            .cloned()
+            .collect();
+
+        let mut rng = tie_break_seed.map(|seed| Solution::construction_rng(seed, id));
+        Some(Solution::generate_solution(data, &teams_ordered, offset % num_teams, offset < num_teams, id, method, rng.as_mut()))
+    }
+
+    /// Returns the random number generator of the construction of one solution.
+    ///
+    /// All the randomness of the construction flows from the run seed, but each solution gets
+    /// its own generator derived from the seed and its id. A schedule thus only depends on
+    /// the seed and its id, so resuming, `limit_per_permutation`, `timeout` or `regenerate`
+    /// build the same schedule as the full run, and every construction method draws from the
+    /// same stream for a given solution.
+    ///
+    /// # Arguments
+    /// * `seed` - The seed of the run.
+    /// * `id` - The id of the solution.
+    ///
+    /// # Returns
+    /// A seeded `StdRng`.
+    ///
+    /// # Example
+    /// ```
+    /// let mut rng = Solution::construction_rng(42, 1);
+    /// let solution = Solution::generate_circle_solution(&data, 0, true, Some(&mut rng));
+    /// ```
+    pub fn construction_rng(seed: u64, id: i32) -> StdRng {
+        StdRng::seed_from_u64(seed ^ (id as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15))
+    }
+
+    /// Generates a schedule using Florian's method construction.
+    ///
+    /// This function constructs a round-robin schedule fixing a team. The `upward`
+    /// flag determines the pattern of home/away assignments for the first match
+    /// of each pairing.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to `Rawdata` containing team information.
+    /// * `fixed_team` - The index of the team to remain fixed during rotations.
+    /// * `upward` - If `true`, the home team assignment follows an upward pattern; otherwise downward.
+    ///
+    /// # Returns
+    /// A `Solution` struct with the scheduled matches for all slots and teams.
+    ///
+    /// # Example
+    /// ```
+    /// let data = Rawdata::generate_example();
+    /// let solution = generate_florian_solution(&data, 0, true);
+    /// println!("{}", solution_to_string(&solution, &data));
+    /// ```
+    pub fn generate_florian_solution(data: &Rawdata, fixed_team: usize, upward: bool) -> Solution {
+        let _timer = ScopedTimer::new("generate_florian_solution");
+        info!(
+            "Starting Florian's construction for {} teams | Fixed team: {} | Pattern: {}",
+            data.n_teams(),
+            fixed_team,
+            if upward {
+                "Upward direction"
+            } else {
+                "Downward direction"
+            }
+        );
+
+        let mut solution_matrix = Solution::new(data);
+
+        let mut teams: Vec<usize> = data
+            .teams
+            .iter()
+            .map(|team| team.id as usize)
+            .collect();
+
+        let fixed_team = teams.remove(fixed_team);
+        teams.push(fixed_team);
+
+        // A single round-robin instance (see `Rawdata::restrict_to_single_round_robin`) stops after the first half
+        let num_rounds = (2 * (data.n_teams() - 1)).min(solution_matrix.n_slots());
+
+        for round in 0..num_rounds {
+            info!("Round: {}", round);
+            info!("Teams before rotation: {:?}", teams);
+            for i in 0..(data.n_teams() / 2) {
+                let team_a = teams[i];
+                let team_b = teams[data.n_teams() - 1 - i];
+                let home_first = (round % 2 == 0) == upward;
+
+                if home_first {
+                    solution_matrix.solution[round][team_a] = Game {
+                        home_game: true,
+                        opponent: team_b as i32,
+                    };
+                    solution_matrix.solution[round][team_b] = Game {
+                        home_game: false,
+                        opponent: team_a as i32,
+                    };
+                } else {
+                    solution_matrix.solution[round][team_a] = Game {
+                        home_game: false,
+                        opponent: team_b as i32,
+                    };
+                    solution_matrix.solution[round][team_b] = Game {
+                        home_game: true,
+                        opponent: team_a as i32,
+                    };
+                }
+
+                info!(
+                    "Pairing: Team {} vs Team {} | {} is home",
+                    team_a,
+                    team_b,
+                    if home_first { team_a } else { team_b }
+                );
+            }
+
+            let fixed_team = teams.remove(teams.len() - 1);
+            teams.rotate_right(1);
+            teams.push(fixed_team);
+            info!("Teams after rotation: {:?}", teams);
+        }
+
+        info!(
+            "Final solution for {} teams | Fixed team: {} | Pattern: {}",
+            data.n_teams(),
+            fixed_team,
+            if upward {
+                "Upward direction"
+            } else {
+                "Downward direction"
+            }
+        );
+
+        solution_matrix
+    }
+
+    /// Generates a schedule using the classic circle method with a mirrored second half.
+    ///
+    /// The teams are placed on a circle with one fixed team and rotated as in
+    /// `generate_florian_solution`, which gives a single round-robin over the first
+    /// `teams - 1` rounds. Within those rounds the fixed team alternates home and away
+    /// while every other pairing hosts the team on the left side of the circle. The second
+    /// half repeats the first one with swapped venues.
+    ///
+    /// Which side hosts a pairing other than the fixed team's is a tie, since the mirrored
+    /// half balances it anyway. With `rng`, this tie is broken by a coin flip drawn from it.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to `Rawdata` containing team information.
+    /// * `fixed_team` - The index of the team to remain fixed during rotations.
+    /// * `upward` - If `true`, the left side of the circle hosts in the first half; otherwise
+    ///   the right side does.
+    /// * `rng` - An optional random number generator breaking the host ties.
+    ///
+    /// # Returns
+    /// A `Solution` struct with the scheduled matches for all slots and teams.
+    ///
+    /// # Example
+    /// ```
+    /// let data = Rawdata::generate_example();
+    /// let solution = generate_circle_solution(&data, 0, true, None);
+    /// println!("{}", solution_to_string(&solution, &data));
+    /// ```
+    pub fn generate_circle_solution(data: &Rawdata, fixed_team: usize, upward: bool, mut rng: Option<&mut StdRng>) -> Solution {
+        let _timer = ScopedTimer::new("generate_circle_solution");
+        info!(
+            "Starting circle construction for {} teams | Fixed team: {} | Pattern: {}",
+            data.n_teams(),
+            fixed_team,
+            if upward {
+                "Upward direction"
+            } else {
+                "Downward direction"
+            }
+        );
+
+        let mut solution_matrix = Solution::new(data);
+        let num_teams = data.n_teams();
+        let half = num_teams - 1;
+
+        let mut teams: Vec<usize> = data
+            .teams
+            .iter()
+            .map(|team| team.id as usize)
+            .collect();
+
+        let fixed_team = teams.remove(fixed_team);
+        teams.push(fixed_team);
+
+        for round in 0..half {
+            for i in 0..(num_teams / 2) {
+                let team_a = teams[i];
+                let team_b = teams[num_teams - 1 - i];
+
+                // The fixed team alternates, the other pairings keep the left side at home (or flip a coin)
+                let left_home = if i == 0 {
+                    (round % 2 == 0) == upward
+                } else {
+                    rng.as_deref_mut().map_or(upward, |rng| rng.random())
+                };
+                let (home, away) = if left_home { (team_a, team_b) } else { (team_b, team_a) };
+
+                // The mirrored second half is left out of a single round-robin instance
+                for (slot, home, away) in [(round, home, away), (round + half, away, home)] {
+                    if slot >= solution_matrix.n_slots() {
+                        continue;
+                    }
+                    solution_matrix.solution[slot][home] = Game {
+                        home_game: true,
+                        opponent: away as i32,
+                    };
+                    solution_matrix.solution[slot][away] = Game {
+                        home_game: false,
+                        opponent: home as i32,
+                    };
+                }
+            }
+
+            let fixed_team = teams.remove(teams.len() - 1);
+            teams.rotate_right(1);
+            teams.push(fixed_team);
+        }
+
+        solution_matrix
+    }
+
+    /// Converts a `Solution` matrix into a formatted string representation.
+    ///
+    /// This function generates a human-readable string showing the schedule of all teams
+    /// for each slot. Each cell shows the opponent ID followed by `H` for a home game or
+    /// `A` for an away game. The output also includes team names and IDs as headers.
+    ///
+    /// # Arguments
+    /// * `solution_matrix` - A reference to the `Solution` containing the schedule.
+    /// * `data` - A reference to the `Rawdata` struct containing team information.
+    ///
+    /// # Returns
+    /// A `String` representing the formatted solution.
+    ///
+    /// # Example
+    /// ```
+    /// let data = Rawdata::generate_example();
+    /// let solution = Solution::generate_example();
+    /// let output_str = solution_to_string(&solution, &data);
+    /// println!("{}", output_str);
+    /// ```
+    /// Example output:
+    /// ```text
+    /// Id: 1
+    ///          ATL:0    NYM:1    PHI:2
+    /// Slot:0    1H       2A       0H
+    /// Slot:1    2H       0A       1H
+    /// ```
+    pub fn solution_to_string(solution_matrix: &Solution, data: &Rawdata) -> String {
+        let mut output = String::new();
+        output.push_str(&format!("Id: {}\n", solution_matrix.id));
+
+        output.push_str(&format!("{:>8}", ""));
+        for team_id in 0..data.n_teams() {
+            output.push_str(&format!(
+                "{:>8}",
+                format!("{}:{}", data.teams[team_id].name, data.teams[team_id].id)
+            ));
+        }
+        output.push('\n');
+
+        for slot_id in 0..solution_matrix.n_slots() {
+            output.push_str(&format!("{:>8}", format!("Slot:{}", slot_id)));
+            for team_id in 0..solution_matrix.n_teams() {
+                output.push_str(&format!(
+                    "{:>8}",
+                    format!(
+                        "{}{}",
+                        solution_matrix.opponent_of(slot_id, team_id).unwrap_or(-1),
+                        if solution_matrix.is_home(slot_id, team_id) { "H" } else { "A" }
+                    )
+                ));
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Returns a copy of the solution restricted to its first `k` slots.
+    ///
+    /// Every kept slot still has one game per team, so the truncated schedule can be
+    /// evaluated as any other solution. Meetings scheduled after slot `k` are dropped,
+    /// so a truncated double round-robin is no longer complete.
+    ///
+    /// # Arguments
+    /// * `k` - The number of slots to keep. Values larger than the number of slots keep the
+    ///   whole schedule.
+    ///
+    /// # Returns
+    /// A new `Solution` with the same `id` and the first `k` slots.
+    ///
+    /// # Panics
+    /// This function will panic if `k` is `0`, since a solution needs at least one slot.
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// let first_half = solution.truncate_slots(solution.solution.len() / 2);
+    /// ```
+    pub fn truncate_slots(&self, k: usize) -> Solution {
+        assert!(k > 0, "A solution needs at least one slot");
+
+        Solution {
+            id: self.id,
+            solution: self.solution.iter().take(k).cloned().collect(),
+            tag: self.tag.clone(),
+        }
+    }
+
+    /// Relabels the teams of the solution according to a permutation.
+    ///
+    /// Team `t` of the original schedule becomes team `perm[t]` in the relabeled one:
+    /// the column of team `t` is moved to column `perm[t]` and every `opponent` id `o`
+    /// is replaced by `perm[o]`. The home/away pattern is kept, so the resulting schedule
+    /// is structurally equivalent to the original one (only the distances change,
+    /// according to the distance matrix entries of the new labels).
+    ///
+    /// # Arguments
+    /// * `perm` - A slice where `perm[t]` is the new label of team `t`. It must be a
+    ///   permutation of `0..teams`.
+    ///
+    /// # Returns
+    /// A new `Solution` with the same `id` and the relabeled schedule.
+    ///
+    /// # Panics
+    /// This function will panic if `perm` is shorter than the number of teams or
+    /// contains an id out of range.
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// let perm = vec![1, 0, 3, 2];
+    /// let relabeled = solution.apply_permutation(&perm);
+    /// let opponent = solution.solution[0][0].opponent;
+    /// assert_eq!(relabeled.solution[0][1].opponent, perm[opponent as usize]);
+    /// ```
+    pub fn apply_permutation(&self, perm: &[i32]) -> Solution {
+        let mut relabeled = self.clone();
+
+        for (slot, row) in self.solution.iter().enumerate() {
+            for (team, game) in row.iter().enumerate() {
+                relabeled.solution[slot][perm[team] as usize] = Game {
+                    home_game: game.home_game,
+                    opponent: if game.opponent >= 0 {
+                        perm[game.opponent as usize]
+                    } else {
+                        game.opponent
+                    },
+                };
+            }
+        }
+
+        relabeled
+    }
+
+    /// Applies improvements to the solution, in the order they are listed.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing team information.
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    /// * `improvements` - The `Improvement`s to apply.
+    ///
+    /// # Example
+    /// ```
+    /// let mut solution = Solution::generate_florian_solution(&data, 0, true);
+    /// solution.improve(&data, &distance_matrix, &[Improvement::Orientation, Improvement::RoundSwaps]);
+    /// ```
+    pub fn improve(&mut self, data: &Rawdata, traveling_distance_matrix: &DistanceMatrix, improvements: &[Improvement]) {
+        for improvement in improvements {
+            match improvement {
+                Improvement::Orientation => {
+                    let flips = self.repair_orientation(data);
+                    if flips > 0 {
+                        info!("Solution {} | Orientation repair: {} games flipped", self.id, flips);
+                    }
+                }
+                Improvement::RoundSwaps => {
+                    let delta = self.descend_round_swaps(traveling_distance_matrix);
+                    if delta < 0 {
+                        info!("Solution {} | Round swaps: distance {:+}", self.id, delta);
+                    }
+                }
+                Improvement::HomeAway => {
+                    let before = Solution::evaluate_objective(traveling_distance_matrix, self);
+                    let (optimized, distance) = self.optimize_home_away(traveling_distance_matrix);
+                    if distance < before {
+                        info!("Solution {} | Home/away optimization: distance {:+}", self.id, distance - before);
+                    }
+                    self.solution = optimized.solution;
+                }
+            }
+        }
+    }
+
+    /// Flips the orientation of selected games to balance the home/away count of each team.
+    ///
+    /// The pairings are never changed: a flip swaps the home and away sides of one game
+    /// (both cells of the game are updated). The function greedily scans the games and
+    /// flips one whenever it strictly reduces the total imbalance
+    /// `sum(|home_count - away_count|)`, until no improving flip is left.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing team information.
+    ///
+    /// # Returns
+    /// The number of flips made (`i32`).
+    ///
+    /// # Example
+    /// ```
+    /// let mut solution = Solution::generate_florian_solution(&data, 0, true);
+    /// let flips = solution.repair_orientation(&data);
+    /// println!("Flipped {} games", flips);
+    /// ```
+    pub fn repair_orientation(&mut self, data: &Rawdata) -> i32 {
+        let mut balance = vec![0i32; data.n_teams()];
+        for row in &self.solution {
+            for (team, game) in row.iter().enumerate() {
+                if game.opponent >= 0 {
+                    balance[team] += if game.home_game { 1 } else { -1 };
+                }
+            }
+        }
+
+        let mut flips = 0;
+        let mut improved = true;
+
+        while improved {
+            improved = false;
+            for slot in 0..self.n_slots() {
+                for team in 0..data.n_teams() {
+                    let game = &self.solution[slot][team];
+                    if !game.home_game || game.opponent < 0 {
+                        continue;
+                    }
+                    let opponent = game.opponent as usize;
+
+                    let (home, away) = (balance[team], balance[opponent]);
+                    let delta = (home - 2).abs() - home.abs() + (away + 2).abs() - away.abs();
+
+                    if delta < 0 {
+                        self.solution[slot][team].home_game = false;
+                        self.solution[slot][opponent].home_game = true;
+                        balance[team] -= 2;
+                        balance[opponent] += 2;
+                        flips += 1;
+                        improved = true;
+                    }
+                }
+            }
+        }
+
+        flips
+    }
+
+    /// Chooses the home/away orientation of the games to reduce the traveling distance.
+    ///
+    /// The pairings of every slot are kept. For each pair of teams, all its games are flipped
+    /// together (home and away sides swapped), so a pair meeting twice still plays once at
+    /// each venue. The function greedily scans the pairs and keeps a flip whenever it strictly
+    /// reduces the distance of the two teams, until no improving flip is left. The constraints
+    /// are not checked, so the result should be evaluated again.
+    ///
+    /// # Arguments
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    ///
+    /// # Returns
+    /// A tuple `(solution, distance)` with the reoriented schedule (same `id`) and its total
+    /// traveling distance, never larger than the distance of `self`.
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// let (optimized, distance) = solution.optimize_home_away(&distance_matrix);
+    /// assert!(distance <= Solution::evaluate_solution(&data, &EvaluationOptions::default(), &distance_matrix, &solution).0);
+    /// ```
+    pub fn optimize_home_away(&self, traveling_distance_matrix: &DistanceMatrix) -> (Solution, i32) {
+        let mut optimized = self.clone();
+        let mut per_team = optimized.per_team_distances(traveling_distance_matrix);
+
+        let mut meetings: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+        for (slot, row) in self.solution.iter().enumerate() {
+            for (team, game) in row.iter().enumerate() {
+                if game.opponent > team as i32 {
+                    meetings.entry((team, game.opponent as usize)).or_default().push(slot);
+                }
+            }
+        }
+
+        let flip = |solution: &mut Solution, (team_a, team_b): (usize, usize), slots: &[usize]| {
+            for &slot in slots {
+                solution.solution[slot][team_a].home_game ^= true;
+                solution.solution[slot][team_b].home_game ^= true;
+            }
+        };
+
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for (&(team_a, team_b), slots) in &meetings {
+                flip(&mut optimized, (team_a, team_b), slots);
+
+                let distance_a = optimized.team_distance(traveling_distance_matrix, team_a);
+                let distance_b = optimized.team_distance(traveling_distance_matrix, team_b);
+
+                if distance_a + distance_b < per_team[team_a] + per_team[team_b] {
+                    per_team[team_a] = distance_a;
+                    per_team[team_b] = distance_b;
+                    improved = true;
+                } else {
+                    flip(&mut optimized, (team_a, team_b), slots);
+                }
+            }
+        }
+
+        (optimized, per_team.iter().sum())
+    }
+
+    /// Swaps two entire slots (rounds) of the solution.
+    ///
+    /// Each slot of a valid schedule is a perfect matching of the teams, so swapping two
+    /// slots keeps the double round-robin structure (every pair still meets the same number
+    /// of times, with the same home/away sides). Constraints depending on the order of the
+    /// slots (capacity, separation) and the traveling distance may change.
+    ///
+    /// # Arguments
+    /// * `a` - The index of the first slot.
+    /// * `b` - The index of the second slot.
+    ///
+    /// # Panics
+    /// This function will panic if `a` or `b` is out of range.
+    ///
+    /// # Example
+    /// ```
+    /// let mut solution = Solution::generate_florian_solution(&data, 0, true);
+    /// let delta = solution.swap_rounds_delta(&distance_matrix, 0, 3);
+    /// solution.swap_rounds(0, 3);
+    /// ```
+    pub fn swap_rounds(&mut self, a: usize, b: usize) {
+        self.solution.swap(a, b);
+    }
+
+    /// Computes the change of total traveling distance caused by `swap_rounds(a, b)`.
+    ///
+    /// Only the legs entering and leaving slots `a` and `b` are affected by the swap, so
+    /// the delta is computed over these legs instead of re-evaluating the whole schedule.
+    /// The legs follow `evaluate_objective`: each team starts at home and travels to the
+    /// location of each slot in order.
+    ///
+    /// # Arguments
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    /// * `a` - The index of the first slot.
+    /// * `b` - The index of the second slot.
+    ///
+    /// # Returns
+    /// The distance after the swap minus the distance before the swap (`i32`).
+    ///
+    /// # Example
+    /// ```
+    /// let before = Solution::evaluate_solution(&data, &EvaluationOptions::default(), &distance_matrix, &solution).0;
+    /// let delta = solution.swap_rounds_delta(&distance_matrix, 1, 4);
+    /// solution.swap_rounds(1, 4);
+    /// assert_eq!(Solution::evaluate_solution(&data, &EvaluationOptions::default(), &distance_matrix, &solution).0, before + delta);
+    /// ```
+    pub fn swap_rounds_delta(&self, traveling_distance_matrix: &DistanceMatrix, a: usize, b: usize) -> i32 {
+        let num_slots = self.n_slots();
+        let num_teams = self.n_teams();
+
+        let mut legs = vec![a, a + 1, b, b + 1];
+        legs.retain(|&leg| leg < num_slots);
+        legs.sort();
+        legs.dedup();
+
+        let swapped = |slot: usize| if slot == a { b } else if slot == b { a } else { slot };
+        let location = |team: usize, slot: usize| {
+            let game = &self.solution[slot][team];
+            if game.home_game { team } else { game.opponent as usize }
+        };
+
+        let mut delta = 0;
+        for team in 0..num_teams {
+            for &leg in &legs {
+                let (old_from, new_from) = if leg == 0 {
+                    (team, team)
+                } else {
+                    (location(team, leg - 1), location(team, swapped(leg - 1)))
+                };
+
+                delta += traveling_distance_matrix.get(new_from, location(team, swapped(leg)))
+                    - traveling_distance_matrix.get(old_from, location(team, leg));
+            }
+        }
+
+        delta
+    }
+
+    /// Swaps pairs of slots as long as a swap shortens the total traveling distance.
+    ///
+    /// Each pass tries every pair of slots and keeps the swap with the largest gain (see
+    /// `swap_rounds_delta`), until no swap is improving. The pairings and orientations of each
+    /// slot are kept, but the constraints depending on the order of the slots are not checked,
+    /// so the result should be evaluated again.
+    ///
+    /// # Arguments
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    ///
+    /// # Returns
+    /// The change of total traveling distance (`i32`, `0` or negative).
+    ///
+    /// # Example
+    /// ```
+    /// let delta = solution.descend_round_swaps(&distance_matrix);
+    /// println!("Distance reduced by {}", -delta);
+    /// ```
+    pub fn descend_round_swaps(&mut self, traveling_distance_matrix: &DistanceMatrix) -> i32 {
+        let num_slots = self.n_slots();
+        let mut total = 0;
+
+        loop {
+            let best = (0..num_slots)
+                .flat_map(|a| (a + 1..num_slots).map(move |b| (a, b)))
+                .map(|(a, b)| (self.swap_rounds_delta(traveling_distance_matrix, a, b), a, b))
+                .min();
+            match best {
+                Some((delta, a, b)) if delta < 0 => {
+                    self.swap_rounds(a, b);
+                    total += delta;
+                }
+                _ => return total,
+            }
+        }
+    }
+
+    /// Converts the solution into a pure opponents grid, without home/away information.
+    ///
+    /// The grid is indexed as `grid[team][slot]` and each cell holds the id of the
+    /// opponent faced by `team` in `slot`. Unassigned cells (byes) are kept as `-1`.
+    ///
+    /// # Returns
+    /// A 2D vector (`Vec<Vec<i32>>`) of size `teams x slots` with the opponent ids.
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// let grid = solution.to_opponents_grid();
+    /// assert_eq!(grid[0][1], solution.solution[1][0].opponent);
+    /// ```
+    pub fn to_opponents_grid(&self) -> Vec<Vec<i32>> {
+        let num_slots = self.n_slots();
+        let num_teams = self.n_teams();
+
+        (0..num_teams)
+            .map(|team| (0..num_slots).map(|slot| self.solution[slot][team].opponent).collect())
+            .collect()
+    }
+
+    /// Lists the matches of each slot as `(home_team, away_team)` pairs.
+    ///
+    /// Each pairing appears once, taken from the side that plays at home. Byes (`-1`
+    /// opponents) are omitted, so a slot of an even compact instance has `teams / 2` pairs.
+    ///
+    /// # Returns
+    /// A `Vec<Vec<(i32, i32)>>` indexed by slot, each holding the `(home, away)` team ids
+    /// ordered by home team.
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// let matches = solution.matches_per_slot();
+    /// assert_eq!(matches[0].len(), data.n_teams() / 2);
+    /// ```
+    pub fn matches_per_slot(&self) -> Vec<Vec<(i32, i32)>> {
+        self.solution
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, game)| game.home_game && game.opponent >= 0)
+                    .map(|(team, game)| (team as i32, game.opponent))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Lists the games of each round with the team names, one line per slot.
+    ///
+    /// Each line reads `Round <slot>: <home> (H) vs <away>, ...`, the games ordered by home
+    /// team (see `matches_per_slot`) and the rounds numbered by slot index as in the grid and
+    /// the violation reports. Teams without a game in a slot are listed after the games as
+    /// `<team> (bye)`.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing the team names.
+    ///
+    /// # Returns
+    /// A `String` with one line per slot, each ending with a line break.
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// print!("{}", solution.to_round_listing(&data));
+    /// ```
+    /// Example output:
+    /// ```text
+    /// Round 0: NYM (H) vs ATL, PHI (H) vs CHI, MON (H) vs CIN, FLA (H) vs PIT
+    /// Round 1: ATL (H) vs CHI, FLA (H) vs MON, PIT (H) vs PHI, CIN (H) vs NYM
+    /// ```
+    pub fn to_round_listing(&self, data: &Rawdata) -> String {
+        let team_name = |team: i32| &data.teams[team as usize].name;
+        let mut output = String::new();
+
+        for (slot, matches) in self.matches_per_slot().iter().enumerate() {
+            let games = matches
+                .iter()
+                .map(|(home, away)| format!("{} (H) vs {}", team_name(*home), team_name(*away)));
+            let byes = self.solution[slot]
+                .iter()
+                .enumerate()
+                .filter(|(_, game)| game.opponent < 0)
+                .map(|(team, _)| format!("{} (bye)", team_name(team as i32)));
+
+            output.push_str(&format!("Round {}: {}\n", slot, games.chain(byes).collect::<Vec<_>>().join(", ")));
+        }
+
+        output
+    }
+
+    /// Saves the opponents grid of the solution as a csv file.
+    ///
+    /// The file has one row per team and one column per slot. The first line is a header
+    /// (`team,slot_0,slot_1,...`) and the first column holds the team index. Each cell holds
+    /// the opponent of `to_opponents_grid` (`-1` for a bye). With `signed`, the cells also
+    /// carry the home/away of the game: the id of the opponent counted from `1`, positive for
+    /// a home game and negative for an away game (`0` for a bye). A signed file can be loaded
+    /// back with `from_opponents_csv`.
+    ///
+    /// # Arguments
+    /// * `path` - A string slice specifying the file path.
+    /// * `signed` - If `true`, the home/away of each game is written in the sign of its cell.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with an I/O error.
+    ///
+    /// # Example
+    /// ```
+    /// solution.save_opponents_csv("output/opponents_1.csv", false).expect("Failed to save file");
+    /// ```
+    /// Example output (unsigned, then signed):
+    /// ```text
+    /// team,slot_0,slot_1,slot_2
+    /// 0,1,2,3
+    /// 1,0,3,2
+    ///
+    /// team,slot_0,slot_1,slot_2
+    /// 0,2,-3,4
+    /// 1,-1,4,-3
+    /// ```
+    pub fn save_opponents_csv(&self, path: &str, signed: bool) -> std::io::Result<()> {
+        let mut output = String::from("team");
+        for slot in 0..self.n_slots() {
+            output.push_str(&format!(",slot_{}", slot));
+        }
+        output.push('\n');
+
+        for (team, row) in self.to_opponents_grid().iter().enumerate() {
+            output.push_str(&team.to_string());
+            for (slot, opponent) in row.iter().enumerate() {
+                let cell = if !signed {
+                    *opponent
+                } else if *opponent < 0 {
+                    0
+                } else if self.solution[slot][team].home_game {
+                    opponent + 1
+                } else {
+                    -(opponent + 1)
+                };
+                output.push_str(&format!(",{}", cell));
+            }
+            output.push('\n');
+        }
+
+        fs::write(path, output)
+    }
+
+    /// Loads a schedule from a signed opponents csv (see `save_opponents_csv`).
+    ///
+    /// The csv has a `team,slot_0,...` header, then one row per team starting with the team
+    /// index (from `0`) and one column per slot. Each cell holds the id of the opponent counted
+    /// from `1`, positive for a home game and negative for an away game (`0` is a bye). The
+    /// result has id `-1` and can be scored with `evaluate_solution` and `violation_report`.
+    ///
+    /// # Arguments
+    /// * `path` - A string slice specifying the csv file path.
+    /// * `data` - A reference to the `Rawdata` the schedule is loaded for.
+    ///
+    /// # Returns
+    /// A `Result` containing the `Solution`, or a `TtpError` if the file cannot be read, the
+    /// header or a team index is missing, a cell is not an integer, the dimensions do not match
+    /// the instance, or the pairing is not symmetric (`a` meets `b` at home while `b` does not
+    /// meet `a` away in the same slot).
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::from_opponents_csv("external/schedule.csv", &data)?;
+    /// let report = Solution::violation_report(&data, &EvaluationOptions::default(), &solution);
+    /// ```
+    /// Example input (4 teams, first two slots):
+    /// ```text
+    /// team,slot_0,slot_1
+    /// 0,2,-3
+    /// 1,-1,4
+    /// 2,4,1
+    /// 3,-3,-2
+    /// ```
+    pub fn from_opponents_csv(path: &str, data: &Rawdata) -> Result<Solution, TtpError> {
+        let content = fs::read_to_string(path)?;
+        let invalid = |line: usize, message: String| TtpError::Validation(format!("{}:{}: {}", path, line, message));
+
+        let mut lines = content.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+        match lines.next() {
+            Some((_, header)) if header.trim().starts_with("team") => {}
+            _ => return Err(TtpError::Validation(format!("{}: missing team,slot_0,... header", path))),
+        }
+
+        let mut grid: Vec<Vec<Game>> = Vec::new();
+        for (line_number, line) in lines {
+            let mut cells = line.split(',').map(str::trim);
+            let team = cells.next().unwrap_or_default();
+            if team.parse::<usize>().ok() != Some(grid.len()) {
+                return Err(invalid(line_number + 1, format!("expected team {}, found '{}'", grid.len(), team)));
+            }
+
+            let row = cells
+                .map(|cell| {
+                    let value: i32 = cell
+                        .parse()
+                        .map_err(|_| invalid(line_number + 1, format!("invalid opponent '{}'", cell)))?;
+                    let opponent = value
+                        .checked_abs()
+                        .ok_or_else(|| invalid(line_number + 1, format!("invalid opponent '{}'", cell)))?;
+                    Ok(Game { home_game: value > 0, opponent: opponent - 1 })
+                })
+                .collect::<Result<Vec<Game>, TtpError>>()?;
+            grid.push(row);
+        }
+
+        // The file is indexed by team, the solution by slot
+        let num_slots = grid.iter().map(Vec::len).max().unwrap_or(0);
+        if let Some(team) = grid.iter().position(|row| row.len() != num_slots) {
+            return Err(TtpError::Validation(format!(
+                "{}: team {} has {} slots, expected {}", path, team, grid[team].len(), num_slots
+            )));
+        }
+        let solution = Solution {
+            id: -1,
+            solution: (0..num_slots).map(|slot| grid.iter().map(|row| row[slot].clone()).collect()).collect(),
+            tag: None,
+        };
+
+        solution
+            .validate_shape(data)
+            .map_err(|error| TtpError::Validation(format!("{}: {}", path, error)))?;
+
+        for (slot, row) in solution.solution.iter().enumerate() {
+            for (team, game) in row.iter().enumerate() {
+                if game.opponent < 0 {
+                    continue;
+                }
+
+                let other = &row[game.opponent as usize];
+                if other.opponent != team as i32 || other.home_game == game.home_game {
+                    return Err(TtpError::Validation(format!(
+                        "{}: slot {}: team {} plays {} but team {} does not play {} the other way",
+                        path, slot, team, game.opponent, game.opponent, team
+                    )));
+                }
+            }
+        }
+
+        Ok(solution)
+    }
+
+    /// Counts the pairings that break the phased property of the schedule.
+    ///
+    /// A phased schedule splits the slots in two halves (the first half has `slots / 2` slots)
+    /// and every pair of teams meets once in each half. A pair is counted if it meets more than
+    /// once in the same half, so a phased double round-robin has `0` and a double round-robin
+    /// that is not phased has at least one such pair.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing the teams.
+    ///
+    /// # Returns
+    /// The number of unordered pairs of teams meeting twice (or more) within a half (`usize`).
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// if data.metadata.phased {
+    ///     println!("Phased violations: {}", solution.phased_violations(&data));
+    /// }
+    /// ```
+    pub fn phased_violations(&self, data: &Rawdata) -> usize {
+        let num_teams = data.n_teams();
+        let half = self.n_slots() / 2;
+        let mut meetings = vec![[0usize; 2]; num_teams * num_teams];
+
+        for (slot, row) in self.solution.iter().enumerate() {
+            for (team, game) in row.iter().enumerate() {
+                if game.opponent > team as i32 {
+                    meetings[team * num_teams + game.opponent as usize][usize::from(slot >= half)] += 1;
+                }
+            }
+        }
+
+        meetings.iter().filter(|halves| halves[0] > 1 || halves[1] > 1).count()
+    }
+
+    /// Returns the games of a team, slot by slot.
+    ///
+    /// Every slot of the solution is listed, including unassigned cells (opponent `-1`),
+    /// so consecutive pairs are consecutive slots.
+    ///
+    /// # Arguments
+    /// * `team` - The team index (column of the solution matrix).
+    ///
+    /// # Returns
+    /// A `Vec<(usize, &Game)>` of `(slot, game)` pairs, in slot order.
+    ///
+    /// # Panics
+    /// Panics if `team` is not a valid team index.
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// for (slot, game) in solution.games_for_team(0) {
+    ///     println!("Slot {}: opponent {} (home: {})", slot, game.opponent, game.home_game);
+    /// }
+    /// ```
+    pub fn games_for_team(&self, team: usize) -> Vec<(usize, &Game)> {
+        self.solution.iter().map(|row| &row[team]).enumerate().collect()
+    }
+
+    /// Computes the home/away balance of each team.
+    ///
+    /// The balance of a team is its number of home games minus its number of away games,
+    /// unassigned cells (opponent `-1`) are ignored. In a double round-robin every team
+    /// plays each opponent once at home and once away, so every balance is `0`.
+    ///
+    /// # Returns
+    /// A `Vec<(usize, i32)>` of `(team, home_count - away_count)` pairs, in team order.
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// assert!(solution.home_away_balance().iter().all(|&(_, balance)| balance == 0));
+    /// ```
+    pub fn home_away_balance(&self) -> Vec<(usize, i32)> {
+        (0..self.n_teams())
+            .map(|team| {
+                let balance = self
+                    .games_for_team(team)
+                    .iter()
+                    .filter(|(_, game)| game.opponent >= 0)
+                    .map(|(_, game)| if game.home_game { 1 } else { -1 })
+                    .sum();
+                (team, balance)
+            })
+            .collect()
+    }
+
+    /// Counts the total number of breaks in the solution.
+    ///
+    /// A break happens when a team plays two consecutive slots with the same
+    /// home/away status (two home games or two away games in a row). Unassigned
+    /// cells (opponent `-1`) interrupt the sequence and are not counted.
+    ///
+    /// # Returns
+    /// The total number of breaks over all teams (`i32`).
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// println!("Breaks: {}", solution.count_breaks());
+    /// ```
+    pub fn count_breaks(&self) -> i32 {
+        let num_teams = self.n_teams();
+        let mut breaks = 0;
+
+        for team in 0..num_teams {
+            for pair in self.games_for_team(team).windows(2) {
+                let (previous, current) = (pair[0].1, pair[1].1);
+
+                if previous.opponent >= 0 && current.opponent >= 0 && previous.home_game == current.home_game {
+                    breaks += 1;
+                }
+            }
+        }
+
+        breaks
+    }
+
+    /// Computes the length of the longest trip in the solution.
+    ///
+    /// A trip is a sequence of consecutive away games of the same team. The result is
+    /// the largest number of consecutive away games over all teams.
+    ///
+    /// # Returns
+    /// The length of the longest trip (`i32`).
+    ///
+    /// # Example
+    /// ```
+    /// let solution = Solution::generate_florian_solution(&data, 0, true);
+    /// println!("Longest trip: {}", solution.longest_trip());
+    /// ```
+    pub fn longest_trip(&self) -> i32 {
+        let num_teams = self.n_teams();
+        let mut longest = 0;
+
+        for team in 0..num_teams {
+            let mut current = 0;
+            for (_, game) in self.games_for_team(team) {
+                if game.opponent >= 0 && !game.home_game {
+                    current += 1;
+                    longest = longest.max(current);
+                } else {
+                    current = 0;
+                }
+            }
+        }
+
+        longest
+    }
+
+    /// Returns the solution with the shortest traveling distance of a pool.
+    ///
+    /// Ties are broken by the first solution in the pool.
+    ///
+    /// # Arguments
+    /// * `solutions` - A slice of `Solution` instances.
+    /// * `distances` - The traveling distance of each solution, in the same order.
+    ///
+    /// # Returns
+    /// An `Option` with the best solution and its distance, `None` if the pool is empty.
+    ///
+    /// # Example
+    /// ```
+    /// let (solutions, distances, _) = Solution::generate_all_solutions(&data, &matrix, permutations, &GenerationOptions::default()).unwrap();
+    /// if let Some((best, distance)) = Solution::best_solution(&solutions, &distances) {
+    ///     println!("Best solution {}: {}", best.id, distance);
+    /// }
+    /// ```
+    pub fn best_solution<'a>(solutions: &'a [Solution], distances: &[i128]) -> Option<(&'a Solution, i128)> {
+        solutions
+            .iter()
+            .zip(distances)
+            .min_by_key(|(_, distance)| **distance)
+            .map(|(solution, distance)| (solution, *distance))
+    }
+
+    /// Returns the id and distance of the shortest feasible solution of a pool.
+    ///
+    /// Unlike `best_solution`, infeasible solutions are ignored, so the result is the best
+    /// schedule that can actually be used. Ties are broken by the first solution in the pool.
+    ///
+    /// # Arguments
+    /// * `solutions` - A slice of `Solution` instances.
+    /// * `distances` - The traveling distance of each solution, in the same order.
+    /// * `feasible` - The feasibility of each solution, in the same order.
+    ///
+    /// # Returns
+    /// An `Option` with `(id, distance)` of the best feasible solution, `None` if no solution
+    /// of the pool is feasible (or the feasibility was not computed).
+    ///
+    /// # Example
+    /// ```
+    /// let (solutions, distances, feasible) = Solution::generate_all_solutions(&data, &matrix, permutations, &GenerationOptions::default()).unwrap();
+    /// if let Some((id, distance)) = Solution::best_feasible_distance(&solutions, &distances, &feasible) {
+    ///     println!("Best feasible solution {}: {}", id, distance);
+    /// }
+    /// ```
+    pub fn best_feasible_distance(solutions: &[Solution], distances: &[i128], feasible: &[bool]) -> Option<(i32, i128)> {
+        solutions
+            .iter()
+            .zip(distances)
+            .zip(feasible)
+            .filter(|(_, is_feasible)| **is_feasible)
+            .min_by_key(|((_, distance), _)| **distance)
+            .map(|((solution, distance), _)| (solution.id, *distance))
+    }
+
+    /// Collects the break totals and the longest trip lengths of a pool of solutions.
+    ///
+    /// # Arguments
+    /// * `solutions` - A slice of `Solution` instances to analyze.
+    ///
+    /// # Returns
+    /// A tuple `(breaks, trips)`:
+    /// - `breaks` (Vec<i128>): total number of breaks of each solution.
+    /// - `trips` (Vec<i128>): longest trip length of each solution.
+    ///
+    /// # Example
+    /// ```
+    /// let (breaks, trips) = Solution::analyze_breaks(&solutions);
+    /// Statistics::generate_named_statistics("Breaks", &breaks, "breaks_histogram.png");
+    /// ```
+    pub fn analyze_breaks(solutions: &[Solution]) -> (Vec<i128>, Vec<i128>) {
+        solutions
+            .iter()
+            .map(|solution| (solution.count_breaks() as i128, solution.longest_trip() as i128))
+            .unzip()
+    }
+
+    /// Collects the number of violations of each constraint family over a pool of solutions.
+    ///
+    /// Families disabled in `data` (see `EvaluationOptions::constraint_enabled`) are left out.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `solutions` - A slice of `Solution` instances to analyze.
+    ///
+    /// # Returns
+    /// A `Vec<(ConstraintFamily, Vec<i128>)>` with, for each evaluated family, the number of
+    /// violations of each solution (in the order of `solutions`).
+    ///
+    /// # Example
+    /// ```
+    /// let per_family = Solution::analyze_constraints(&data, &EvaluationOptions::default(), &solutions);
+    /// Statistics::generate_constraint_statistics(&per_family, "output").unwrap();
+    /// ```
+    pub fn analyze_constraints(data: &Rawdata, evaluation: &EvaluationOptions, solutions: &[Solution]) -> Vec<(ConstraintFamily, Vec<i128>)> {
+        let reports: Vec<ViolationReport> = solutions
+            .iter()
+            .map(|solution| Solution::violation_report(data, evaluation, solution))
+            .collect();
+
+        ConstraintFamily::ALL
+            .into_iter()
+            .filter(|family| evaluation.constraint_enabled(*family))
+            .map(|family| (family, reports.iter().map(|report| report.count(family) as i128).collect()))
+            .collect()
+    }
+
+    /// Builds the detailed list of constraint violations of a solution.
+    ///
+    /// 1. **Capacity constraints**: Verifies for each team, within the specified interval (`c_intp`)
+    ///    of consecutive slots (`c_mode2` "SLOTS") or consecutive games of the team (`c_mode2` "GAMES",
+    ///    the default), or in the unit forced by `evaluation.capacity_window` (see
+    ///    `EvaluationOptions::window_counts_slots`), the number of home or away games falls within
+    ///    the minimum (`c_min`) and maximum (`c_max`) allowed. Only the teams of `c_team_groups1`
+    ///    are checked and only games against opponents of `c_team_groups2` are counted.
+    ///    A constraint whose window is longer than the schedule (`c_intp` > slots) is skipped.
+    ///    With `evaluation.ca2_group_pairs`, a `CA2` constraint is instead checked once over the whole
+    ///    schedule: the games between its two team groups (`Solution::cross_group_games`) must be
+    ///    between `c_min` and `c_max`.
+    ///
+    /// 2. **Separation constraints**: Ensures that matches between two teams respect the minimum and maximum
+    ///    separation distances defined by each constraint. The gap between two consecutive meetings of
+    ///    a pair is counted once per unordered pair (e.g. meetings in slots 1 and 5 have a gap of 4).
+    ///    Only pairs where both teams belong to `c_team_groups` are checked, and only their meetings in
+    ///    the slots of the constraint (`c_slots`, all slots when empty).
+    ///
+    /// 3. **Round-robin constraints**: Checks that every pair of teams meets exactly `evaluation.max_meetings`
+    ///    times (2 for a double round-robin: once at each venue). With `evaluation.partial_round_robin`,
+    ///    pairs may meet fewer times, only more meetings are violations.
+    ///
+    /// The dimensions of the solution are reconciled with the instance first (see `check_dimensions`).
+    /// Pairs are visited in ascending order, so the report is deterministic. The violations of the
+    /// families listed in `evaluation.disabled_constraints` are dropped from the report.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `solution_matrix` - A reference to the `Solution` with the scheduled games.
+    ///
+    /// # Returns
+    /// A `ViolationReport` with one entry per violation.
+    ///
+    /// # Panics
+    /// Panics if the dimensions of the solution do not match the instance (see `check_dimensions`).
+    ///
+    /// # Example
+    /// ```
+    /// let report = Solution::violation_report(&data, &EvaluationOptions::default(), &solution);
+    /// info!("{}", report);
+    /// ```
+    pub fn violation_report(data: &Rawdata, evaluation: &EvaluationOptions, solution_matrix: &Solution) -> ViolationReport {
+        if let Err(error) = solution_matrix.check_dimensions(data) {
+            panic!("{}", error);
+        }
+
+        let num_slots = solution_matrix.n_slots();
+        let num_teams = solution_matrix.n_teams();
+        let mut report = ViolationReport::default();
+
+        // Capacity Constraints:
+
+        let groups = if evaluation.ca2_group_pairs { data.group_map() } else { HashMap::new() };
+
+        for constraint in &data.capacity_constraints {
+            if evaluation.ca2_group_pairs && constraint.c_group_pairs {
+                let count = solution_matrix.cross_group_games(constraint, &groups);
+
+                if count < constraint.c_min as usize || count > constraint.c_max as usize {
+                    report.violations.push(Violation {
+                        family: ConstraintFamily::Capacity,
+                        constraint: constraint.index,
+                        teams: Vec::new(),
+                        slot: None,
+                        value: count as i32,
+                    });
+                }
+                continue;
+            }
+
+            // A window longer than the schedule cannot be checked, skip the constraint
+            if constraint.c_intp as usize > num_slots {
+                continue;
+            }
+
+            let teams = data.group_membership(constraint.c_team_groups1);
+            let opponents = data.group_membership(constraint.c_team_groups2);
+
+            for team in (0..num_teams).filter(|team| teams.get(*team).copied().unwrap_or(false)) {
+                // Slots of the team in playing order. With mode2 "SLOTS" the window slides over
+                // every slot, with "GAMES" the slots where the team does not play are skipped.
+                let slots: Vec<usize> = (0..num_slots)
+                    .filter(|&slot| evaluation.window_counts_slots(constraint) || solution_matrix.opponent_of(slot, team).is_some())
+                    .collect();
+
+                for window in slots.windows(constraint.c_intp.max(1) as usize) {
+                    let count = window
+                        .iter()
+                        .filter(|&&slot| {
+                            solution_matrix
+                                .opponent_of(slot, team)
+                                .is_some_and(|opponent| opponents.get(opponent as usize).copied().unwrap_or(false))
+                        })
+                        .filter(|&&slot| match constraint.c_mode1 {
+                            'A' => solution_matrix.is_home(slot, team),
+                            'H' => !solution_matrix.is_home(slot, team),
+                            _ => false,
+                        })
+                        .count();
+
+                    if count < constraint.c_min as usize || count > constraint.c_max as usize {
+                        report.violations.push(Violation {
+                            family: ConstraintFamily::Capacity,
+                            constraint: constraint.index,
+                            teams: vec![team],
+                            slot: Some(window[0]),
+                            value: count as i32,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Separation Constraints:
+
+        let mut meetings: BTreeMap<(usize, usize), Vec<usize>> = BTreeMap::new();
+
+        for slot in 0..num_slots {
+            for team in 0..num_teams {
+                // Each meeting is listed by both teams, keep it once per unordered pair
+                if let Some(opponent) = solution_matrix.opponent_of(slot, team)
+                    && opponent > team as i32
+                {
+                    meetings.entry((team, opponent as usize)).or_default().push(slot);
+                }
+            }
+        }
+
+        for constraint in &data.separation_constraints {
+            let teams = data.group_membership(constraint.c_team_groups);
+
+            for (&(team_a, team_b), slots) in &meetings {
+                if !teams.get(team_a).copied().unwrap_or(false) || !teams.get(team_b).copied().unwrap_or(false) {
+                    continue;
+                }
+
+                let slots: Vec<usize> = slots.iter().copied().filter(|&slot| constraint.applies_to_slot(slot)).collect();
+
+                for consecutive in slots.windows(2) {
+                    let gap = consecutive[1].abs_diff(consecutive[0]);
+
+                    if gap <= constraint.c_min as usize || gap > constraint.c_max as usize {
+                        report.violations.push(Violation {
+                            family: ConstraintFamily::Separation,
+                            constraint: constraint.index,
+                            teams: vec![team_a, team_b],
+                            slot: Some(consecutive[1]),
+                            value: gap as i32,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Round-robin constraints
+
+        let mut match_count: BTreeMap<(usize, usize), i32> = BTreeMap::new();
+
+        // Every pair is expected to meet, pairs that never meet are counted with 0 meetings
+        for team_a in 0..num_teams {
+            for team_b in team_a + 1..num_teams {
+                match_count.insert((team_a, team_b), 0);
+            }
+        }
+
+        for slot in 0..num_slots {
+            for team in 0..num_teams {
+                // Each meeting is listed by both teams, count it once per unordered pair
+                if let Some(opponent) = solution_matrix.opponent_of(slot, team)
+                    && opponent > team as i32
+                {
+                    *match_count.entry((team, opponent as usize)).or_insert(0) += 1;
+                }
+            }
+        }
+
+        for (&(team_a, team_b), &count) in &match_count {
+            let expected = evaluation.max_meetings as i32;
+            if count > expected || (count < expected && !evaluation.partial_round_robin) {
+                report.violations.push(Violation {
+                    family: ConstraintFamily::RoundRobin,
+                    constraint: 0,
+                    teams: vec![team_a, team_b],
+                    slot: None,
+                    value: count,
+                });
+            }
+        }
+
+        // Disabled families contribute no violation
+        report.violations.retain(|violation| evaluation.constraint_enabled(violation.family));
+
+        report
+    }
+
+    /// Checks all constraints for a solution, including capacity, separation, and round-robin.
+    ///
+    /// The counters are taken from `violation_report`, which describes each check.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `solution_matrix` - A reference to the `Solution` with the scheduled games.
+    ///
+    /// # Returns
+    /// A tuple `(capacity_violations, separation_violations, round_robin_respected)`
+    /// - `capacity_violations` (i32): total number of capacity constraint violations.
+    /// - `separation_violations` (i32): total number of separation constraint violations.
+    /// - `round_robin_respected` (bool): true if all pairs of teams respect the round-robin.
+    ///
+    /// # Example
+    /// ```
+    /// let data = Rawdata::generate_example();
+    /// let solution = Solution::generate_example();
+    /// let (cap_viol, sep_viol, rr_ok) = check_constraints(&data, &EvaluationOptions::default(), &solution);
+    /// println!("Capacity violations: {}, Separation violations: {}, Round-robin ok: {}", cap_viol, sep_viol, rr_ok);
+    /// ```
+    fn check_constraints(data: &Rawdata, evaluation: &EvaluationOptions, solution_matrix: &Solution) -> (i32, i32, bool) {
+        let _timer = ScopedTimer::new("check_constraints");
+        let report = Self::violation_report(data, evaluation, solution_matrix);
+
+        (
+            report.count(ConstraintFamily::Capacity),
+            report.count(ConstraintFamily::Separation),
+            report.count(ConstraintFamily::RoundRobin) == 0,
+        )
+    }
+
+    /// Calculates the total traveling distance for all teams in a given solution.
+    ///
+    /// This function iterates over all teams and all slots in the solution. For each team,
+    /// it tracks the current location and adds the distance to the next game location.
+    /// Home games do not require traveling, while away games add the distance to the opponent's location.
+    ///
+    /// # Arguments
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    /// * `solution_matrix` - A reference to the `Solution` containing the schedule of games
+    ///   for all slots and teams.
+    ///
+    /// # Returns
+    /// The total traveling distance for all teams (i32).
+    ///
     /// # Example
     /// ```
-    /// let distance_matrix = vec![vec![0, 5, 7], vec![5, 0, 3], vec![7, 3, 0]];
+    /// let distance_matrix = DistanceMatrix::from(vec![vec![0, 5, 7], vec![5, 0, 3], vec![7, 3, 0]]);
     /// let total = evaluate_objective(&distance_matrix, &solution);
     /// println!("Total traveling distance: {}", total);
     /// ```
     fn evaluate_objective(
-        traveling_distance_matrix: &Vec<Vec<i32>>,
+        traveling_distance_matrix: &DistanceMatrix,
         solution_matrix: &Solution,
     ) -> i32 {
-        let num_slots = solution_matrix.solution.len();
-        let num_teams = solution_matrix.solution[0].len();
-        let mut total_distance = 0;
+        let _timer = ScopedTimer::new("evaluate_objective");
+        solution_matrix
+            .per_team_distances(traveling_distance_matrix)
+            .iter()
+            .sum()
+    }
 
-        for team in 0..num_teams {
-            let mut current_location = team;
-            for slot in 0..num_slots {
-                let game = &solution_matrix.solution[slot][team];
-                let next_location = if game.home_game {
-                    team
-                } else {
-                    game.opponent as usize
-                };
-                total_distance += traveling_distance_matrix[current_location][next_location];
-                current_location = next_location;
+    /// Calculates the traveling distance of each team in the solution.
+    ///
+    /// Each team starts at its home location and, for each slot in order, travels to the
+    /// location of its game (its own venue for home games, the opponent's venue for away games).
+    /// The sum of the returned values is the total distance of `evaluate_objective`.
+    ///
+    /// # Arguments
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    ///
+    /// # Returns
+    /// A `Vec<i32>` where the element `t` is the traveling distance of team `t`.
+    ///
+    /// # Example
+    /// ```
+    /// let per_team = solution.per_team_distances(&distance_matrix);
+    /// println!("Distance of team 0: {}", per_team[0]);
+    /// ```
+    pub fn per_team_distances(&self, traveling_distance_matrix: &DistanceMatrix) -> Vec<i32> {
+        (0..self.n_teams())
+            .map(|team| self.team_distance(traveling_distance_matrix, team))
+            .collect()
+    }
+
+    /// Calculates the traveling distance of a single team (see `per_team_distances`).
+    fn team_distance(&self, traveling_distance_matrix: &DistanceMatrix, team: usize) -> i32 {
+        self.team_legs(team)
+            .map(|(from, to)| traveling_distance_matrix.get(from, to))
+            .sum()
+    }
+
+    /// Returns the `(from, to)` locations of the trip of a team before each slot, in slot order.
+    ///
+    /// The team starts at its home location. Locations are team ids (the matrix translates
+    /// them to venues): the team itself for a home game, the opponent for an away game. A team
+    /// with a bye stays where it is.
+    fn team_legs(&self, team: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let mut current_location = team;
+        (0..self.n_slots()).map(move |slot| {
+            let next_location = match self.opponent_of(slot, team) {
+                Some(_) if self.is_home(slot, team) => team,
+                Some(opponent) => opponent as usize,
+                None => current_location,
+            };
+            let leg = (current_location, next_location);
+            current_location = next_location;
+            leg
+        })
+    }
+
+    /// Calculates the traveling distance incurred in each slot, summed over the teams.
+    ///
+    /// The value of a slot is the distance traveled by all the teams to reach the location of
+    /// their game in that slot, so the sum of the returned values is the total distance of
+    /// `evaluate_objective`, and a travel-heavy round stands out.
+    ///
+    /// # Arguments
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    ///
+    /// # Returns
+    /// A `Vec<i32>` where the element `s` is the distance traveled for slot `s`.
+    ///
+    /// # Example
+    /// ```
+    /// let per_slot = solution.per_slot_travel(&distance_matrix);
+    /// assert_eq!(per_slot.iter().sum::<i32>(), solution.per_team_distances(&distance_matrix).iter().sum::<i32>());
+    /// ```
+    pub fn per_slot_travel(&self, traveling_distance_matrix: &DistanceMatrix) -> Vec<i32> {
+        let mut per_slot = vec![0; self.n_slots()];
+        for team in 0..self.n_teams() {
+            for (slot, (from, to)) in self.team_legs(team).enumerate() {
+                per_slot[slot] += traveling_distance_matrix.get(from, to);
+            }
+        }
+
+        per_slot
+    }
+
+    /// Calculates the traveling distance of each team entering each slot.
+    ///
+    /// The cell `[team][slot]` is the distance traveled by the team to reach the location of
+    /// its game in the slot, so every row sums to the distance of the team (see
+    /// `per_team_distances`), every column to the travel of the slot (see `per_slot_travel`)
+    /// and all cells to the total distance of `evaluate_objective`.
+    ///
+    /// # Arguments
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    ///
+    /// # Returns
+    /// A `Vec<Vec<i32>>` with one row per team and one column per slot.
+    ///
+    /// # Example
+    /// ```
+    /// let heatmap = solution.travel_heatmap(&distance_matrix);
+    /// println!("Travel of team 0 entering slot 3: {}", heatmap[0][3]);
+    /// ```
+    pub fn travel_heatmap(&self, traveling_distance_matrix: &DistanceMatrix) -> Vec<Vec<i32>> {
+        (0..self.n_teams())
+            .map(|team| {
+                self.team_legs(team)
+                    .map(|(from, to)| traveling_distance_matrix.get(from, to))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Saves the travel heatmap of the solution (see `travel_heatmap`) as a csv file.
+    ///
+    /// The file has one row per team and one column per slot. The first line is a header
+    /// (`team,slot_0,slot_1,...`) and the first column holds the team name.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing the team names.
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    /// * `path` - A string slice specifying the file path.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with an I/O error.
+    ///
+    /// # Example
+    /// Example output:
+    /// ```text
+    /// team,slot_0,slot_1,slot_2
+    /// ATL,0,745,0
+    /// NYM,1164,0,0
+    /// ```
+    pub fn save_heatmap_csv(&self, data: &Rawdata, traveling_distance_matrix: &DistanceMatrix, path: &str) -> std::io::Result<()> {
+        let mut output = String::from("team");
+        for slot in 0..self.n_slots() {
+            output.push_str(&format!(",slot_{}", slot));
+        }
+        output.push('\n');
+
+        for (team, row) in self.travel_heatmap(traveling_distance_matrix).iter().enumerate() {
+            output.push_str(&data.teams[team].name);
+            for distance in row {
+                output.push_str(&format!(",{}", distance));
             }
+            output.push('\n');
         }
 
-        total_distance
+        fs::write(path, output)
+    }
+
+    /// Measures how unevenly the travel is spread among the teams.
+    ///
+    /// The imbalance is the standard deviation of the per-team distances
+    /// (see `per_team_distances`). A value of `0.0` means every team travels the same distance.
+    ///
+    /// # Arguments
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    ///
+    /// # Returns
+    /// The standard deviation of the per-team distances (`f64`).
+    ///
+    /// # Example
+    /// ```
+    /// let imbalance = solution.travel_imbalance(&distance_matrix);
+    /// println!("Travel imbalance: {}", imbalance);
+    /// ```
+    pub fn travel_imbalance(&self, traveling_distance_matrix: &DistanceMatrix) -> f64 {
+        let per_team: Vec<i128> = self
+            .per_team_distances(traveling_distance_matrix)
+            .iter()
+            .map(|distance| *distance as i128)
+            .collect();
+
+        Statistics::std_dev(&per_team)
+    }
+
+    /// Counts the games between the two team groups of a group-pair (`CA2`) capacity constraint.
+    ///
+    /// A game is counted when a team of `c_team_groups1` meets a team of `c_team_groups2` in one
+    /// of the slots of the constraint (`c_slots`, all slots when empty). `c_mode1` selects the
+    /// side of the first team as for the other capacity constraints (`'A'` or `'H'`), any other
+    /// value counts both sides, and a game between two teams of both groups is counted once.
+    ///
+    /// # Arguments
+    /// * `constraint` - A reference to the `CapacityConstraints` to count.
+    /// * `groups` - The team groups of the instance, as returned by `Rawdata::group_map`.
+    ///   `ALL_TEAMS_GROUP` matches every team.
+    ///
+    /// # Returns
+    /// The number of cross-group games (`usize`).
+    ///
+    /// # Example
+    /// ```
+    /// let groups = data.group_map();
+    /// let count = solution.cross_group_games(&data.capacity_constraints[0], &groups);
+    /// ```
+    pub fn cross_group_games(&self, constraint: &CapacityConstraints, groups: &HashMap<i32, Vec<i32>>) -> usize {
+        let num_teams = self.solution.first().map_or(0, |row| row.len());
+        let members = |group: i32| {
+            if group == ALL_TEAMS_GROUP {
+                return vec![true; num_teams];
+            }
+            let mut membership = vec![false; num_teams];
+            for &id in groups.get(&group).into_iter().flatten() {
+                if let Some(member) = membership.get_mut(id as usize) {
+                    *member = true;
+                }
+            }
+            membership
+        };
+        let (first, second) = (members(constraint.c_team_groups1), members(constraint.c_team_groups2));
+
+        self.solution
+            .iter()
+            .enumerate()
+            .filter(|(slot, _)| constraint.applies_to_slot(*slot))
+            .map(|(_, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|&(team, game)| {
+                        let opponent = game.opponent as usize;
+                        if !first[team] || game.opponent < 0 || !second.get(opponent).copied().unwrap_or(false) {
+                            return false;
+                        }
+
+                        match constraint.c_mode1 {
+                            'A' => game.home_game,
+                            'H' => !game.home_game,
+                            // The game is also seen from the opponent, count it from the lower id
+                            _ => !(first[opponent] && second[team] && opponent < team),
+                        }
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Computes the penalty of a solution from its constraint violations.
+    ///
+    /// The penalty is the number of capacity and separation violations, plus one
+    /// if the round-robin structure is not respected. With `evaluation.graduated_penalties`,
+    /// the separation violations are counted by `Solution::graduated_separation_penalty`
+    /// instead, so a far miss costs more than a near miss. The binary counts are still
+    /// given by `Solution::evaluate_solution`.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `solution_matrix` - A reference to the `Solution` to evaluate.
+    ///
+    /// # Returns
+    /// The penalty of the solution (`i32`), `0` for a feasible solution.
+    ///
+    /// # Example
+    /// ```
+    /// let penalty = Solution::penalty_cost(&data, &EvaluationOptions::default(), &solution);
+    /// ```
+    pub fn penalty_cost(data: &Rawdata, evaluation: &EvaluationOptions, solution_matrix: &Solution) -> i32 {
+        let report = Self::violation_report(data, evaluation, solution_matrix);
+        let cap_constraints = report.count(ConstraintFamily::Capacity);
+        let sep_constraints = if evaluation.graduated_penalties {
+            Self::graduated_separation_penalty(data, &report)
+        } else {
+            report.count(ConstraintFamily::Separation)
+        };
+        let round_robin_respect = report.count(ConstraintFamily::RoundRobin) == 0;
+
+        cap_constraints + sep_constraints + if round_robin_respect { 0 } else { 1 }
+    }
+
+    /// Computes the graduated penalty of the separation violations of a report.
+    ///
+    /// Each violation costs its distance to the separation window
+    /// (`SeparationConstraints::violation_depth`) times the `c_penalty` of its constraint,
+    /// e.g. `(c_min - gap + 1) * c_penalty` for meetings that are too close.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` the report was built from.
+    /// * `report` - The `ViolationReport` of the solution (see `Solution::violation_report`).
+    ///
+    /// # Returns
+    /// The graduated separation penalty (`i32`), `0` without separation violations.
+    ///
+    /// # Example
+    /// ```
+    /// let report = Solution::violation_report(&data, &EvaluationOptions::default(), &solution);
+    /// let penalty = Solution::graduated_separation_penalty(&data, &report);
+    /// ```
+    pub fn graduated_separation_penalty(data: &Rawdata, report: &ViolationReport) -> i32 {
+        report
+            .violations
+            .iter()
+            .filter(|violation| violation.family == ConstraintFamily::Separation)
+            .filter_map(|violation| match data.constraint_by_index(violation.constraint) {
+                Some(ConstraintRef::Separation(constraint)) => {
+                    Some(constraint.violation_depth(violation.value as usize) * constraint.c_penalty)
+                }
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// Computes a weighted objective combining distance, penalty and travel imbalance.
+    ///
+    /// `objective = distance + lambda * penalty + mu * imbalance`, where `penalty` is given by
+    /// `penalty_cost` and `imbalance` by `travel_imbalance`. With `lambda = mu = 0` the
+    /// objective is the total traveling distance.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` containing teams and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
+    ///   the distance from team `i` to team `j`.
+    /// * `solution_matrix` - A reference to the `Solution` to evaluate.
+    /// * `lambda` - Weight of the penalty term.
+    /// * `mu` - Weight of the travel imbalance term.
+    ///
+    /// # Returns
+    /// The weighted objective (`f64`), lower is better.
+    ///
+    /// # Example
+    /// ```
+    /// let objective = Solution::weighted_objective(&data, &EvaluationOptions::default(), &distance_matrix, &solution, 1000.0, 0.5);
+    /// ```
+    pub fn weighted_objective(
+        data: &Rawdata,
+        evaluation: &EvaluationOptions,
+        traveling_distance_matrix: &DistanceMatrix,
+        solution_matrix: &Solution,
+        lambda: f64,
+        mu: f64,
+    ) -> f64 {
+        let distance = Self::evaluate_objective(traveling_distance_matrix, solution_matrix) as f64;
+        let penalty = Self::penalty_cost(data, evaluation, solution_matrix) as f64;
+        let imbalance = if mu != 0.0 {
+            solution_matrix.travel_imbalance(traveling_distance_matrix)
+        } else {
+            0.0
+        };
+
+        distance + lambda * penalty + mu * imbalance
+    }
+
+    /// Evaluates the solution against an instance, without a pre-built traveling distance matrix.
+    ///
+    /// Convenient for one-shot evaluations. The matrix comes from `DistanceMatrix::for_data`, so
+    /// it is only built again when the instance changes. Loops over many solutions should still
+    /// build it once and call `evaluate_solution`.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` struct containing teams, distances, slots, and constraints.
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    ///
+    /// # Returns
+    /// The same tuple as `evaluate_solution`:
+    /// `(total_distance, capacity_violations, separation_violations, round_robin_respected)`.
+    ///
+    /// # Example
+    /// ```
+    /// let data = XmlManager::read_xml("NL8.xml").unwrap();
+    /// let (total_distance, cap_viol, sep_viol, rr_ok) = solution.evaluate_with(&data, &EvaluationOptions::default());
+    /// ```
+    pub fn evaluate_with(&self, data: &Rawdata, evaluation: &EvaluationOptions) -> (i32, i32, i32, bool) {
+        let traveling_distance_matrix = DistanceMatrix::for_data(data);
+        Self::evaluate_solution(data, evaluation, &traveling_distance_matrix, self)
     }
 
     /// Evaluates a given solution by calculating the total traveling distance and checking constraints.
@@ -964,7 +3715,8 @@ impl Solution {
     ///
     /// # Arguments
     /// * `data` - A reference to the `Rawdata` struct containing teams, slots, and constraints.
-    /// * `traveling_distance_matrix` - A reference to a 2D vector where `matrix[i][j]` represents
+    /// * `evaluation` - A reference to the `EvaluationOptions` of the constraint checks.
+    /// * `traveling_distance_matrix` - A reference to the `DistanceMatrix`, where `get(i, j)` is
     ///   the distance from team `i` to team `j`.
     /// * `solution_matrix` - A reference to the `Solution` containing the schedule of games
     ///   for all slots and teams.
@@ -979,17 +3731,18 @@ impl Solution {
     /// # Example
     /// ```
     /// let data = Rawdata::generate_example();
-    /// let distance_matrix = vec![vec![0,5,7], vec![5,0,3], vec![7,3,0]];
+    /// let distance_matrix = DistanceMatrix::from(vec![vec![0,5,7], vec![5,0,3], vec![7,3,0]]);
     /// let solution = Solution::generate_example();
-    /// let (total_distance, cap_viol, sep_viol, rr_ok) = evaluate_solution(&data, &distance_matrix, &solution);
+    /// let (total_distance, cap_viol, sep_viol, rr_ok) = evaluate_solution(&data, &EvaluationOptions::default(), &distance_matrix, &solution);
     /// ```
     pub fn evaluate_solution(
         data: &Rawdata,
-        traveling_distance_matrix: &Vec<Vec<i32>>,
+        evaluation: &EvaluationOptions,
+        traveling_distance_matrix: &DistanceMatrix,
         solution_matrix: &Solution,
     ) -> (i32, i32, i32, bool) {
         let (cap_constraints, sep_constraints, round_robin_respect) =
-            Self::check_constraints(data, solution_matrix);
+            Self::check_constraints(data, evaluation, solution_matrix);
         let result = Self::evaluate_objective(traveling_distance_matrix, solution_matrix);
         (
             result,
@@ -999,3 +3752,1146 @@ impl Solution {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistics::ConstraintSummary;
+    use crate::test_support::{captured_logs, temp_path};
+    use crate::xml_manager::XmlManager;
+
+    fn nl8() -> Rawdata {
+        XmlManager::read_xml("NL8.xml").unwrap()
+    }
+
+    fn first_solution(data: &Rawdata) -> Solution {
+        let permutation: Vec<i32> = (0..data.n_teams() as i32).collect();
+        Solution::regenerate(data, &[permutation], 1, ConstructionMethod::Florian, None).unwrap()
+    }
+
+    #[test]
+    fn disabled_constraint_families_are_not_reported() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        // A double round-robin checked as a single one breaks every pair
+        let single = EvaluationOptions { max_meetings: 1, ..Default::default() };
+        let report = Solution::violation_report(&data, &single, &solution);
+        assert!(report.violations.iter().any(|violation| violation.family == ConstraintFamily::RoundRobin));
+
+        let disabled = EvaluationOptions { disabled_constraints: vec![ConstraintFamily::RoundRobin], ..single };
+        let filtered = Solution::violation_report(&data, &disabled, &solution);
+        assert!(filtered.violations.iter().all(|violation| violation.family != ConstraintFamily::RoundRobin));
+        assert_eq!(
+            filtered.violations.len(),
+            report.violations.iter().filter(|violation| violation.family != ConstraintFamily::RoundRobin).count()
+        );
+    }
+
+    #[test]
+    fn relabeling_keeps_the_structure_of_the_schedule() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        let perm = vec![3, 0, 1, 2, 7, 4, 5, 6];
+        let relabeled = solution.apply_permutation(&perm);
+
+        assert!(relabeled.validate(&data).is_ok());
+        for (slot, row) in solution.solution.iter().enumerate() {
+            for (team, game) in row.iter().enumerate() {
+                let moved = &relabeled.solution[slot][perm[team] as usize];
+                assert_eq!(moved.opponent, perm[game.opponent as usize]);
+                assert_eq!(moved.home_game, game.home_game);
+            }
+        }
+
+        let identity: Vec<i32> = (0..data.n_teams() as i32).collect();
+        assert_eq!(solution.apply_permutation(&identity).solution, solution.solution);
+    }
+
+    fn pairings(solution: &Solution) -> Vec<Vec<i32>> {
+        solution.solution.iter().map(|row| row.iter().map(|game| game.opponent).collect()).collect()
+    }
+
+    fn imbalance(solution: &Solution) -> i32 {
+        solution.home_away_balance().iter().map(|(_, balance)| balance.abs()).sum()
+    }
+
+    #[test]
+    fn orientation_repair_keeps_the_pairings_and_reduces_the_imbalance() {
+        let data = nl8();
+        let mut solution = first_solution(&data);
+        // Team 0 plays every game at home
+        for row in &mut solution.solution {
+            let opponent = row[0].opponent as usize;
+            row[0].home_game = true;
+            row[opponent].home_game = false;
+        }
+        let before = imbalance(&solution);
+        let original = pairings(&solution);
+
+        let flips = solution.repair_orientation(&data);
+        assert!(flips > 0);
+        assert!(imbalance(&solution) < before);
+        assert_eq!(pairings(&solution), original);
+        assert!(solution.validate(&data).is_ok());
+    }
+
+    #[test]
+    fn round_swap_delta_matches_a_full_evaluation() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let solution = first_solution(&data);
+        let before = Solution::evaluate_objective(&matrix, &solution);
+
+        for a in 0..solution.n_slots() {
+            for b in a + 1..solution.n_slots() {
+                let delta = solution.swap_rounds_delta(&matrix, a, b);
+                let mut swapped = solution.clone();
+                swapped.swap_rounds(a, b);
+                assert_eq!(Solution::evaluate_objective(&matrix, &swapped), before + delta, "swap {} {}", a, b);
+                assert!(swapped.validate(&data).is_ok());
+            }
+        }
+
+        let mut swapped = solution.clone();
+        swapped.swap_rounds(0, 9);
+        let report = Solution::violation_report(&data, &EvaluationOptions::default(), &swapped);
+        assert!(report.violations.iter().all(|violation| violation.family != ConstraintFamily::RoundRobin));
+    }
+
+    #[test]
+    fn round_swap_descent_never_increases_the_distance() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let mut solution = first_solution(&data);
+        let before = Solution::evaluate_objective(&matrix, &solution);
+        let original = solution.clone();
+
+        let delta = solution.descend_round_swaps(&matrix);
+        assert!(delta <= 0);
+        assert_eq!(Solution::evaluate_objective(&matrix, &solution), before + delta);
+        let mut rounds = solution.solution.clone();
+        let mut original_rounds = original.solution.clone();
+        rounds.sort_by_key(|row| row.iter().map(|game| (game.opponent, game.home_game)).collect::<Vec<_>>());
+        original_rounds.sort_by_key(|row| row.iter().map(|game| (game.opponent, game.home_game)).collect::<Vec<_>>());
+        assert_eq!(rounds, original_rounds);
+    }
+
+    #[test]
+    fn home_away_optimization_never_increases_the_distance() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        for id in 1..=2 * data.n_teams() as i32 {
+            let permutation: Vec<i32> = (0..data.n_teams() as i32).collect();
+            let solution = Solution::regenerate(&data, &[permutation], id, ConstructionMethod::Florian, None).unwrap();
+            let before = Solution::evaluate_objective(&matrix, &solution);
+
+            let (optimized, distance) = solution.optimize_home_away(&matrix);
+            assert!(distance <= before);
+            assert_eq!(Solution::evaluate_objective(&matrix, &optimized), distance);
+            assert_eq!(pairings(&optimized), pairings(&solution));
+            assert!(optimized.validate(&data).is_ok());
+        }
+    }
+
+    #[test]
+    fn iter_solutions_yields_the_saved_ids_in_order() {
+        let data = nl8();
+        let permutation: Vec<i32> = (0..data.n_teams() as i32).collect();
+        let directory = temp_path("iter_solutions");
+        fs::create_dir_all(&directory).unwrap();
+        for id in [10, 2, 3] {
+            let solution = Solution::regenerate(&data, std::slice::from_ref(&permutation), id, ConstructionMethod::Florian, None).unwrap();
+            save_to_file(&solution, &format!("{}/solution_{}.json", directory, id), false).unwrap();
+        }
+
+        let ids: Vec<i32> = Solution::iter_solutions(&directory, &data).map(|solution| solution.unwrap().id).collect();
+        let loaded: Vec<i32> = Solution::load_solutions(&directory, &data).unwrap().iter().map(|solution| solution.id).collect();
+        fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(ids, vec![2, 3, 10]);
+        assert_eq!(loaded, ids);
+    }
+
+    #[test]
+    fn permutations_are_capped_at_the_factorial_of_the_teams() {
+        let data = nl8().subset_teams(&[0, 1, 2, 3]);
+        let permutations = Solution::generate_random_permutations(&data, 100, 42, "", false, &NameTemplate::default()).unwrap();
+
+        assert_eq!(permutations.len(), 24);
+        assert_eq!(permutations.iter().collect::<HashSet<_>>().len(), 24);
+    }
+
+    #[test]
+    fn signed_opponents_csv_round_trips() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        let path = temp_path("round_trip.csv");
+        solution.save_opponents_csv(&path, true).unwrap();
+
+        let loaded = Solution::from_opponents_csv(&path, &data).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.to_opponents_grid(), solution.to_opponents_grid());
+        assert_eq!(loaded.solution, solution.solution);
+    }
+
+    #[test]
+    fn opponents_csv_rejects_an_asymmetric_pairing() {
+        let data = nl8();
+        let mut solution = first_solution(&data);
+        solution.solution[0][0].home_game = !solution.solution[0][0].home_game;
+        let path = temp_path("asymmetric.csv");
+        solution.save_opponents_csv(&path, true).unwrap();
+
+        let result = Solution::from_opponents_csv(&path, &data);
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn opponents_csv_rejects_the_minimum_integer() {
+        let data = nl8();
+        let path = temp_path("minimum.csv");
+        let slots = vec!["0"; data.n_slots() - 1].join(",");
+        fs::write(&path, format!("team,slot_0\n0,{},{}\n", i32::MIN, slots)).unwrap();
+
+        let result = Solution::from_opponents_csv(&path, &data);
+        fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn generation_is_reproducible() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let names = NameTemplate::default();
+        let permutations = Solution::generate_random_permutations(&data, 3, 42, "", false, &names).unwrap();
+        assert_eq!(Solution::generate_random_permutations(&data, 3, 42, "", false, &names).unwrap(), permutations);
+
+        let options = GenerationOptions { summary_only: true, ..Default::default() };
+        let first = Solution::generate_all_solutions(&data, &matrix, permutations.clone(), &options).unwrap();
+        let second = Solution::generate_all_solutions(&data, &matrix, permutations, &options).unwrap();
+        assert_eq!(first.1, second.1);
+        assert_eq!(Solution::best_solution(&first.0, &first.1), Solution::best_solution(&second.0, &second.1));
+    }
+
+    #[test]
+    fn evaluate_with_matches_evaluate_solution() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        let evaluation = EvaluationOptions::default();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+
+        assert_eq!(solution.evaluate_with(&data, &evaluation), Solution::evaluate_solution(&data, &evaluation, &matrix, &solution));
+    }
+
+    #[test]
+    fn opponents_grid_transposes_the_opponents_of_the_schedule() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        let grid = solution.to_opponents_grid();
+
+        assert_eq!(grid.len(), data.n_teams());
+        for (team, row) in grid.iter().enumerate() {
+            assert_eq!(row.len(), data.n_slots());
+            for (slot, &opponent) in row.iter().enumerate() {
+                assert_eq!(opponent, solution.solution[slot][team].opponent);
+                assert_eq!(grid[opponent as usize][slot], team as i32);
+            }
+        }
+    }
+
+    /// Builds a schedule from the `(home, away)` games of each slot.
+    fn from_games(num_teams: usize, slots: &[&[(usize, usize)]]) -> Solution {
+        let mut solution = vec![vec![Game { home_game: false, opponent: -1 }; num_teams]; slots.len()];
+        for (slot, games) in slots.iter().enumerate() {
+            for &(home, away) in games.iter() {
+                solution[slot][home] = Game { home_game: true, opponent: away as i32 };
+                solution[slot][away] = Game { home_game: false, opponent: home as i32 };
+            }
+        }
+        Solution { id: 1, solution, tag: None }
+    }
+
+    /// A double round-robin of 4 teams, team 0 playing its three home games first.
+    fn four_teams() -> Solution {
+        from_games(4, &[&[(0, 1), (2, 3)], &[(0, 2), (1, 3)], &[(0, 3), (1, 2)], &[(1, 0), (3, 2)], &[(2, 0), (3, 1)], &[(3, 0), (2, 1)]])
+    }
+
+    #[test]
+    fn break_and_trip_statistics_cover_each_solution_of_the_pool() {
+        let first = four_teams();
+        let mut second = first.clone();
+        second.swap_rounds(2, 3);
+
+        // Home/away sequences HHHAAA, AHHHAA, HAAAHH, AAAHHH, then HHAHAA, AHHHAA, HAAAHH, AAHAHH
+        let (breaks, trips) = Solution::analyze_breaks(&[first, second]);
+        assert_eq!(breaks, vec![14, 10]);
+        assert_eq!(trips, vec![3, 3]);
+    }
+
+    #[test]
+    fn saved_permutations_load_back_with_their_count() {
+        let data = nl8();
+        let permutations = Solution::generate_random_permutations(&data, 7, 42, "", false, &NameTemplate::default()).unwrap();
+        let path = temp_path("permutations.json");
+        Solution::save_permutations(&data, &permutations, 42, &path).unwrap();
+
+        let loaded = Solution::load_permutations(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.permutations.len(), 7);
+        assert_eq!(loaded.permutations, permutations);
+        assert_eq!((loaded.seed, loaded.instance_name.as_str()), (42, "NL8"));
+    }
+
+    #[test]
+    fn separation_gaps_are_absolute_slot_distances() {
+        let mut data = nl8().subset_teams(&[0, 1, 2, 3]);
+        // Teams 0 and 1 meet in slots 1 and 5
+        let solution = from_games(4, &[&[(0, 2), (1, 3)], &[(0, 1), (2, 3)], &[(0, 3), (1, 2)], &[(2, 0), (3, 1)], &[(3, 0), (2, 1)], &[(1, 0), (3, 2)]]);
+        assert!(solution.validate(&data).is_ok());
+        let separation_between = |data: &Rawdata, pair: &[usize]| {
+            Solution::violation_report(data, &EvaluationOptions::default(), &solution)
+                .violations
+                .into_iter()
+                .find(|violation| violation.family == ConstraintFamily::Separation && violation.teams == pair)
+        };
+
+        data.separation_constraints[0].c_min = 4;
+        let violation = separation_between(&data, &[0, 1]).unwrap();
+        assert_eq!((violation.value, violation.slot), (4, Some(5)));
+
+        data.separation_constraints[0].c_min = 3;
+        assert!(separation_between(&data, &[0, 1]).is_none());
+        assert_eq!(separation_between(&data, &[0, 3]).unwrap().value, 2);
+    }
+
+    #[test]
+    fn resume_skips_the_saved_solutions_without_overwriting_them() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let permutation: Vec<i32> = (0..data.n_teams() as i32).collect();
+        let directory = temp_path("resume");
+        fs::create_dir_all(&directory).unwrap();
+        let saved = format!("{}/solution_3.json", directory);
+        fs::write(&saved, "saved by a previous run").unwrap();
+
+        let options = GenerationOptions { path: directory.clone(), save: true, resume: true, summary_only: true, ..Default::default() };
+        let (solutions, _, _) = Solution::generate_all_solutions(&data, &matrix, vec![permutation], &options).unwrap();
+        let content = fs::read_to_string(&saved).unwrap();
+        let written = Path::new(&format!("{}/solution_4.json", directory)).exists();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(content, "saved by a previous run");
+        assert!(written);
+        assert_eq!(solutions.len(), 2 * data.n_teams() - 1);
+        assert!(solutions.iter().all(|solution| solution.id != 3));
+    }
+
+    /// The 4 first teams of NL8, all at a distance of 1 from each other.
+    fn four_unit_distances() -> Rawdata {
+        let mut data = nl8().subset_teams(&[0, 1, 2, 3]);
+        for distance in &mut data.distances {
+            distance.dist = if distance.team1 == distance.team2 { 0 } else { 1 };
+        }
+        data
+    }
+
+    #[test]
+    fn travel_imbalance_is_lower_for_the_more_balanced_schedule() {
+        let data = four_unit_distances();
+        let matrix = DistanceMatrix::from_rawdata(&data);
+        let balanced = four_teams();
+        let mut unbalanced = balanced.clone();
+        unbalanced.swap_rounds(1, 4);
+
+        assert_eq!(balanced.per_team_distances(&matrix), vec![3, 4, 4, 4]);
+        assert_eq!(unbalanced.per_team_distances(&matrix), vec![5, 4, 4, 6]);
+        assert!((balanced.travel_imbalance(&matrix) - 0.75f64.sqrt() / 2.0).abs() < 1e-9);
+        assert!(balanced.travel_imbalance(&matrix) < unbalanced.travel_imbalance(&matrix));
+
+        let evaluation = EvaluationOptions::default();
+        let without = Solution::weighted_objective(&data, &evaluation, &matrix, &unbalanced, 1.0, 0.0);
+        let with = Solution::weighted_objective(&data, &evaluation, &matrix, &unbalanced, 1.0, 2.0);
+        assert!((with - without - 2.0 * unbalanced.travel_imbalance(&matrix)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_ragged_solution_file_is_rejected_with_its_name() {
+        let data = nl8();
+        let mut solution = first_solution(&data);
+        solution.solution[3].pop();
+        let directory = temp_path("ragged");
+        fs::create_dir_all(&directory).unwrap();
+        let file = format!("{}/solution_1.json", directory);
+        save_to_file(&solution, &file, false).unwrap();
+
+        let result = Solution::load_solutions(&directory, &data);
+        fs::remove_dir_all(&directory).unwrap();
+        let Err(TtpError::Validation(message)) = result else {
+            panic!("a ragged solution should not load");
+        };
+        assert!(message.contains(&file));
+        assert!(message.contains("7 teams in slot 3, expected 8"));
+        assert_eq!(solution.validate_shape(&data), Err("solution 1 has 7 teams in slot 3, expected 8".to_string()));
+    }
+
+    /// Team 0 plays at home in slots 0 and 2, away in slot 3, and has a bye in the other slots.
+    fn schedule_with_byes() -> Solution {
+        from_games(4, &[&[(0, 1), (2, 3)], &[(2, 1)], &[(0, 2), (1, 3)], &[(3, 0), (1, 2)], &[], &[]])
+    }
+
+    /// Capacity violations of team 0 for at most one home game in a window of 2, with the
+    /// window unit of `mode2` unless `capacity_window` forces one.
+    fn team_0_capacity_violations(mode2: &str, capacity_window: Option<CapacityWindow>) -> Vec<(Option<usize>, i32)> {
+        let mut data = four_unit_distances();
+        let mut constraint = CapacityConstraints::new();
+        (constraint.c_intp, constraint.c_min, constraint.c_max, constraint.c_mode1) = (2, 0, 1, 'A');
+        constraint.c_mode2 = mode2.to_string();
+        data.capacity_constraints = vec![constraint];
+
+        Solution::violation_report(&data, &EvaluationOptions { capacity_window, ..Default::default() }, &schedule_with_byes())
+            .violations
+            .iter()
+            .filter(|violation| violation.family == ConstraintFamily::Capacity && violation.teams == [0])
+            .map(|violation| (violation.slot, violation.value))
+            .collect()
+    }
+
+    #[test]
+    fn capacity_windows_count_games_or_slots_by_mode2() {
+        // Games H, H, A: the first two games are at home
+        assert_eq!(team_0_capacity_violations("G", None), vec![(Some(0), 2)]);
+        assert_eq!(team_0_capacity_violations("GAMES", None), vec![(Some(0), 2)]);
+        // Slots H, bye, H, A, bye, bye: at most one home game in any 2 slots
+        assert!(team_0_capacity_violations("SLOTS", None).is_empty());
+        assert!(team_0_capacity_violations("S", None).is_empty());
+    }
+
+    #[test]
+    fn a_forced_capacity_window_unit_overrides_mode2() {
+        for mode2 in ["GAMES", "SLOTS"] {
+            assert_eq!(team_0_capacity_violations(mode2, Some(CapacityWindow::Games)), vec![(Some(0), 2)]);
+            assert!(team_0_capacity_violations(mode2, Some(CapacityWindow::Slots)).is_empty());
+        }
+    }
+
+    #[test]
+    fn truncated_schedules_only_travel_over_the_kept_slots() {
+        let data = four_unit_distances();
+        let matrix = DistanceMatrix::from_rawdata(&data);
+        let solution = four_teams();
+        let first_half = solution.truncate_slots(3);
+
+        assert_eq!(first_half.n_slots(), 3);
+        assert!(first_half.solution.iter().all(|row| row.len() == 4));
+        assert_eq!(first_half.per_team_distances(&matrix), vec![0, 2, 2, 3]);
+        assert_eq!(Solution::evaluate_objective(&matrix, &first_half), 7);
+        assert_eq!(Solution::evaluate_objective(&matrix, &solution), 15);
+        assert_eq!(solution.truncate_slots(10).solution, solution.solution);
+    }
+
+    #[test]
+    fn a_wildcard_separation_constraint_checks_every_team() {
+        let mut data = nl8();
+        // No team is left in group 0 and every pair must be 8 slots apart
+        for team in &mut data.teams {
+            team.team_groups = 1;
+        }
+        data.separation_constraints[0].c_min = 8;
+        let solution = first_solution(&data);
+        let separation_teams = |data: &Rawdata| -> HashSet<usize> {
+            Solution::violation_report(data, &EvaluationOptions::default(), &solution)
+                .violations
+                .iter()
+                .filter(|violation| violation.family == ConstraintFamily::Separation)
+                .flat_map(|violation| violation.teams.clone())
+                .collect()
+        };
+        assert!(separation_teams(&data).is_empty());
+
+        data.separation_constraints[0].c_team_groups = ALL_TEAMS_GROUP;
+        assert_eq!(separation_teams(&data), (0..8).collect());
+    }
+
+    #[test]
+    fn the_first_ranked_file_is_the_shortest_solution() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let permutation: Vec<i32> = (0..data.n_teams() as i32).collect();
+        let directory = temp_path("sort_output");
+        fs::create_dir_all(&directory).unwrap();
+        let options = GenerationOptions { path: directory.clone(), save: true, summary_only: true, ..Default::default() };
+        let (solutions, distances, _) = Solution::generate_all_solutions(&data, &matrix, vec![permutation], &options).unwrap();
+
+        Solution::rank_saved_solutions(&solutions, SolutionKey::Distance, &data, &EvaluationOptions::default(), &matrix, &directory, &NameTemplate::default()).unwrap();
+        let mut names: Vec<String> = fs::read_dir(&directory).unwrap().map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned()).collect();
+        names.sort();
+        let first: Solution = from_reader(File::open(format!("{}/{}", directory, names[0])).unwrap()).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        let (best, best_distance) = Solution::best_solution(&solutions, &distances).unwrap();
+        assert_eq!(names.len(), solutions.len());
+        assert_eq!(names[0], format!("rank_0001_dist_{}.json", best_distance));
+        assert_eq!(first.id, best.id);
+        assert!(names.iter().all(|name| name.starts_with("rank_")));
+    }
+
+    #[test]
+    fn each_slot_lists_one_match_per_pair_of_teams() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        let matches = solution.matches_per_slot();
+
+        assert_eq!(matches.len(), data.n_slots());
+        for (slot, games) in matches.iter().enumerate() {
+            assert_eq!(games.len(), data.n_teams() / 2);
+            for &(home, away) in games {
+                assert!(solution.is_home(slot, home as usize));
+                assert_eq!(solution.opponent_of(slot, home as usize), Some(away));
+            }
+        }
+        assert_eq!(schedule_with_byes().matches_per_slot()[1], vec![(2, 1)]);
+    }
+
+    #[test]
+    fn a_capacity_window_longer_than_the_schedule_is_skipped() {
+        let mut data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let solution = first_solution(&data);
+        for constraint in &mut data.capacity_constraints {
+            (constraint.c_intp, constraint.c_max) = (data.slots.len() as i32 + 6, 0);
+        }
+
+        let (_, capacity_violations, _, _) = Solution::evaluate_solution(&data, &EvaluationOptions::default(), &matrix, &solution);
+        assert_eq!(capacity_violations, 0);
+        assert_eq!(Solution::violation_report(&data, &EvaluationOptions::default(), &solution).count(ConstraintFamily::Capacity), 0);
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with cargo test --release -- --ignored --nocapture"]
+    fn benchmark_only_distance() {
+        let mut data = nl8();
+        // 100 copies of each constraint of NL8
+        data.capacity_constraints = data.capacity_constraints.iter().cycle().take(100 * data.capacity_constraints.len()).cloned().collect();
+        data.separation_constraints = data.separation_constraints.iter().cycle().take(100 * data.separation_constraints.len()).cloned().collect();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let permutations = Solution::generate_random_permutations(&data, 200, 42, "", false, &NameTemplate::default()).unwrap();
+        let run = |only_distance: bool| {
+            let options = GenerationOptions { only_distance, summary_only: true, ..Default::default() };
+            let started = Instant::now();
+            let (_, distances, _) = Solution::generate_all_solutions(&data, &matrix, permutations.clone(), &options).unwrap();
+            (started.elapsed(), distances)
+        };
+
+        let (full_elapsed, full) = run(false);
+        let (elapsed, only_distance) = run(true);
+
+        assert_eq!(only_distance, full);
+        println!(
+            "generation, {} solutions and {} constraints: {:.3} ms with the distance only, {:.3} ms with the constraint checks",
+            full.len(),
+            data.capacity_constraints.len() + data.separation_constraints.len(),
+            elapsed.as_secs_f64() * 1000.0,
+            full_elapsed.as_secs_f64() * 1000.0
+        );
+    }
+
+    #[test]
+    fn games_for_team_lists_one_game_per_slot() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        let games = solution.games_for_team(3);
+
+        assert_eq!(games.len(), solution.n_slots());
+        for (position, (slot, game)) in games.iter().enumerate() {
+            assert_eq!(*slot, position);
+            assert_eq!(**game, solution.solution[position][3]);
+        }
+    }
+
+    #[test]
+    fn shuffled_generation_orders_are_reproducible() {
+        let order = |seed: u64| Solution::generation_order(8, Some(&mut StdRng::seed_from_u64(seed)));
+        let canonical = Solution::generation_order(8, None);
+
+        assert_eq!(order(42), order(42));
+        assert_ne!(order(42), canonical);
+        assert_ne!(order(42), order(43));
+        let mut sorted = order(42);
+        sorted.sort_by_key(|&(direction, fixed_team)| (!direction, fixed_team));
+        assert_eq!(sorted, canonical);
+    }
+
+    #[test]
+    fn the_best_feasible_distance_ignores_a_shorter_infeasible_solution() {
+        let solutions: Vec<Solution> = (1..=3).map(|id| Solution { id, ..four_teams() }).collect();
+        let distances = [300, 100, 200];
+
+        assert_eq!(Solution::best_solution(&solutions, &distances).map(|(solution, distance)| (solution.id, distance)), Some((2, 100)));
+        assert_eq!(Solution::best_feasible_distance(&solutions, &distances, &[true, false, true]), Some((3, 200)));
+        assert_eq!(Solution::best_feasible_distance(&solutions, &distances, &[false; 3]), None);
+    }
+
+    #[test]
+    fn pairs_must_meet_the_expected_number_of_times() {
+        let round_robin_violations = |data: &Rawdata, solution: &Solution, max_meetings: usize| {
+            let evaluation = EvaluationOptions { max_meetings, ..Default::default() };
+            Solution::violation_report(data, &evaluation, solution).count(ConstraintFamily::RoundRobin)
+        };
+        let double = four_unit_distances();
+        let mut single = double.clone();
+        single.restrict_to_single_round_robin();
+        let schedule = four_teams();
+        let first_half = schedule.truncate_slots(3);
+
+        assert_eq!(round_robin_violations(&double, &schedule, 2), 0);
+        assert_eq!(round_robin_violations(&double, &schedule, 1), 6);
+        assert_eq!(round_robin_violations(&single, &first_half, 1), 0);
+        assert_eq!(round_robin_violations(&single, &first_half, 2), 6);
+    }
+
+    #[test]
+    fn random_solutions_survive_a_json_round_trip() {
+        let data = nl8();
+        let mut rng = StdRng::seed_from_u64(149);
+        for _ in 0..50 {
+            let mut permutation: Vec<i32> = (0..data.n_teams() as i32).collect();
+            permutation.shuffle(&mut rng);
+            let id = rng.random_range(1..=2 * data.n_teams() as i32);
+            let method = if rng.random_bool(0.5) { ConstructionMethod::Florian } else { ConstructionMethod::Circle };
+            let mut solution = Solution::regenerate(&data, &[permutation], id, method, None).unwrap();
+            solution.tag = rng.random_bool(0.5).then(|| format!("run \"{}\"", rng.random_range(0..100)));
+
+            for compact in [false, true] {
+                let path = temp_path("round_trip.json");
+                save_to_file(&solution, &path, compact).unwrap();
+                let loaded: Solution = from_reader(File::open(&path).unwrap()).unwrap();
+                fs::remove_file(&path).unwrap();
+                assert_eq!(loaded, solution);
+            }
+        }
+    }
+
+    #[test]
+    fn progress_is_logged_every_interval_and_at_the_end() {
+        let progress = ProgressBarLog::with_log_interval(7, Some(3));
+        let lines: Vec<String> = (1..=7).filter_map(|position| progress.progress_line(position)).collect();
+        assert_eq!(lines, ["Progress: processed 3/7 (42%)", "Progress: processed 6/7 (85%)", "Progress: processed 7/7 (100%)"]);
+
+        assert!((1..=7).all(|position| ProgressBarLog::new(7).progress_line(position).is_none()));
+        assert!(ProgressBarLog::with_log_interval(7, Some(0)).progress_line(7).is_none());
+    }
+
+    #[test]
+    fn a_solution_of_another_instance_size_is_caught() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        assert!(solution.check_dimensions(&data).is_ok());
+        // Fewer slots than the instance, as a truncated schedule, are accepted
+        assert!(solution.truncate_slots(5).check_dimensions(&data).is_ok());
+
+        let smaller = data.subset_teams(&[0, 1, 2, 3]);
+        let Err(TtpError::Validation(message)) = solution.check_dimensions(&smaller) else {
+            panic!("8 teams should not fit an instance of 4");
+        };
+        assert_eq!(message, "solution 1 is 14 slots x 8 teams but the instance has 6 slots x 4 teams");
+
+        let mut shorter = data.clone();
+        shorter.restrict_to_single_round_robin();
+        assert!(solution.check_dimensions(&shorter).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "solution 1 is 14 slots x 8 teams but the instance has 6 slots x 4 teams")]
+    fn evaluating_a_solution_of_another_instance_size_panics_with_the_mismatch() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        Solution::violation_report(&data.subset_teams(&[0, 1, 2, 3]), &EvaluationOptions::default(), &solution);
+    }
+
+    #[test]
+    fn a_double_round_robin_that_is_not_phased_has_phased_violations() {
+        let data = four_unit_distances();
+        let phased = four_teams();
+        let mut mixed = phased.clone();
+        mixed.swap_rounds(2, 3);
+
+        assert_eq!(phased.phased_violations(&data), 0);
+        // 0-1 and 2-3 meet twice in the first half, 0-3 and 1-2 twice in the second
+        assert_eq!(mixed.phased_violations(&data), 4);
+        assert_eq!(Solution::violation_report(&data, &EvaluationOptions::default(), &mixed).count(ConstraintFamily::RoundRobin), 0);
+    }
+
+    #[test]
+    fn feasible_and_infeasible_solutions_are_saved_apart() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let permutations = vec![(0..8).collect::<Vec<i32>>(); 2];
+        let directory = temp_path("keep_infeasible_separate");
+        let options = GenerationOptions { path: directory.clone(), save: true, split_by_feasibility: true, summary_only: true, ..Default::default() };
+        // Florian solutions of the first permutation are feasible, circle ones of the second are not
+        let (florian, _, florian_feasible) = Solution::generate_all_solutions(&data, &matrix, permutations.clone(), &GenerationOptions { permutation_indices: Some(vec![0]), ..options.clone() }).unwrap();
+        let (circle, _, circle_feasible) = Solution::generate_all_solutions(&data, &matrix, permutations, &GenerationOptions { method: ConstructionMethod::Circle, permutation_indices: Some(vec![1]), ..options }).unwrap();
+
+        let files = |subdirectory: &str| -> HashSet<String> {
+            fs::read_dir(format!("{}/{}", directory, subdirectory)).unwrap().map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned()).collect()
+        };
+        let (feasible, infeasible) = (files("feasible"), files("infeasible"));
+        let at_root = fs::read_dir(&directory).unwrap().filter(|entry| entry.as_ref().unwrap().path().is_file()).count();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(florian_feasible.iter().all(|is_feasible| *is_feasible) && circle_feasible.iter().all(|is_feasible| !is_feasible));
+        let names = |solutions: &[Solution]| -> HashSet<String> { solutions.iter().map(|solution| format!("solution_{}.json", solution.id)).collect() };
+        assert_eq!(feasible, names(&florian));
+        assert_eq!(infeasible, names(&circle));
+        assert_eq!(at_root, 0);
+    }
+
+    #[test]
+    fn the_remaining_time_is_estimated_once_after_the_warmup() {
+        let progress = ProgressBarLog::new(1000).with_eta(Some(200));
+        let lines: Vec<(u64, String)> = (1..=1000).filter_map(|position| progress.eta_line(position).map(|line| (position, line))).collect();
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].0, 200);
+        assert!(lines[0].1.starts_with("ETA: ~"));
+        assert!(lines[0].1.contains("remaining for 800 solutions (estimated over the first 200 in"));
+        // Nothing is left to estimate when the run is not longer than the warmup
+        assert!(ProgressBarLog::new(200).with_eta(Some(200)).eta_line(200).is_none());
+        assert!((1..=1000).all(|position| ProgressBarLog::new(1000).eta_line(position).is_none()));
+    }
+
+    #[test]
+    fn the_content_hash_only_depends_on_the_schedule() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        // Same schedule built again, loaded back from JSON, and saved under another id and tag
+        let rebuilt = first_solution(&data);
+        let loaded: Solution = serde_json::from_str(&serde_json::to_string(&solution).unwrap()).unwrap();
+        let renamed = Solution { id: 99, tag: Some("other run".to_string()), ..solution.clone() };
+
+        assert_eq!(solution.content_hash(), 0xd44eba616765e82e);
+        assert!([rebuilt, loaded, renamed].iter().all(|other| other.content_hash() == solution.content_hash()));
+
+        let mut swapped = solution.clone();
+        swapped.swap_rounds(0, 1);
+        assert_ne!(swapped.content_hash(), solution.content_hash());
+    }
+
+    #[test]
+    fn a_single_round_robin_meets_each_pair_once() {
+        let mut data = nl8();
+        data.restrict_to_single_round_robin();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let permutation: Vec<i32> = vec![6, 2, 7, 0, 4, 1, 5, 3];
+        for method in [ConstructionMethod::Florian, ConstructionMethod::Circle] {
+            let options = GenerationOptions { method, summary_only: true, ..Default::default() };
+            let (solutions, _, _) = Solution::generate_all_solutions(&data, &matrix, vec![permutation.clone()], &options).unwrap();
+
+            for solution in &solutions {
+                assert_eq!(solution.n_slots(), 7);
+                let mut pairs: Vec<(i32, i32)> = solution.matches_per_slot().into_iter().flatten().map(|(home, away)| (home.min(away), home.max(away))).collect();
+                pairs.sort_unstable();
+                pairs.dedup();
+                assert_eq!(pairs.len(), 28);
+                let single = EvaluationOptions { max_meetings: 1, ..Default::default() };
+                assert_eq!(Solution::violation_report(&data, &single, solution).count(ConstraintFamily::RoundRobin), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn constraint_statistics_summarize_each_family_over_the_pool() {
+        let mut data = four_unit_distances();
+        // At most one home game in any 2 slots: one violation per pair of consecutive home games
+        let mut constraint = CapacityConstraints::new();
+        (constraint.c_intp, constraint.c_min, constraint.c_max, constraint.c_mode1) = (2, 0, 1, 'A');
+        data.capacity_constraints = vec![constraint];
+        let first = four_teams();
+        let mut second = first.clone();
+        second.swap_rounds(2, 3);
+
+        let per_family = Solution::analyze_constraints(&data, &EvaluationOptions::default(), &[first, second]);
+        assert_eq!(
+            per_family,
+            vec![(ConstraintFamily::Capacity, vec![7, 5]), (ConstraintFamily::Separation, vec![0, 0]), (ConstraintFamily::RoundRobin, vec![0, 0])]
+        );
+
+        let capacity = ConstraintSummary::from_violations(ConstraintFamily::Capacity, &per_family[0].1);
+        assert_eq!((capacity.mean, capacity.median, capacity.max, capacity.satisfied), (6.0, 6.0, 7, 0.0));
+        let round_robin = ConstraintSummary::from_violations(ConstraintFamily::RoundRobin, &per_family[2].1);
+        assert_eq!(round_robin.satisfied, 1.0);
+
+        let directory = temp_path("constraint_statistics");
+        Statistics::generate_constraint_statistics(&per_family, &directory).unwrap();
+        let table = fs::read_to_string(format!("{}/constraint_summary.md", directory)).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+        let families: Vec<&str> = table.lines().skip(2).map(|line| line.split('|').nth(1).unwrap().trim()).collect();
+        assert_eq!(families, per_family.iter().map(|(family, _)| family.to_string()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn compact_json_is_smaller_and_loads_back_the_same() {
+        let data = nl8();
+        let solution = Solution { tag: Some("compact".to_string()), ..first_solution(&data) };
+        let (pretty_path, compact_path) = (temp_path("pretty.json"), temp_path("compact.json"));
+        save_to_file(&solution, &pretty_path, false).unwrap();
+        save_to_file(&solution, &compact_path, true).unwrap();
+
+        let (pretty, compact) = (fs::read_to_string(&pretty_path).unwrap(), fs::read_to_string(&compact_path).unwrap());
+        fs::remove_file(&pretty_path).unwrap();
+        fs::remove_file(&compact_path).unwrap();
+        assert!(compact.len() < pretty.len());
+        assert!(!compact.contains('\n'));
+        assert_eq!(serde_json::from_str::<Solution>(&compact).unwrap(), solution);
+        assert_eq!(serde_json::from_str::<Solution>(&pretty).unwrap(), solution);
+    }
+
+    #[test]
+    fn explained_solutions_match_the_generated_pool() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let permutations = vec![(0..8).collect::<Vec<i32>>(), vec![6, 2, 7, 0, 4, 1, 5, 3]];
+        let options = GenerationOptions { summary_only: true, ..Default::default() };
+        let (solutions, distances, feasible) = Solution::generate_all_solutions(&data, &matrix, permutations.clone(), &options).unwrap();
+
+        for ((solution, distance), is_feasible) in solutions.iter().zip(&distances).zip(&feasible) {
+            let explained = Solution::regenerate(&data, &permutations, solution.id, ConstructionMethod::Florian, None).unwrap();
+            let (explained_distance, capacity, separation, round_robin) = explained.evaluate_with(&data, &EvaluationOptions::default());
+            assert_eq!(explained.solution, solution.solution);
+            assert_eq!(explained_distance as i128, *distance);
+            assert_eq!(Solution::is_feasible(capacity, separation, round_robin), *is_feasible);
+            assert_eq!(explained.per_team_distances(&matrix).iter().sum::<i32>(), explained_distance);
+        }
+        assert!(Solution::regenerate(&data, &permutations, solutions.len() as i32 + 1, ConstructionMethod::Florian, None).is_none());
+    }
+
+    #[test]
+    fn chunked_solutions_load_back_as_saved() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let directory = temp_path("chunks");
+        fs::create_dir_all(&directory).unwrap();
+        let options = GenerationOptions { path: directory.clone(), save: true, chunk_size: Some(5), summary_only: true, ..Default::default() };
+        let (solutions, _, _) = Solution::generate_all_solutions(&data, &matrix, vec![(0..8).collect()], &options).unwrap();
+
+        let mut files: Vec<String> = fs::read_dir(&directory).unwrap().map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned()).collect();
+        files.sort();
+        let loaded = Solution::load_solutions(&directory, &data).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        // 16 solutions in batches of 5, the last one partial
+        assert_eq!(files, ["chunk_1.json", "chunk_2.json", "chunk_3.json", "chunk_4.json"]);
+        assert_eq!(loaded, solutions);
+    }
+
+    #[test]
+    fn each_key_ranks_the_pool_in_its_own_order() {
+        let mut data = four_unit_distances();
+        let mut constraint = CapacityConstraints::new();
+        (constraint.c_intp, constraint.c_min, constraint.c_max, constraint.c_mode1) = (2, 0, 1, 'A');
+        data.capacity_constraints = vec![constraint];
+        let matrix = DistanceMatrix::from_rawdata(&data);
+        // Distance, penalty and breaks: 15, 7, 14 then 19, 3, 6 then 17, 5, 10
+        let pool: Vec<Solution> = [None, Some((1, 4)), Some((2, 3))]
+            .iter()
+            .enumerate()
+            .map(|(index, swap)| {
+                let mut solution = Solution { id: index as i32 + 1, ..four_teams() };
+                if let Some((a, b)) = *swap {
+                    solution.swap_rounds(a, b);
+                }
+                solution
+            })
+            .collect();
+        let order = |key: SolutionKey| {
+            let mut sorted = pool.clone();
+            Solution::sort_by_key(&mut sorted, key, &data, &EvaluationOptions::default(), &matrix);
+            sorted.iter().map(|solution| solution.id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(order(SolutionKey::Distance), [1, 3, 2]);
+        assert_eq!(order(SolutionKey::Penalty), [2, 3, 1]);
+        assert_eq!(order(SolutionKey::Breaks), [2, 3, 1]);
+        assert_eq!(order(SolutionKey::Weighted(0.5)), [1, 3, 2]);
+        assert_eq!(order(SolutionKey::Weighted(2.0)), [2, 3, 1]);
+        // 22 for every solution, the ties keep the id order
+        assert_eq!(order(SolutionKey::Weighted(1.0)), [1, 2, 3]);
+    }
+
+    #[test]
+    fn home_away_balance_is_zero_only_for_a_balanced_schedule() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        assert_eq!(solution.home_away_balance(), (0..8).map(|team| (team, 0)).collect::<Vec<_>>());
+
+        // The game of team 2 in slot 0 moves to the other venue
+        let mut broken = solution.clone();
+        let opponent = broken.solution[0][2].opponent as usize;
+        let home = broken.solution[0][2].home_game;
+        broken.solution[0][2].home_game = !home;
+        broken.solution[0][opponent].home_game = home;
+        let shift = if home { -2 } else { 2 };
+        let balance = broken.home_away_balance();
+        assert_eq!(balance[2], (2, shift));
+        assert_eq!(balance[opponent], (opponent, -shift));
+        assert_eq!(balance.iter().filter(|(_, difference)| *difference != 0).count(), 2);
+    }
+
+    #[test]
+    fn hamming_distance_counts_the_cells_that_differ() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        assert_eq!(solution.hamming_distance(&first_solution(&data)), 0);
+
+        // One game played at the other venue changes the cells of both teams
+        let mut flipped = solution.clone();
+        let opponent = flipped.solution[4][1].opponent as usize;
+        flipped.solution[4][1].home_game = !flipped.solution[4][1].home_game;
+        flipped.solution[4][opponent].home_game = !flipped.solution[4][opponent].home_game;
+        assert_eq!(solution.hamming_distance(&flipped), 2);
+        assert_eq!(flipped.hamming_distance(&solution), 2);
+    }
+
+    #[test]
+    fn a_limit_per_permutation_samples_that_many_solutions_of_each_permutation() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let permutations = vec![(0..8).collect::<Vec<i32>>(), vec![6, 2, 7, 0, 4, 1, 5, 3], vec![7, 6, 5, 4, 3, 2, 1, 0]];
+        let ids = |sample_seed: u64| {
+            let options = GenerationOptions { limit_per_permutation: Some(5), sample_seed, summary_only: true, ..Default::default() };
+            let (solutions, distances, _) = Solution::generate_all_solutions(&data, &matrix, permutations.clone(), &options).unwrap();
+            assert_eq!(distances.len(), solutions.len());
+            solutions.iter().map(|solution| solution.id).collect::<Vec<i32>>()
+        };
+
+        let sampled = ids(42);
+        assert_eq!(sampled.len(), permutations.len() * 5);
+        for (permutation, block) in sampled.chunks(5).enumerate() {
+            let first_id = permutation as i32 * 16 + 1;
+            assert!(block.iter().all(|id| (first_id..first_id + 16).contains(id)));
+        }
+        assert_eq!(ids(42), sampled);
+        assert_ne!(ids(43), sampled);
+    }
+
+    #[test]
+    fn a_timeout_stops_the_generation_with_the_solutions_built_so_far() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let permutations = vec![(0..8).collect::<Vec<i32>>(); 500];
+        let generate = |timeout: Duration| {
+            let options = GenerationOptions { timeout: Some(timeout), summary_only: true, ..Default::default() };
+            Solution::generate_all_solutions(&data, &matrix, permutations.clone(), &options).unwrap()
+        };
+
+        let (solutions, distances, feasible) = generate(Duration::ZERO);
+        assert!(solutions.is_empty() && distances.is_empty() && feasible.is_empty());
+
+        let (solutions, distances, feasible) = generate(Duration::from_millis(5));
+        assert!(solutions.len() < permutations.len() * 16);
+        assert_eq!((distances.len(), feasible.len()), (solutions.len(), solutions.len()));
+        assert!(solutions.iter().map(|solution| solution.id).eq(1..=solutions.len() as i32));
+    }
+
+    #[test]
+    fn a_far_separation_miss_costs_more_than_a_near_one_with_graduated_penalties() {
+        let solution = four_teams();
+        let penalty = |c_min: i32, graduated_penalties: bool| {
+            let mut data = four_unit_distances();
+            data.capacity_constraints.clear();
+            data.separation_constraints[0].c_min = c_min;
+            data.separation_constraints[0].c_team_groups = ALL_TEAMS_GROUP;
+            Solution::penalty_cost(&data, &EvaluationOptions { graduated_penalties, ..Default::default() }, &solution)
+        };
+
+        // The pairs meet 1, 3 (three times) or 4 slots apart, so all of them miss 4 and 5
+        assert_eq!((penalty(4, false), penalty(5, false)), (6, 6));
+        assert_eq!(penalty(4, true), 4 + 2 + 2 + 2 + 1 + 1);
+        assert_eq!(penalty(5, true), 5 + 3 + 3 + 3 + 2 + 2);
+    }
+
+    #[test]
+    fn only_the_summary_of_the_generation_is_logged_with_summary_only() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let logs = |summary_only: bool| {
+            captured_logs(|| {
+                let options = GenerationOptions { summary_only, ..Default::default() };
+                Solution::generate_all_solutions(&data, &matrix, vec![(0..8).collect()], &options).unwrap();
+            })
+        };
+
+        let verbose = logs(false);
+        let summary = logs(true);
+        let per_solution = |line: &String| line.starts_with("Solution") || line.starts_with("Round: ");
+        assert!(verbose.iter().any(|line| line.starts_with("Solution:\nId: 16\n")) && verbose.iter().any(|line| line.starts_with("Round: ")));
+
+        // The permutation, the statistics per direction and fixed team, and the best solution
+        assert!(!summary.iter().any(per_solution));
+        assert_eq!(summary.len(), 1 + 2 + 8 + 1);
+        assert!(summary.iter().all(|line| verbose.contains(line)));
+        assert_eq!(summary.last().unwrap(), "Best feasible solution: 15 | Distance: 58346");
+    }
+
+    #[test]
+    fn a_travel_heavy_round_stands_out_of_the_per_slot_travel() {
+        let mut data = four_unit_distances();
+        for distance in data.distances.iter_mut().filter(|distance| distance.team1 + distance.team2 == 1) {
+            distance.dist = 100;
+        }
+        let matrix = DistanceMatrix::from_rawdata(&data);
+        let solution = four_teams();
+
+        // In slot 2, team 2 travels from team 0 to team 1 and team 3 from team 1 to team 0
+        let per_slot = solution.per_slot_travel(&matrix);
+        assert_eq!(per_slot, vec![101, 102, 200, 102, 3, 2]);
+        assert_eq!(per_slot.iter().sum::<i32>(), solution.per_team_distances(&matrix).iter().sum::<i32>());
+    }
+
+    #[test]
+    fn each_team_must_play_exactly_one_game_per_slot() {
+        let data = four_unit_distances();
+        assert_eq!(four_teams().check_one_game_per_slot(&data), Ok(()));
+        assert_eq!(first_solution(&nl8()).check_one_game_per_slot(&nl8()), Ok(()));
+
+        // In slot 0 team 3 is booked against team 0, which plays team 1, and in slot 5 team 1 plays
+        // itself, so their opponents of the schedule are not listed back either
+        let mut double_booked = four_teams();
+        double_booked.solution[0][3].opponent = 0;
+        double_booked.solution[5][1].opponent = 1;
+        assert_eq!(double_booked.check_one_game_per_slot(&data), Err(vec![(0, 2), (0, 3), (5, 1), (5, 2)]));
+        assert!(double_booked.validate(&data).is_err());
+
+        // An even number of teams expects no bye
+        let idle = schedule_with_byes().check_one_game_per_slot(&data).unwrap_err();
+        assert_eq!(&idle[..2], &[(1, 0), (1, 3)]);
+        assert_eq!(idle.len(), 2 + 4 + 4);
+    }
+
+    #[test]
+    fn randomized_tie_breaks_are_reproducible_from_their_seed() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let permutations = vec![(0..8).collect::<Vec<i32>>()];
+        let schedules = |tie_break_seed: Option<u64>| {
+            let options = GenerationOptions { method: ConstructionMethod::Circle, tie_break_seed, summary_only: true, ..Default::default() };
+            Solution::generate_all_solutions(&data, &matrix, permutations.clone(), &options).unwrap().0
+        };
+
+        let seeded = schedules(Some(7));
+        assert_eq!(schedules(Some(7)), seeded);
+        assert_ne!(schedules(Some(8)), seeded);
+        assert_ne!(schedules(None), seeded);
+        for solution in &seeded {
+            assert_eq!(Solution::regenerate(&data, &permutations, solution.id, ConstructionMethod::Circle, Some(7)).as_ref(), Some(solution));
+        }
+    }
+
+    #[test]
+    fn the_round_listing_names_the_games_then_the_byes_of_each_round() {
+        let listing = schedule_with_byes().to_round_listing(&four_unit_distances());
+        let lines: Vec<&str> = listing.lines().collect();
+
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], "Round 0: ATL (H) vs NYM, PHI (H) vs MON");
+        assert_eq!(lines[1], "Round 1: PHI (H) vs NYM, ATL (bye), MON (bye)");
+        assert_eq!(lines[3], "Round 3: NYM (H) vs PHI, MON (H) vs ATL");
+        assert_eq!(lines[4], "Round 4: ATL (bye), NYM (bye), PHI (bye), MON (bye)");
+    }
+
+    #[test]
+    fn opponents_and_home_games_are_read_within_the_bounds_only() {
+        let solution = schedule_with_byes();
+        assert_eq!((solution.opponent_of(0, 0), solution.is_home(0, 0)), (Some(1), true));
+        assert_eq!((solution.opponent_of(0, 1), solution.is_home(0, 1)), (Some(0), false));
+        // Team 0 has a bye in slot 1
+        assert_eq!((solution.opponent_of(1, 0), solution.is_home(1, 0)), (None, false));
+
+        for (slot, team) in [(6, 0), (0, 4), (usize::MAX, usize::MAX)] {
+            assert_eq!(solution.opponent_of(slot, team), None);
+            assert!(!solution.is_home(slot, team));
+        }
+    }
+
+    #[test]
+    fn only_the_permutations_at_the_chosen_indices_are_generated() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let permutations = Solution::generate_random_permutations(&data, 4, 42, "", false, &NameTemplate::default()).unwrap();
+        let generate = |permutation_indices: Option<Vec<usize>>| {
+            let options = GenerationOptions { permutation_indices, shuffle_seed: Some(7), summary_only: true, ..Default::default() };
+            Solution::generate_all_solutions(&data, &matrix, permutations.clone(), &options).unwrap().0
+        };
+
+        let all = generate(None);
+        let chosen = generate(Some(vec![1, 3]));
+        assert_eq!(chosen.len(), 2 * 16);
+        assert!(chosen.iter().all(|solution| (17..=32).contains(&solution.id) || (49..=64).contains(&solution.id)));
+        // Same ids, order and schedules as in the run over every permutation
+        let expected: Vec<&Solution> = all.iter().filter(|solution| matches!((solution.id - 1) / 16, 1 | 3)).collect();
+        assert!(chosen.iter().eq(expected));
+    }
+
+    #[test]
+    fn the_travel_heatmap_cells_sum_to_the_objective() {
+        let data = nl8();
+        let matrix = Solution::generate_traveling_distance_matrix(&data);
+        let solution = first_solution(&data);
+        let heatmap = solution.travel_heatmap(&matrix);
+
+        assert_eq!((heatmap.len(), heatmap[0].len()), (8, 14));
+        assert_eq!(heatmap.iter().flatten().sum::<i32>(), Solution::evaluate_objective(&matrix, &solution));
+        let rows: Vec<i32> = heatmap.iter().map(|row| row.iter().sum()).collect();
+        assert_eq!(rows, solution.per_team_distances(&matrix));
+        let columns: Vec<i32> = (0..14).map(|slot| heatmap.iter().map(|row| row[slot]).sum()).collect();
+        assert_eq!(columns, solution.per_slot_travel(&matrix));
+
+        let path = temp_path("heatmap.csv");
+        solution.save_heatmap_csv(&data, &matrix, &path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 1 + 8);
+        assert!(lines[0].starts_with("team,slot_0,slot_1,") && lines[0].ends_with(",slot_13"));
+        let first_row: Vec<String> = heatmap[0].iter().map(i32::to_string).collect();
+        assert_eq!(lines[1], format!("ATL,{}", first_row.join(",")));
+    }
+
+    #[test]
+    fn permutations_generating_the_same_pool_collapse_to_the_first_one() {
+        // With two teams, both orders build the same schedules
+        let two_teams = nl8().subset_teams(&[0, 1]);
+        for method in [ConstructionMethod::Florian, ConstructionMethod::Circle] {
+            assert_eq!(Solution::dedup_equivalent_permutations(&two_teams, &[vec![1, 0], vec![0, 1]], method), vec![vec![1, 0]]);
+        }
+
+        // A permutation listed twice is dropped, its rotations and its reverse are not
+        let data = nl8();
+        let permutation: Vec<i32> = vec![6, 2, 7, 0, 4, 1, 5, 3];
+        let mut rotated = permutation.clone();
+        rotated.rotate_left(3);
+        let reversed: Vec<i32> = permutation.iter().rev().copied().collect();
+        let permutations = vec![permutation.clone(), rotated.clone(), permutation.clone(), reversed.clone()];
+        assert_eq!(Solution::dedup_equivalent_permutations(&data, &permutations, ConstructionMethod::Florian), vec![permutation, rotated, reversed]);
+    }
+
+    #[test]
+    fn a_truncated_feasible_schedule_stays_feasible() {
+        let data = nl8();
+        let solution = first_solution(&data);
+        let truncated = solution.truncate_slots(10);
+        let feasible = |solution: &Solution, evaluation: &EvaluationOptions| {
+            let (_, capacity, separation, round_robin) = solution.evaluate_with(&data, evaluation);
+            Solution::is_feasible(capacity, separation, round_robin)
+        };
+        let partial = EvaluationOptions { partial_round_robin: true, ..Default::default() };
+
+        assert!(feasible(&solution, &EvaluationOptions::default()) && feasible(&solution, &partial));
+        assert!(!feasible(&truncated, &EvaluationOptions::default()));
+        assert!(feasible(&truncated, &partial));
+
+        // A pair meeting more often than expected is still a violation
+        let single = EvaluationOptions { max_meetings: 1, ..partial };
+        assert!(!feasible(&truncated, &single));
+    }
+}
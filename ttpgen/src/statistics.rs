@@ -1,9 +1,251 @@
+// Std library
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::fs;
+use std::fmt::Display;
+use std::hash::Hash;
+
 // External crates
 use plotters::prelude::*;
 use log::{info};
+use serde::{Deserialize, Serialize};
+
+// Local modules
+use crate::error::TtpError;
+use crate::results::RunResult;
+use crate::violation_report::ConstraintFamily;
 
 pub struct Statistics;
 
+/// Number of decimal places of the formatted statistics, unless changed with `--stats-precision`.
+pub const DEFAULT_PRECISION: usize = 2;
+
+thread_local! {
+    /// Number of decimal places used by `Statistics::format_float` on the current thread.
+    static PRECISION: Cell<usize> = const { Cell::new(DEFAULT_PRECISION) };
+}
+
+/// Streaming accumulator of summary statistics, updated one value at a time with
+/// Welford's algorithm, so the values themselves do not need to be kept.
+///
+/// Only the mean, variance (population), standard deviation and min-max are available:
+/// the median and quartiles need the whole data set.
+///
+/// # Example
+/// ```
+/// let mut stats = RunningStats::new();
+/// for distance in [10_i128, 20, 30, 40] {
+///     stats.push(distance);
+/// }
+/// println!("Mean: {}", stats.mean());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RunningStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    min: i128,
+    max: i128,
+}
+
+/// Summary statistics of a data set, as reported in the log, ready to be exported as a table.
+///
+/// # Fields
+/// * `count` - The number of values.
+/// * `mean`, `median`, `variance`, `std_dev` - As computed by the `Statistics` functions.
+/// * `min`, `max` - The smallest and largest values.
+/// * `q1`, `q3` - The first and third quartiles.
+///
+/// # Example
+/// ```
+/// let summary = StatsSummary::from_data(&[10_i128, 20, 30, 40]);
+/// println!("{}", summary.to_markdown());
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StatsSummary {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub variance: f64,
+    pub std_dev: f64,
+    pub min: i128,
+    pub max: i128,
+    pub q1: f64,
+    pub q3: f64,
+}
+
+/// Summary of the violations of one constraint family over a pool.
+///
+/// # Fields
+/// * `family` - The constraint family.
+/// * `mean`, `median` - The mean and median number of violations per solution.
+/// * `max` - The largest number of violations of a solution.
+/// * `satisfied` - The fraction of solutions without violations of the family (between 0 and 1).
+///
+/// # Example
+/// ```
+/// let summary = ConstraintSummary::from_violations(ConstraintFamily::Capacity, &[0, 2, 0, 1]);
+/// assert_eq!(summary.satisfied, 0.5);
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ConstraintSummary {
+    pub family: ConstraintFamily,
+    pub mean: f64,
+    pub median: f64,
+    pub max: i128,
+    pub satisfied: f64,
+}
+
+impl ConstraintSummary {
+    /// Summarizes the violations of a constraint family.
+    ///
+    /// # Arguments
+    /// * `family` - The constraint family.
+    /// * `violations` - The number of violations of each solution of the pool.
+    ///
+    /// # Panics
+    /// Panics if `violations` is empty (see `Statistics::mean` and `Statistics::min_max`).
+    pub fn from_violations(family: ConstraintFamily, violations: &[i128]) -> Self {
+        Self {
+            family,
+            mean: Statistics::mean(violations),
+            median: Statistics::median(violations),
+            max: Statistics::min_max(violations).1,
+            satisfied: violations.iter().filter(|&&count| count == 0).count() as f64 / violations.len() as f64,
+        }
+    }
+}
+
+impl StatsSummary {
+    /// Computes the summary statistics of a vector of values.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to a vector of `i128` values.
+    ///
+    /// # Panics
+    /// Panics if `data` is empty (see `Statistics::mean` and `Statistics::min_max`).
+    pub fn from_data(data: &[i128]) -> Self {
+        let (min, max) = Statistics::min_max(data);
+        let (q1, median, q3) = Statistics::quartiles(data);
+
+        Self {
+            count: data.len(),
+            mean: Statistics::mean(data),
+            median,
+            variance: Statistics::variance(data),
+            std_dev: Statistics::std_dev(data),
+            min,
+            max,
+            q1,
+            q3,
+        }
+    }
+
+    /// Returns the `(label, value)` rows of the summary tables, floats formatted with `Statistics::format_float`.
+    fn rows(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("Solutions", self.count.to_string()),
+            ("Mean", Statistics::format_float(self.mean)),
+            ("Median", Statistics::format_float(self.median)),
+            ("Variance", Statistics::format_float(self.variance)),
+            ("Std Dev", Statistics::format_float(self.std_dev)),
+            ("Min", self.min.to_string()),
+            ("Max", self.max.to_string()),
+            ("Q1", Statistics::format_float(self.q1)),
+            ("Q3", Statistics::format_float(self.q3)),
+        ]
+    }
+
+    /// Formats the summary as a Markdown table with one row per statistic.
+    ///
+    /// # Returns
+    /// A `String` with the header `| Statistic | Value |`, its separator and the rows.
+    ///
+    /// # Example
+    /// ```
+    /// let table = StatsSummary::from_data(&[10_i128, 20]).to_markdown();
+    /// assert!(table.contains("| Mean | 15.00 |"));
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let mut output = String::from("| Statistic | Value |\n|---|---:|\n");
+        for (label, value) in self.rows() {
+            output.push_str(&format!("| {} | {} |\n", label, value));
+        }
+        output
+    }
+
+    /// Formats the summary as a LaTeX `tabular` with one row per statistic.
+    ///
+    /// # Returns
+    /// A `String` with a `tabular` environment with `\hline` rules.
+    ///
+    /// # Example
+    /// ```
+    /// let table = StatsSummary::from_data(&[10_i128, 20]).to_latex();
+    /// assert!(table.contains("Mean & 15.00 \\\\"));
+    /// ```
+    pub fn to_latex(&self) -> String {
+        let mut output = String::from("\\begin{tabular}{lr}\n\\hline\nStatistic & Value \\\\\n\\hline\n");
+        for (label, value) in self.rows() {
+            output.push_str(&format!("{} & {} \\\\\n", label, value));
+        }
+        output.push_str("\\hline\n\\end{tabular}\n");
+        output
+    }
+}
+
+impl RunningStats {
+    /// Creates an empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a value to the accumulator.
+    ///
+    /// # Arguments
+    /// * `value` - The new value.
+    pub fn push(&mut self, value: i128) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+
+        self.count += 1;
+        let delta = value as f64 - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value as f64 - self.mean);
+    }
+
+    /// Returns the number of values pushed so far.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the arithmetic mean of the values pushed so far (`0.0` if empty).
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the population variance of the values pushed so far, as `Statistics::variance`.
+    /// The result is `NaN` if no value was pushed.
+    pub fn variance(&self) -> f64 {
+        self.m2 / self.count as f64
+    }
+
+    /// Returns the standard deviation of the values pushed so far.
+    pub fn std_dev(&self) -> f64 {
+        self.variance().sqrt()
+    }
+
+    /// Returns the minimum and maximum of the values pushed so far.
+    pub fn min_max(&self) -> (i128, i128) {
+        (self.min, self.max)
+    }
+}
+
 impl Statistics{
 
     /// Computes the arithmetic mean (average) of a vector of integer values.
@@ -24,7 +266,7 @@ impl Statistics{
     /// let values = vec![10_i128, 20, 30, 40];
     /// let avg = mean(&values);
     /// ```
-    pub fn mean(data: &Vec<i128>) -> f64 {
+    pub fn mean(data: &[i128]) -> f64 {
         let sum: i128 = data.iter().sum();
         sum as f64 / data.len() as f64
     }
@@ -47,12 +289,35 @@ impl Statistics{
     /// let med = median(&values);
     /// ```
     ///
-    pub fn median(data: &Vec<i128>) -> f64 {
-        let mut sorted = data.clone();
+    pub fn median(data: &[i128]) -> f64 {
+        let mut sorted = data.to_vec();
         sorted.sort();
 
+        Statistics::median_sorted(&sorted)
+    }
+
+    /// Computes the median of a slice that is already sorted in ascending order.
+    ///
+    /// Unlike `median`, this function does not clone or sort its input, so it can be
+    /// called repeatedly on sub-slices of a single sorted vector.
+    ///
+    /// # Arguments
+    /// * `sorted` - A slice of `i128` values sorted in ascending order.
+    ///
+    /// # Returns
+    /// A `f64` representing the median of the slice.
+    ///
+    /// # Panics
+    /// This function will **panic** if `sorted` is empty.
+    ///
+    /// # Example
+    /// ```
+    /// let values = vec![1_i128, 3, 5, 7, 9];
+    /// let med = Statistics::median_sorted(&values);
+    /// ```
+    fn median_sorted(sorted: &[i128]) -> f64 {
         let mid = sorted.len() / 2;
-        if sorted.len() % 2 == 0 {
+        if sorted.len().is_multiple_of(2) {
             (sorted[mid - 1] as f64 + sorted[mid] as f64) / 2.0
         } else {
             sorted[mid] as f64
@@ -77,7 +342,7 @@ impl Statistics{
     /// let var = Statistics::variance(&values);
     /// ```
     ///
-    pub fn variance(data: &Vec<i128>) -> f64 {
+    pub fn variance(data: &[i128]) -> f64 {
         let m = Statistics::mean(data);
         data.iter()
             .map(|value| {
@@ -105,10 +370,39 @@ impl Statistics{
     /// let sd = Statistics::std_dev(&values);
     /// ```
     ///
-    pub fn std_dev(data: &Vec<i128>) -> f64 {
+    pub fn std_dev(data: &[i128]) -> f64 {
         Statistics::variance(data).sqrt()
     }
 
+    /// Computes the coefficient of variation (`std_dev / mean`) of a vector of integer values.
+    ///
+    /// Unlike the standard deviation, it does not depend on the scale of the values, so the
+    /// dispersion of instances with very different distances can be compared.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to a vector of `i128` values.
+    ///
+    /// # Returns
+    /// A `f64` representing the coefficient of variation, `NaN` if the mean is zero (the
+    /// ratio is undefined).
+    ///
+    /// # Panics
+    /// This function will **panic** if `data` is empty (see `std_dev`).
+    ///
+    /// # Example
+    /// ```
+    /// let values = vec![2_i128, 4, 4, 4, 5, 5, 7, 9];
+    /// assert_eq!(Statistics::coefficient_of_variation(&values), 0.4);
+    /// ```
+    pub fn coefficient_of_variation(data: &[i128]) -> f64 {
+        let mean = Statistics::mean(data);
+        if mean == 0.0 {
+            return f64::NAN;
+        }
+
+        Statistics::std_dev(data) / mean
+    }
+
     /// Returns the minimum and maximum values in a vector of integer values.
     ///
     /// # Arguments
@@ -129,12 +423,16 @@ impl Statistics{
     /// let (min_val, max_val) = Statistics::min_max(&values);
     /// ```
     ///
-    pub fn min_max(data: &Vec<i128>) -> (i128, i128) {
+    pub fn min_max(data: &[i128]) -> (i128, i128) {
         (*data.iter().min().unwrap(), *data.iter().max().unwrap())
     }
 
     /// Computes the first, second (median), and third quartiles of a vector of integer values.
     ///
+    /// The data is sorted only once, the quartiles are then taken as the medians of
+    /// the whole sorted slice and of its lower and upper halves. A single value has no
+    /// halves, so it is the three quartiles.
+    ///
     /// # Arguments
     /// * `data` - A reference to a vector of `i128` values.
     ///
@@ -149,18 +447,80 @@ impl Statistics{
     /// let values = vec![7_i128, 15, 36, 39, 40, 41, 42, 43, 47, 49];
     /// let (q1, q2, q3) = Statistics::quartiles(&values);
     /// ```
-    pub fn quartiles(data: &Vec<i128>) -> (f64, f64, f64) {
-        let mut sorted = data.clone();
+    pub fn quartiles(data: &[i128]) -> (f64, f64, f64) {
+        let mut sorted = data.to_vec();
         sorted.sort();
         let n = sorted.len();
 
-        let q2 = Statistics::median(&sorted);
-        let q1 = Statistics::median(&sorted[..n/2].to_vec());
-        let q3 = Statistics::median(&sorted[(n+1)/2..].to_vec());
+        let q2 = Statistics::median_sorted(&sorted);
+        if n == 1 {
+            return (q2, q2, q2);
+        }
+        let q1 = Statistics::median_sorted(&sorted[..n/2]);
+        let q3 = Statistics::median_sorted(&sorted[n.div_ceil(2)..]);
 
         (q1, q2, q3)
     }
 
+    /// Flags the outliers of a vector of integer values using Tukey fences.
+    ///
+    /// A value is an outlier when it lies below `Q1 - 1.5 * IQR` or above `Q3 + 1.5 * IQR`,
+    /// where `IQR = Q3 - Q1` and the quartiles are computed by `quartiles`.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to a vector of `i128` values.
+    ///
+    /// # Returns
+    /// A vector of `(index, value)` pairs for each outlier, in the original order of `data`.
+    /// An empty vector is returned if `data` has less than two values.
+    ///
+    /// # Example
+    /// ```
+    /// let values = vec![10_i128, 11, 12, 11, 10, 95];
+    /// let outliers = Statistics::flag_outliers(&values);
+    /// assert_eq!(outliers, vec![(5, 95)]);
+    /// ```
+    pub fn flag_outliers(data: &[i128]) -> Vec<(usize, i128)> {
+        if data.len() < 2 {
+            return Vec::new();
+        }
+
+        let (q1, _, q3) = Statistics::quartiles(data);
+        let iqr = q3 - q1;
+        let lower = q1 - 1.5 * iqr;
+        let upper = q3 + 1.5 * iqr;
+
+        data.iter()
+            .enumerate()
+            .filter(|(_, value)| (**value as f64) < lower || (**value as f64) > upper)
+            .map(|(index, value)| (index, *value))
+            .collect()
+    }
+
+    /// Keeps only the values whose corresponding solution is feasible.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to a vector of `i128` values (e.g. distances).
+    /// * `feasible` - A slice of booleans parallel to `data`, `true` for feasible solutions.
+    ///
+    /// # Returns
+    /// A `Vec<i128>` containing only the values flagged as feasible, in their original order.
+    ///
+    /// # Example
+    /// ```
+    /// let distances = vec![10_i128, 20, 30];
+    /// let feasible = vec![true, false, true];
+    /// let filtered = Statistics::filter_feasible(&distances, &feasible);
+    /// assert_eq!(filtered, vec![10, 30]);
+    /// ```
+    pub fn filter_feasible(data: &[i128], feasible: &[bool]) -> Vec<i128> {
+        data.iter()
+            .zip(feasible)
+            .filter(|(_, is_feasible)| **is_feasible)
+            .map(|(value, _)| *value)
+            .collect()
+    }
+
     /// Plots a histogram of the given distances and saves it as an image file.
     ///
     /// This function divides the range of distances into a fixed number of bins (20),
@@ -168,53 +528,138 @@ impl Statistics{
     /// chart using the `plotters` crate. The Y-axis is scaled based on the maximum
     /// count plus a margin of 5 (can be changed).
     ///
+    /// With a `feasible` slice, each bin is split into stacked feasible (green, bottom) and
+    /// infeasible (red, top) segments with a legend. Without it, bins are drawn in one color.
+    ///
     /// # Arguments
     /// * `distances` - A reference to a vector of `i128` distances.
+    /// * `feasible` - The optional feasibility of each distance, in the same order.
     /// * `filename` - A string slice representing the path where the histogram image
     ///   will be saved.
     ///
-    /// # Panics
-    /// This function will panic if:
-    /// - The distances vector is empty.
-    /// - Writing the image file fails.
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with a `TtpError`:
+    /// `Validation` if the distances vector is empty or `feasible` has another length,
+    /// `Plot` if drawing or writing the image fails.
     ///
     /// # Example
     /// ```
     /// let distances = vec![10, 20, 20, 30, 40, 40, 40, 50];
-    /// Statistics::plot_histogram(&distances, "output/histogram.png");
+    /// Statistics::plot_histogram(&distances, None, "output/histogram.png").unwrap();
     /// ```
-    pub fn plot_histogram(distances: &Vec<i128>, filename: &str) {
-        let min = *distances.iter().min().unwrap();
-        let max = *distances.iter().max().unwrap();
+    pub fn plot_histogram(distances: &[i128], feasible: Option<&[bool]>, filename: &str) -> Result<(), TtpError> {
+        match feasible {
+            Some(feasible) => Statistics::plot_histogram_by_feasibility(distances, feasible, filename),
+            None => Statistics::plot_histogram_with_caption(distances, "Distance Distribution", filename),
+        }
+    }
+
+    /// Plots the stacked feasible/infeasible histogram of `plot_histogram`.
+    ///
+    /// # Arguments
+    /// * `distances` - A reference to a vector of `i128` distances.
+    /// * `feasible` - The feasibility of each distance, in the same order.
+    /// * `filename` - A string slice representing the path where the histogram image
+    ///   will be saved.
+    fn plot_histogram_by_feasibility(distances: &[i128], feasible: &[bool], filename: &str) -> Result<(), TtpError> {
+        if feasible.len() != distances.len() {
+            return Err(TtpError::Validation(format!(
+                "{} feasibility flags given for {} distances",
+                feasible.len(),
+                distances.len()
+            )));
+        }
+        let (Some(&min), Some(&max)) = (distances.iter().min(), distances.iter().max()) else {
+            return Err(TtpError::Validation("Cannot plot a histogram of an empty data set".to_string()));
+        };
 
         let root = BitMapBackend::new(filename, (1280, 720))
             .into_drawing_area();
-        root.fill(&WHITE).unwrap();
+        root.fill(&WHITE).map_err(plot_error)?;
 
-        let bins = 20;
-        let step = ((max - min) / bins).max(1);
+        let step = Statistics::histogram_step(min, max);
+        let feasible_counts = Statistics::histogram_counts(&Statistics::filter_feasible(distances, feasible), min, step);
+        let counts = Statistics::histogram_counts(distances, min, step);
 
-        let mut counts: Vec<i128> = Vec::new();
+        let y_max = counts.iter().max().cloned().unwrap_or(0) + 5;
 
-        for b in 0..bins {
-            let start = min + b * step;
-            let end = start + step;
+        let mut chart = ChartBuilder::on(&root)
+            .caption("Distance Distribution", ("sans-serif", 40))
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(40)
+            .build_cartesian_2d(Statistics::histogram_x_range(min, max), 0..y_max)
+            .map_err(plot_error)?;
 
-            let count = distances.iter().filter(|&&v| v >= start && v < end).count() as i128;
-            counts.push(count);
+        chart.configure_mesh().draw().map_err(plot_error)?;
+
+        // Feasible segment from 0, infeasible segment stacked on top of it
+        let segments = [
+            ("feasible", GREEN, feasible_counts.iter().map(|&f| (0, f)).collect::<Vec<_>>()),
+            ("infeasible", RED, counts.iter().zip(&feasible_counts).map(|(&c, &f)| (f, c)).collect::<Vec<_>>()),
+        ];
+        for (label, color, ranges) in segments {
+            chart.draw_series(ranges.into_iter().enumerate().map(|(bin, (bottom, top))| {
+                let start = min + (bin as i128) * step;
+                Rectangle::new([(start, bottom), (start + step, top)], color.mix(0.6).filled())
+            }))
+            .map_err(plot_error)?
+            .label(label)
+            .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.mix(0.6).filled()));
         }
 
+        chart.configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()
+            .map_err(plot_error)?;
+
+        root.present().map_err(plot_error)
+    }
+
+    /// Plots a histogram of the given values with a custom caption and saves it as an image file.
+    ///
+    /// This is the same chart as `plot_histogram`, used for values other than distances
+    /// (e.g. breaks or trip lengths).
+    ///
+    /// # Arguments
+    /// * `distances` - A reference to a vector of `i128` values.
+    /// * `caption` - The caption drawn on top of the chart.
+    /// * `filename` - A string slice representing the path where the histogram image
+    ///   will be saved.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with a `TtpError`:
+    /// `Validation` if the values vector is empty, `Plot` if drawing or writing the image fails.
+    ///
+    /// # Example
+    /// ```
+    /// let breaks = vec![12, 14, 14, 16];
+    /// Statistics::plot_histogram_with_caption(&breaks, "Breaks Distribution", "output/breaks.png").unwrap();
+    /// ```
+    pub fn plot_histogram_with_caption(distances: &[i128], caption: &str, filename: &str) -> Result<(), TtpError> {
+        let (Some(&min), Some(&max)) = (distances.iter().min(), distances.iter().max()) else {
+            return Err(TtpError::Validation("Cannot plot a histogram of an empty data set".to_string()));
+        };
+
+        let root = BitMapBackend::new(filename, (1280, 720))
+            .into_drawing_area();
+        root.fill(&WHITE).map_err(plot_error)?;
+
+        let step = Statistics::histogram_step(min, max);
+        let counts = Statistics::histogram_counts(distances, min, step);
+
         let y_max = counts.iter().max().cloned().unwrap_or(0) + 5;
 
         let mut chart = ChartBuilder::on(&root)
-            .caption("Distance Distribution", ("sans-serif", 40))
+            .caption(caption, ("sans-serif", 40))
             .margin(10)
             .x_label_area_size(40)
             .y_label_area_size(40)
-            .build_cartesian_2d(min..max, 0..y_max)
-            .unwrap();
+            .build_cartesian_2d(Statistics::histogram_x_range(min, max), 0..y_max)
+            .map_err(plot_error)?;
 
-        chart.configure_mesh().draw().unwrap();
+        chart.configure_mesh().draw().map_err(plot_error)?;
 
         for (b, &count) in counts.iter().enumerate() {
             let start = min + (b as i128) * step;
@@ -223,30 +668,732 @@ impl Statistics{
             chart.draw_series(std::iter::once(Rectangle::new(
                 [(start, 0), (end, count)],
                 BLUE.mix(0.6).filled(),
-            ))).unwrap();
+            ))).map_err(plot_error)?;
         }
+
+        root.present().map_err(plot_error)
+    }
+
+    /// Plots the histograms of two sets of values on one chart and saves it as an image file.
+    ///
+    /// Both series share the x-range and the bin layout of `plot_histogram` (20 bins over the
+    /// combined range) and are drawn semi-transparent, with a legend naming each of them.
+    ///
+    /// # Arguments
+    /// * `a` - A reference to the first vector of `i128` values.
+    /// * `b` - A reference to the second vector of `i128` values.
+    /// * `labels` - The legend labels of `a` and `b`.
+    /// * `filename` - A string slice representing the path where the histogram image
+    ///   will be saved.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with a `TtpError`:
+    /// `Validation` if both vectors are empty, `Plot` if drawing or writing the image fails.
+    ///
+    /// # Example
+    /// ```
+    /// let before = vec![10, 20, 20, 30];
+    /// let after = vec![15, 15, 25, 35];
+    /// Statistics::plot_histogram_compare(&before, &after, ("before", "after"), "output/compare.png").unwrap();
+    /// ```
+    pub fn plot_histogram_compare(a: &[i128], b: &[i128], labels: (&str, &str), filename: &str) -> Result<(), TtpError> {
+        let (Some(&min), Some(&max)) = (a.iter().chain(b).min(), a.iter().chain(b).max()) else {
+            return Err(TtpError::Validation("Cannot plot a histogram of an empty data set".to_string()));
+        };
+
+        let root = BitMapBackend::new(filename, (1280, 720))
+            .into_drawing_area();
+        root.fill(&WHITE).map_err(plot_error)?;
+
+        let step = Statistics::histogram_step(min, max);
+        let counts_a = Statistics::histogram_counts(a, min, step);
+        let counts_b = Statistics::histogram_counts(b, min, step);
+
+        let y_max = counts_a.iter().chain(&counts_b).max().cloned().unwrap_or(0) + 5;
+
+        let mut chart = ChartBuilder::on(&root)
+            .caption("Distance Distribution", ("sans-serif", 40))
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(40)
+            .build_cartesian_2d(Statistics::histogram_x_range(min, max), 0..y_max)
+            .map_err(plot_error)?;
+
+        chart.configure_mesh().draw().map_err(plot_error)?;
+
+        for (counts, label, color) in [(&counts_a, labels.0, BLUE), (&counts_b, labels.1, RED)] {
+            chart.draw_series(counts.iter().enumerate().map(|(bin, &count)| {
+                let start = min + (bin as i128) * step;
+                Rectangle::new([(start, 0), (start + step, count)], color.mix(0.4).filled())
+            }))
+            .map_err(plot_error)?
+            .label(label)
+            .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.mix(0.4).filled()));
+        }
+
+        chart.configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()
+            .map_err(plot_error)?;
+
+        root.present().map_err(plot_error)
+    }
+
+    /// Number of bins of the histograms.
+    const HISTOGRAM_BINS: i128 = 20;
+
+    /// Returns the bin width of a histogram covering `min..max` (at least `1`).
+    fn histogram_step(min: i128, max: i128) -> i128 {
+        ((max - min) / Statistics::HISTOGRAM_BINS).max(1)
+    }
+
+    /// Returns the x-range of a histogram of values between `min` and `max`.
+    ///
+    /// If all values are equal the range `min..max` would be empty, so it is widened to
+    /// `min - 1..max + 1`, where the single bin `[min, min + 1)` is visible.
+    fn histogram_x_range(min: i128, max: i128) -> std::ops::Range<i128> {
+        if min == max { min - 1..max + 1 } else { min..max }
+    }
+
+    /// Counts the values falling into each bin `[min + b * step, min + (b + 1) * step)`.
+    fn histogram_counts(data: &[i128], min: i128, step: i128) -> Vec<i128> {
+        (0..Statistics::HISTOGRAM_BINS)
+            .map(|b| {
+                let start = min + b * step;
+                let end = start + step;
+                data.iter().filter(|&&v| v >= start && v < end).count() as i128
+            })
+            .collect()
     }
 
     /// Computes and logs statistical summaries of a vector of distances.
     ///
+    /// The histogram is saved as `filename` (`dist_histogram.png` by default) in `output_dir`, which is created
+    /// if it does not exist. With `feasible`, its bins are split by feasibility (see `plot_histogram`).
+    ///
     /// # Arguments
     /// * `distances` - A reference to a vector of `i128` values representing distances.
+    /// * `feasible` - The optional feasibility of each distance, in the same order.
+    /// * `output_dir` - A string slice representing the directory where the histogram is saved.
+    /// * `filename` - The file name of the histogram inside `output_dir`.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with a `TtpError` if
+    /// `output_dir` cannot be created or the histogram cannot be saved.
+    ///
+    /// # Example
+    /// ```
+    /// let distances = vec![10, 20, 30, 40, 50];
+    /// Statistics::generate_statistics(&distances, None, "output", "dist_histogram.png").unwrap();
+    /// ```
+    pub fn generate_statistics(distances: &[i128], feasible: Option<&[bool]>, output_dir: &str, filename: &str) -> Result<(), TtpError> {
+        fs::create_dir_all(output_dir)?;
+
+        Statistics::log_statistics(distances);
+        Statistics::plot_histogram(distances, feasible, &format!("{}/{}", output_dir, filename))
+    }
+
+    /// Computes and logs statistical summaries of a named vector of values.
+    ///
+    /// Works as `generate_statistics` but for any metric collected over the pool
+    /// (e.g. breaks or trip lengths), with its own histogram file.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the metric, used in the log and in the histogram caption.
+    /// * `data` - A reference to a vector of `i128` values.
+    /// * `output_dir` - A string slice representing the directory where the histogram is saved.
+    /// * `filename` - The file name of the histogram image inside `output_dir`.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with a `TtpError` if
+    /// `output_dir` cannot be created or the histogram cannot be saved.
+    ///
+    /// # Example
+    /// ```
+    /// let breaks = vec![12, 14, 14, 16];
+    /// Statistics::generate_named_statistics("Breaks", &breaks, "output", "breaks_histogram.png").unwrap();
+    /// ```
+    pub fn generate_named_statistics(name: &str, data: &[i128], output_dir: &str, filename: &str) -> Result<(), TtpError> {
+        fs::create_dir_all(output_dir)?;
+
+        info!("{} statistics:", name);
+        Statistics::log_statistics(data);
+        Statistics::plot_histogram_with_caption(
+            data,
+            &format!("{} Distribution", name),
+            &format!("{}/{}", output_dir, filename),
+        )
+    }
+
+    /// Logs and saves the pool-level statistics of the violations of each constraint family.
+    ///
+    /// For each family, the mean, median and maximum number of violations per solution are
+    /// reported, with the fraction of solutions satisfying the family (no violation). The
+    /// table is also saved as `constraint_summary.md` in `output_dir`.
+    ///
+    /// # Arguments
+    /// * `per_family` - The violations of each solution by family, as returned by
+    ///   `Solution::analyze_constraints`. Every vector must be non-empty.
+    /// * `output_dir` - A string slice representing the directory where the table is saved.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with a `TtpError` if the
+    /// file cannot be written.
+    ///
+    /// # Example
+    /// ```
+    /// let per_family = Solution::analyze_constraints(&data, &EvaluationOptions::default(), &solutions);
+    /// Statistics::generate_constraint_statistics(&per_family, "output").unwrap();
+    /// ```
+    /// Example output:
+    /// ```text
+    /// | Family | Mean | Median | Max | Satisfied |
+    /// |---|---:|---:|---:|---:|
+    /// | Capacity | 1.25 | 1.00 | 4 | 37.50% |
+    /// ```
+    pub fn generate_constraint_statistics(per_family: &[(ConstraintFamily, Vec<i128>)], output_dir: &str) -> Result<(), TtpError> {
+        let mut output = String::from("| Family | Mean | Median | Max | Satisfied |\n|---|---:|---:|---:|---:|\n");
+
+        for (family, violations) in per_family {
+            let summary = ConstraintSummary::from_violations(*family, violations);
+            let max = summary.max;
+            let (mean, median, satisfied) = (
+                Statistics::format_float(summary.mean),
+                Statistics::format_float(summary.median),
+                Statistics::format_float(summary.satisfied * 100.0),
+            );
+
+            info!(
+                "{} violations | Mean: {} | Median: {} | Max: {} | Satisfied: {}%",
+                family,
+                mean,
+                median,
+                max,
+                satisfied
+            );
+            output.push_str(&format!("| {} | {} | {} | {} | {}% |\n", family, mean, median, max, satisfied));
+        }
+
+        fs::create_dir_all(output_dir)?;
+        let path = format!("{}/constraint_summary.md", output_dir);
+        fs::write(&path, output)?;
+        info!("Constraint statistics table saved to {}", path);
+        Ok(())
+    }
+
+    /// Computes the summary statistics of the values of each group.
+    ///
+    /// The values are grouped by their key (e.g. the direction or the fixed team of the
+    /// construction), so the groups can be compared with each other.
+    ///
+    /// # Arguments
+    /// * `values` - A reference to a vector of `i128` values.
+    /// * `keys` - The group of each value, in the same order as `values`.
+    ///
+    /// # Returns
+    /// A `HashMap` from each key to the `StatsSummary` of its values.
+    ///
+    /// # Panics
+    /// Panics if `values` and `keys` have different lengths.
+    ///
+    /// # Example
+    /// ```
+    /// let distances = vec![10, 20, 30, 40];
+    /// let upward = vec![true, true, false, false];
+    /// let groups = Statistics::summary_over_groups(&distances, &upward);
+    /// assert_eq!(groups[&true].mean, 15.0);
+    /// ```
+    pub fn summary_over_groups<K: Eq + Hash + Clone>(values: &[i128], keys: &[K]) -> HashMap<K, StatsSummary> {
+        assert_eq!(values.len(), keys.len(), "every value needs a group key");
+
+        let mut groups: HashMap<K, Vec<i128>> = HashMap::new();
+        for (value, key) in values.iter().zip(keys) {
+            groups.entry(key.clone()).or_default().push(*value);
+        }
+
+        groups
+            .into_iter()
+            .map(|(key, group)| (key, StatsSummary::from_data(&group)))
+            .collect()
+    }
+
+    /// Logs the summary of each group computed by `summary_over_groups`, ordered by key.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the grouping, used as a prefix of each line.
+    /// * `groups` - A reference to the summaries of the groups.
+    ///
+    /// # Example
+    /// ```
+    /// let groups = Statistics::summary_over_groups(&distances, &fixed_teams);
+    /// Statistics::log_groups("Fixed team", &groups);
+    /// ```
+    pub fn log_groups<K: Ord + Hash + Display>(name: &str, groups: &HashMap<K, StatsSummary>) {
+        let mut keys: Vec<&K> = groups.keys().collect();
+        keys.sort();
+
+        for key in keys {
+            let summary = &groups[key];
+            info!(
+                "{} {} | Solutions: {} | Mean: {} | Median: {} | Std Dev: {} | Min-Max: {:?}",
+                name,
+                key,
+                summary.count,
+                Statistics::format_float(summary.mean),
+                Statistics::format_float(summary.median),
+                Statistics::format_float(summary.std_dev),
+                (summary.min, summary.max)
+            );
+        }
+    }
+
+    /// Writes the summary statistics of a vector of distances as a table file.
+    ///
+    /// The table is saved in `output_dir` (created if it does not exist) as
+    /// `stats_summary.md` for `"markdown"` and `stats_summary.tex` for `"latex"`.
+    ///
+    /// # Arguments
+    /// * `distances` - A reference to a non-empty vector of `i128` values.
+    /// * `format` - The table format, `"markdown"` or `"latex"`.
+    /// * `output_dir` - A string slice representing the directory where the table is saved.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with a `TtpError` if the
+    /// format is unknown or the file cannot be written.
+    ///
+    /// # Example
+    /// ```
+    /// let distances = vec![10, 20, 30, 40, 50];
+    /// Statistics::save_summary_table(&distances, "markdown", "output").unwrap();
+    /// ```
+    pub fn save_summary_table(distances: &[i128], format: &str, output_dir: &str) -> Result<(), TtpError> {
+        let summary = StatsSummary::from_data(distances);
+        let (content, extension) = match format {
+            "markdown" => (summary.to_markdown(), "md"),
+            "latex" => (summary.to_latex(), "tex"),
+            _ => return Err(TtpError::Validation(format!("unknown statistics output format '{}'", format))),
+        };
+
+        fs::create_dir_all(output_dir)?;
+        let path = format!("{}/stats_summary.{}", output_dir, extension);
+        fs::write(&path, content)?;
+        info!("Statistics table saved to {}", path);
+        Ok(())
+    }
+
+    /// Logs the quick summary of a vector of distances: count, feasible count, min, max and mean.
+    ///
+    /// This is the cheapest statistics path: the values are visited once, nothing is sorted
+    /// and no histogram is drawn.
+    ///
+    /// # Arguments
+    /// * `distances` - A reference to a non-empty vector of `i128` values representing distances.
+    /// * `feasible_count` - The number of feasible solutions, `None` if it was not computed.
     ///
     /// # Example
     /// ```
     /// let distances = vec![10, 20, 30, 40, 50];
-    /// Statistics::generate_statistics(&distances);
+    /// Statistics::generate_count_statistics(&distances, Some(4));
     /// ```
-    pub fn generate_statistics(distances: &Vec<i128>) {
+    pub fn generate_count_statistics(distances: &[i128], feasible_count: Option<usize>) {
+        let (count, min_max, mean) = Statistics::count_summary(distances);
+
+        info!("Count: {}", count);
+        match feasible_count {
+            Some(count) => info!("Feasible: {}", count),
+            None => info!("Feasible: not computed"),
+        }
+        info!("Min-Max: {:?}", min_max);
+        info!("Mean: {}", Statistics::format_float(mean));
+    }
 
-        info!("Mean: {}", Statistics::mean(&distances));
-        info!("Median: {}", Statistics::median(&distances));
-        info!("Variance: {}", Statistics::variance(&distances));
-        info!("Std Dev: {}", Statistics::std_dev(&distances));
-        info!("Min-Max: {:?}", Statistics::min_max(&distances));
-        info!("Quartiles: {:?}", Statistics::quartiles(&distances));
+    /// Computes the count, min-max and mean logged by `generate_count_statistics`, in one pass.
+    fn count_summary(distances: &[i128]) -> (usize, (i128, i128), f64) {
+        let mut sum: i128 = 0;
+        let mut min = i128::MAX;
+        let mut max = i128::MIN;
+        for distance in distances {
+            sum += distance;
+            min = min.min(*distance);
+            max = max.max(*distance);
+        }
 
-        Statistics::plot_histogram(&distances, "dist_histogram.png");
+        (distances.len(), (min, max), sum as f64 / distances.len() as f64)
     }
 
-}
\ No newline at end of file
+    /// Computes and logs the streaming statistical summaries of a vector of distances.
+    ///
+    /// Values are accumulated one by one in a `RunningStats`, so only the mean, variance,
+    /// std dev and min-max are reported. The median, quartiles, outliers and histogram
+    /// are not available in this mode.
+    ///
+    /// # Arguments
+    /// * `distances` - A reference to a vector of `i128` values representing distances.
+    ///
+    /// # Example
+    /// ```
+    /// let distances = vec![10, 20, 30, 40, 50];
+    /// Statistics::generate_running_statistics(&distances);
+    /// ```
+    pub fn generate_running_statistics(distances: &[i128]) {
+        let mut stats = RunningStats::new();
+        for distance in distances {
+            stats.push(*distance);
+        }
+
+        info!("Values: {}", stats.count());
+        info!("Mean: {}", Statistics::format_float(stats.mean()));
+        info!("Variance: {}", Statistics::format_float(stats.variance()));
+        info!("Std Dev: {}", Statistics::format_float(stats.std_dev()));
+        info!("Min-Max: {:?}", stats.min_max());
+        info!("Median and quartiles are unavailable with approximate (streaming) statistics");
+    }
+
+    /// Logs the statistical summaries (mean, median, variance, std dev, min-max, quartiles) of a vector,
+    /// followed by the number of outliers and the five most extreme ones.
+    ///
+    /// # Arguments
+    /// * `data` - A reference to a vector of `i128` values.
+    fn log_statistics(data: &[i128]) {
+        let (q1, q2, q3) = Statistics::quartiles(data);
+        info!("Mean: {}", Statistics::format_float(Statistics::mean(data)));
+        info!("Median: {}", Statistics::format_float(Statistics::median(data)));
+        info!("Variance: {}", Statistics::format_float(Statistics::variance(data)));
+        info!("Std Dev: {}", Statistics::format_float(Statistics::std_dev(data)));
+        info!("Coefficient of variation: {}", Statistics::format_float(Statistics::coefficient_of_variation(data)));
+        info!("Min-Max: {:?}", Statistics::min_max(data));
+        info!(
+            "Quartiles: ({}, {}, {})",
+            Statistics::format_float(q1),
+            Statistics::format_float(q2),
+            Statistics::format_float(q3)
+        );
+
+        let mut outliers = Statistics::flag_outliers(data);
+        info!("Outliers: {}", outliers.len());
+        if !outliers.is_empty() {
+            let median = Statistics::median(data);
+            outliers.sort_by(|a, b| (b.1 as f64 - median).abs().total_cmp(&(a.1 as f64 - median).abs()));
+            info!("Most extreme outliers (index, value): {:?}", &outliers[..outliers.len().min(5)]);
+        }
+    }
+
+    /// Combines the distance statistics of several runs into one summary (`merge` subcommand).
+    ///
+    /// If every run kept its distances (see `RunResult::distances`), they are concatenated and
+    /// summarized with `StatsSummary::from_data`, so the result is the summary of the whole pool.
+    /// Otherwise the summaries are combined from their moments: the count, mean, variance,
+    /// standard deviation, minimum and maximum are still exact, while the median and quartiles
+    /// are the count-weighted means of those of the runs (an approximation). Runs without
+    /// statistics (empty pools) are skipped.
+    ///
+    /// # Arguments
+    /// * `results` - A slice of the `RunResult` of each run.
+    ///
+    /// # Returns
+    /// A `Result` with the `StatsSummary` of the distances of all the runs, or a
+    /// `TtpError::Validation` if no run has statistics.
+    ///
+    /// # Example
+    /// ```
+    /// let runs = vec![RunResult::load("a.json").unwrap(), RunResult::load("b.json").unwrap()];
+    /// let merged = Statistics::merge_runs(&runs).unwrap();
+    /// println!("{} solutions, mean distance {}", merged.count, merged.mean);
+    /// ```
+    pub fn merge_runs(results: &[RunResult]) -> Result<StatsSummary, TtpError> {
+        let summaries: Vec<&StatsSummary> = results.iter().filter_map(|result| result.statistics.as_ref()).collect();
+        if summaries.is_empty() {
+            return Err(TtpError::Validation(format!("None of the {} runs has statistics to merge", results.len())));
+        }
+
+        if results
+            .iter()
+            .all(|result| result.statistics.as_ref().is_none_or(|summary| result.distances.len() == summary.count))
+        {
+            let distances: Vec<i128> = results.iter().flat_map(|result| result.distances.iter().copied()).collect();
+            return Ok(StatsSummary::from_data(&distances));
+        }
+
+        let count: usize = summaries.iter().map(|summary| summary.count).sum();
+        let weighted = |value: fn(&StatsSummary) -> f64| {
+            summaries.iter().map(|summary| value(summary) * summary.count as f64).sum::<f64>() / count as f64
+        };
+        let mean = weighted(|summary| summary.mean);
+        // The population variance of the union is the mean of the second moments minus the squared mean
+        let variance = (weighted(|summary| summary.variance + summary.mean * summary.mean) - mean * mean).max(0.0);
+
+        Ok(StatsSummary {
+            count,
+            mean,
+            median: weighted(|summary| summary.median),
+            variance,
+            std_dev: variance.sqrt(),
+            min: summaries.iter().map(|summary| summary.min).min().unwrap(),
+            max: summaries.iter().map(|summary| summary.max).max().unwrap(),
+            q1: weighted(|summary| summary.q1),
+            q3: weighted(|summary| summary.q3),
+        })
+    }
+
+    /// Sets the number of decimal places of the formatted statistics (`--stats-precision`).
+    ///
+    /// Only the presentation changes (log, tables and comparison csv): the computed values
+    /// keep their full precision. The precision applies to the current thread, the one
+    /// running the framework.
+    ///
+    /// # Arguments
+    /// * `precision` - The number of decimal places.
+    ///
+    /// # Example
+    /// ```
+    /// Statistics::set_precision(4);
+    /// assert_eq!(Statistics::format_float(2.0 / 3.0), "0.6667");
+    /// ```
+    pub fn set_precision(precision: usize) {
+        PRECISION.with(|current| current.set(precision));
+    }
+
+    /// Formats a statistic with the number of decimal places set by `set_precision`
+    /// (`DEFAULT_PRECISION` otherwise).
+    ///
+    /// # Arguments
+    /// * `value` - The value to format.
+    ///
+    /// # Returns
+    /// A `String` with the rounded value.
+    ///
+    /// # Example
+    /// ```
+    /// assert_eq!(Statistics::format_float(15.0), "15.00");
+    /// ```
+    pub fn format_float(value: f64) -> String {
+        format!("{:.*}", PRECISION.with(Cell::get), value)
+    }
+}
+
+/// Converts a drawing error of `plotters` into a `TtpError::Plot`.
+fn plot_error<E: std::fmt::Display>(error: E) -> TtpError {
+    TtpError::Plot(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_path;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::path::Path;
+    use std::time::Instant;
+
+    /// The quartiles as computed before sorting once: `median` copies and sorts each half again.
+    fn reference_quartiles(data: &[i128]) -> (f64, f64, f64) {
+        let mut sorted = data.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+
+        let q2 = Statistics::median(&sorted);
+        let q1 = Statistics::median(&sorted[..n / 2]);
+        let q3 = Statistics::median(&sorted[n.div_ceil(2)..]);
+
+        (q1, q2, q3)
+    }
+
+    fn random_values(len: usize, seed: u64) -> Vec<i128> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..len).map(|_| rng.random_range(0..100_000)).collect()
+    }
+
+    #[test]
+    fn quartiles_match_the_reference_implementation() {
+        for len in 2..50 {
+            let values = random_values(len, len as u64);
+            assert_eq!(Statistics::quartiles(&values), reference_quartiles(&values), "{} values", len);
+        }
+        assert_eq!(Statistics::quartiles(&[7]), (7.0, 7.0, 7.0));
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with cargo test --release -- --ignored --nocapture"]
+    fn benchmark_quartiles() {
+        let values = random_values(1_000_000, 42);
+        // Best of 5 runs, to leave out the warm-up of the allocator
+        let best_of = |quartiles: fn(&[i128]) -> (f64, f64, f64)| {
+            (0..5)
+                .map(|_| {
+                    let started = Instant::now();
+                    let result = quartiles(&values);
+                    (started.elapsed(), result)
+                })
+                .min_by_key(|(elapsed, _)| *elapsed)
+                .unwrap()
+        };
+
+        let (elapsed, sorted_once) = best_of(Statistics::quartiles);
+        let (reference_elapsed, reference) = best_of(reference_quartiles);
+
+        assert_eq!(sorted_once, reference);
+        println!(
+            "quartiles, 1M values: {:.3} ms sorting once, {:.3} ms sorting three times",
+            elapsed.as_secs_f64() * 1000.0,
+            reference_elapsed.as_secs_f64() * 1000.0
+        );
+    }
+
+    #[test]
+    fn feasible_only_statistics_leave_out_the_infeasible_distances() {
+        let distances = vec![100_i128, 20, 300, 40, 60];
+        let feasible = vec![false, true, false, true, true];
+        let filtered = Statistics::filter_feasible(&distances, &feasible);
+        assert_eq!(filtered, vec![20, 40, 60]);
+
+        let summary = StatsSummary::from_data(&filtered);
+        assert_eq!(summary.count, 3);
+        assert_eq!(summary.mean, 40.0);
+        assert_eq!(summary.median, 40.0);
+        assert_eq!((summary.min, summary.max), (20, 60));
+    }
+
+    #[test]
+    fn outliers_are_flagged_outside_the_tukey_fences() {
+        let values = vec![10_i128, 12, 11, 13, 12, 11, 10, 12];
+        assert!(Statistics::flag_outliers(&values).is_empty());
+
+        let mut with_outlier = values.clone();
+        with_outlier.insert(3, 100);
+        assert_eq!(Statistics::flag_outliers(&with_outlier), vec![(3, 100)]);
+        assert!(Statistics::flag_outliers(&[5]).is_empty());
+    }
+
+    #[test]
+    fn the_histogram_is_saved_in_the_output_directory() {
+        let directory = temp_path("statistics");
+        let output_dir = format!("{}/run", directory);
+        Statistics::generate_statistics(&[10, 20, 20, 30, 50], None, &output_dir, "dist_histogram.png").unwrap();
+
+        let saved = Path::new(&output_dir).join("dist_histogram.png").is_file();
+        fs::remove_dir_all(&directory).unwrap();
+        assert!(saved);
+    }
+
+    #[test]
+    fn running_statistics_match_the_batch_computation() {
+        let values = random_values(1000, 7);
+        let mut stats = RunningStats::new();
+        for &value in &values {
+            stats.push(value);
+        }
+
+        assert_eq!(stats.count(), values.len());
+        assert!((stats.mean() - Statistics::mean(&values)).abs() < 1e-6);
+        assert!((stats.variance() - Statistics::variance(&values)).abs() / Statistics::variance(&values) < 1e-9);
+        assert!((stats.std_dev() - Statistics::std_dev(&values)).abs() < 1e-6);
+        assert_eq!(stats.min_max(), (*values.iter().min().unwrap(), *values.iter().max().unwrap()));
+    }
+
+    #[test]
+    fn the_overlay_histogram_of_two_pools_is_saved() {
+        let directory = temp_path("overlay");
+        fs::create_dir_all(&directory).unwrap();
+        let filename = format!("{}/compare.png", directory);
+        Statistics::plot_histogram_compare(&[10, 20, 20, 30], &[25, 35, 35, 60], ("florian", "circle"), &filename).unwrap();
+
+        let saved = Path::new(&filename).is_file();
+        fs::remove_dir_all(&directory).unwrap();
+        assert!(saved);
+        assert!(Statistics::plot_histogram_compare(&[], &[], ("florian", "circle"), &filename).is_err());
+    }
+
+    #[test]
+    fn the_markdown_table_has_one_row_per_statistic() {
+        let markdown = StatsSummary::from_data(&[10, 20, 30, 40]).to_markdown();
+        let lines: Vec<&str> = markdown.lines().collect();
+
+        assert_eq!(&lines[..2], ["| Statistic | Value |", "|---|---:|"]);
+        let labels: Vec<&str> = lines[2..].iter().map(|line| line.split('|').nth(1).unwrap().trim()).collect();
+        assert_eq!(labels, ["Solutions", "Mean", "Median", "Variance", "Std Dev", "Min", "Max", "Q1", "Q3"]);
+        assert_eq!(lines[2], "| Solutions | 4 |");
+        assert!(lines[3].starts_with("| Mean | 25"));
+        assert_eq!(&lines[7..9], ["| Min | 10 |", "| Max | 40 |"]);
+    }
+
+    #[test]
+    fn the_count_summary_matches_the_full_statistics() {
+        assert_eq!(Statistics::count_summary(&[30, 10, 50, 20]), (4, (10, 50), 27.5));
+
+        let values = random_values(500, 3);
+        let (count, min_max, mean) = Statistics::count_summary(&values);
+        assert_eq!(count, values.len());
+        assert_eq!(min_max, Statistics::min_max(&values));
+        assert!((mean - Statistics::mean(&values)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn the_histogram_split_by_feasibility_is_saved() {
+        let directory = temp_path("feasibility");
+        fs::create_dir_all(&directory).unwrap();
+        let filename = format!("{}/dist_histogram.png", directory);
+        let distances = [10, 20, 20, 30, 50, 50];
+        Statistics::plot_histogram(&distances, Some(&[true, false, true, true, false, false]), &filename).unwrap();
+
+        let saved = Path::new(&filename).is_file();
+        let mismatch = Statistics::plot_histogram(&distances, Some(&[true, false]), &filename);
+        fs::remove_dir_all(&directory).unwrap();
+        assert!(saved);
+        assert!(matches!(mismatch, Err(TtpError::Validation(_))));
+    }
+
+    #[test]
+    fn statistics_are_summarized_per_group() {
+        let values = [10_i128, 40, 20, 60, 30, 50];
+        let keys = ["upward", "downward", "upward", "downward", "upward", "downward"];
+        let groups = Statistics::summary_over_groups(&values, &keys);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups["upward"], StatsSummary::from_data(&[10, 20, 30]));
+        assert_eq!(groups["downward"], StatsSummary::from_data(&[40, 60, 50]));
+        assert_eq!((groups["upward"].count, groups["upward"].mean), (3, 20.0));
+        assert_eq!((groups["downward"].min, groups["downward"].max), (40, 60));
+    }
+
+    #[test]
+    fn formatted_statistics_follow_the_precision() {
+        assert_eq!(Statistics::format_float(2.0 / 3.0), "0.67");
+
+        Statistics::set_precision(4);
+        assert_eq!(Statistics::format_float(2.0 / 3.0), "0.6667");
+        let markdown = StatsSummary::from_data(&[10, 20, 30, 40]).to_markdown();
+        assert!(markdown.contains("| Mean | 25.0000 |"));
+        assert!(markdown.contains("| Std Dev | 11.1803 |"));
+        // Integer statistics are not rounded
+        assert!(markdown.contains("| Min | 10 |"));
+
+        Statistics::set_precision(0);
+        assert_eq!(Statistics::format_float(2.0 / 3.0), "1");
+        Statistics::set_precision(DEFAULT_PRECISION);
+    }
+
+    #[test]
+    fn a_histogram_of_equal_distances_is_still_plotted() {
+        assert_eq!(Statistics::histogram_x_range(42, 42), 41..43);
+        assert_eq!(Statistics::histogram_counts(&[42; 5], 42, Statistics::histogram_step(42, 42))[0], 5);
+
+        let filename = temp_path("equal_histogram.png");
+        Statistics::plot_histogram(&[42; 5], None, &filename).unwrap();
+        let image = fs::read(&filename).unwrap();
+        fs::remove_file(&filename).unwrap();
+        assert!(image.starts_with(b"\x89PNG"));
+    }
+
+    #[test]
+    fn the_coefficient_of_variation_does_not_depend_on_the_scale() {
+        // Mean 5 and standard deviation 2
+        let values = vec![2_i128, 4, 4, 4, 5, 5, 7, 9];
+        let scaled: Vec<i128> = values.iter().map(|value| value * 1000).collect();
+        assert_eq!(Statistics::coefficient_of_variation(&values), 0.4);
+        assert!((Statistics::coefficient_of_variation(&scaled) - 0.4).abs() < 1e-12);
+
+        assert_eq!(Statistics::coefficient_of_variation(&[42, 42, 42]), 0.0);
+        assert!(Statistics::coefficient_of_variation(&[-1, 1]).is_nan());
+    }
+}
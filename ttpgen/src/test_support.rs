@@ -0,0 +1,73 @@
+// Std library
+use std::cell::RefCell;
+use std::env;
+use std::process;
+use std::sync::Once;
+
+// External crates
+use log::{LevelFilter, Log, Metadata, Record};
+
+// Local modules
+use crate::logging;
+
+thread_local! {
+    /// Messages logged on the current thread since the last `captured_logs`.
+    static CAPTURED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Logger keeping the messages of each thread, muted ones excepted (see `logging::quietly`).
+///
+/// Warnings and errors are counted as by the framework logger (see `logging::warning_count`).
+struct CapturingLogger;
+
+impl Log for CapturingLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        logging::count_warning(record.level());
+        if !logging::is_muted(record.level()) {
+            CAPTURED.with(|captured| captured.borrow_mut().push(record.args().to_string()));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Returns a path in the temporary directory for the files of a test.
+///
+/// The name is prefixed with the id of the test process, so concurrent test runs do not
+/// share their files.
+///
+/// # Arguments
+/// * `name` - The name of the file or directory, unique among the tests.
+///
+/// # Returns
+/// The path (`String`), such as `/tmp/ttpgen_1234_append.csv` for `append.csv`.
+pub fn temp_path(name: &str) -> String {
+    env::temp_dir().join(format!("ttpgen_{}_{}", process::id(), name)).to_string_lossy().into_owned()
+}
+
+/// Runs a closure and returns the messages it logged on the current thread.
+///
+/// The first call installs the capturing logger for the whole test process, the messages
+/// of the other threads are kept apart.
+///
+/// # Arguments
+/// * `f` - The closure to run.
+///
+/// # Returns
+/// The messages (`Vec<String>`) in the order they were logged, without their level.
+pub fn captured_logs(f: impl FnOnce()) -> Vec<String> {
+    static LOGGER: CapturingLogger = CapturingLogger;
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(LevelFilter::Info);
+    });
+
+    CAPTURED.with(|captured| captured.borrow_mut().clear());
+    f();
+    CAPTURED.with(|captured| captured.take())
+}
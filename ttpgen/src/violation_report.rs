@@ -0,0 +1,158 @@
+// Std library
+use std::fmt;
+use std::str::FromStr;
+
+// External crates
+use serde::{Deserialize, Serialize};
+
+/// Family of the constraint broken by a violation.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ConstraintFamily {
+    Capacity,
+    Separation,
+    RoundRobin,
+}
+
+impl ConstraintFamily {
+    /// Every constraint family, in report order.
+    pub const ALL: [ConstraintFamily; 3] = [
+        ConstraintFamily::Capacity,
+        ConstraintFamily::Separation,
+        ConstraintFamily::RoundRobin,
+    ];
+}
+
+impl fmt::Display for ConstraintFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ConstraintFamily::Capacity => "Capacity",
+            ConstraintFamily::Separation => "Separation",
+            ConstraintFamily::RoundRobin => "Round-robin",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for ConstraintFamily {
+    type Err = String;
+
+    /// Parses the RobinX family code (`CA`, `SE`) or the name of a family, case insensitive.
+    /// The round-robin check is `RR`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.to_ascii_lowercase().as_str() {
+            "ca" | "capacity" => Ok(ConstraintFamily::Capacity),
+            "se" | "separation" => Ok(ConstraintFamily::Separation),
+            "rr" | "round-robin" => Ok(ConstraintFamily::RoundRobin),
+            other => Err(format!("Unknown constraint family '{}' (expected CA, SE or RR)", other)),
+        }
+    }
+}
+
+/// A single constraint violation found in a solution.
+///
+/// # Fields
+/// * `family` - The family of the broken constraint.
+/// * `constraint` - Position of the constraint in the XML file (see `Rawdata::constraint_by_index`,
+///   `0` for round-robin).
+/// * `teams` - Indexes of the teams involved (one team for capacity, a pair otherwise).
+/// * `slot` - The slot where the violation starts (the start of the window for capacity,
+///   the second meeting for separation, `None` for round-robin).
+/// * `value` - The observed value: game count for capacity, gap for separation and
+///   number of meetings of the pair for round-robin.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Violation {
+    pub family: ConstraintFamily,
+    pub constraint: usize,
+    pub teams: Vec<usize>,
+    pub slot: Option<usize>,
+    pub value: i32,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let teams = self
+            .teams
+            .iter()
+            .map(|team| team.to_string())
+            .collect::<Vec<_>>()
+            .join(" vs ");
+
+        match self.family {
+            ConstraintFamily::Capacity if self.slot.is_none() => write!(
+                f,
+                "{} constraint #{}: {} games between the team groups",
+                self.family, self.constraint, self.value
+            ),
+            ConstraintFamily::Capacity => write!(
+                f,
+                "{} constraint #{}: team {} has {} games in the window starting at slot {}",
+                self.family,
+                self.constraint,
+                teams,
+                self.value,
+                self.slot.unwrap_or_default()
+            ),
+            ConstraintFamily::Separation => write!(
+                f,
+                "{} constraint #{}: teams {} meet again at slot {} after {} slots",
+                self.family,
+                self.constraint,
+                teams,
+                self.slot.unwrap_or_default(),
+                self.value
+            ),
+            ConstraintFamily::RoundRobin => write!(
+                f,
+                "{}: teams {} meet {} times",
+                self.family, teams, self.value
+            ),
+        }
+    }
+}
+
+/// Detailed list of the constraint violations of a solution.
+///
+/// The counters returned by `Solution::evaluate_solution` are derived from this report,
+/// so both always agree.
+///
+/// # Example
+/// ```
+/// let report = Solution::violation_report(&data, &EvaluationOptions::default(), &solution);
+/// info!("{}", report);
+/// ```
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ViolationReport {
+    pub violations: Vec<Violation>,
+}
+
+impl ViolationReport {
+    /// Counts the violations of a given constraint family.
+    ///
+    /// # Arguments
+    /// * `family` - The `ConstraintFamily` to count.
+    ///
+    /// # Returns
+    /// The number of violations of that family as `i32`.
+    pub fn count(&self, family: ConstraintFamily) -> i32 {
+        self.violations
+            .iter()
+            .filter(|violation| violation.family == family)
+            .count() as i32
+    }
+}
+
+impl fmt::Display for ViolationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Violation report: {} capacity, {} separation, {} round-robin",
+            self.count(ConstraintFamily::Capacity),
+            self.count(ConstraintFamily::Separation),
+            self.count(ConstraintFamily::RoundRobin)
+        )?;
+        for violation in &self.violations {
+            write!(f, "\n  - {}", violation)?;
+        }
+        Ok(())
+    }
+}
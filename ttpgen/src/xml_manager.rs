@@ -1,6 +1,11 @@
-use crate::data_set::{CapacityConstraints, Distance, Rawdata, SeparationConstraints, Slot, Team};
+use crate::data_set::{CapacityConstraints, Distance, Metadata, Rawdata, SeparationConstraints, Slot, Team, ALL_TEAMS_GROUP};
+use crate::error::TtpError;
+use crate::solution::Solution;
+use quick_xml::events::{BytesDecl, BytesText, Event};
+use quick_xml::Writer;
 use roxmltree::Document;
-use std::fs;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
 
 /// Structure responsible for managing XML file reading and parsing.
 pub struct XmlManager;
@@ -15,30 +20,40 @@ impl XmlManager {
     ///
     /// The XML elements are mapped as follows:
     /// - `<InstanceName>` → `Rawdata.instance_name`
-    /// - `<team>` → `Rawdata.teams`
+    /// - `<DataType>`, `<Contributor>`, `<Objective>` → `Rawdata.metadata`
+    /// - `<compactness>` → `Rawdata.metadata.game_mode`, `<gameMode>` (`P`) → `Rawdata.metadata.phased`
+    /// - `<Teams number>`, `<Slots number>` → `Rawdata.declared_teams`, `Rawdata.declared_slots`
+    /// - `<team>` → `Rawdata.teams` (with an optional `venue` attribute, see `Rawdata::venue_of`)
     /// - `<slot>` → `Rawdata.slots`
     /// - `<distance>` → `Rawdata.distances`
-    /// - Elements starting with `"CA"` → `Rawdata.capacity_constraints`
+    /// - Elements starting with `"CA"` → `Rawdata.capacity_constraints` (`CA2` elements are group-pair constraints)
     /// - Elements starting with `"SE"` → `Rawdata.separation_constraints`
+    /// - Any other constraint element (e.g. `"BR1"`, `"FA2"`) → `Rawdata.unsupported_constraints`
+    ///
+    /// Unsupported constraints are not evaluated, their tag names are only collected
+    /// (sorted and without duplicates) so the caller can warn about them or abort.
+    /// Every constraint element, supported or not, is numbered in document order and the
+    /// number is stored in the `index` of the parsed constraints (see `Rawdata::constraint_by_index`).
+    /// Declared counts of teams and slots are checked against the parsed elements
+    /// (see `Rawdata::check_declared_counts`).
     ///
     /// # Arguments
     /// * `path` - A string slice representing the path to the XML file.
     ///
     /// # Returns
-    /// A `Rawdata` struct containing all parsed information from the XML.
-    ///
-    /// # Panics
-    /// This function will panic if the XML file cannot be opened or parsed.
+    /// A `Result` with the `Rawdata` struct containing all parsed information from the XML,
+    /// or a `TtpError` (`Io` if the file cannot be opened, `Xml` if it cannot be parsed,
+    /// `Validation` if a declared count does not match or a constraint has an invalid team group).
     ///
     /// # Example
     /// ```
-    /// let raw_data = read_xml("instances/example.xml");
+    /// let raw_data = read_xml("instances/example.xml").unwrap();
     /// println!("Instance name: {}", raw_data.instance_name);
     /// println!("Number of teams: {}", raw_data.teams.len());
     /// ```
-    pub fn read_xml(path: &str) -> Rawdata {
-        let xml = fs::read_to_string(path).expect("Error opening XML file");
-        let doc = Document::parse(&xml).expect("Error parsing XML");
+    pub fn read_xml(path: &str) -> Result<Rawdata, TtpError> {
+        let xml = fs::read_to_string(path)?;
+        let doc = Document::parse(&xml)?;
 
         let mut raw_data = Rawdata {
             instance_name: String::new(),
@@ -47,8 +62,13 @@ impl XmlManager {
             distances: Vec::new(),
             capacity_constraints: Vec::new(),
             separation_constraints: Vec::new(),
+            unsupported_constraints: Vec::new(),
+            metadata: Metadata::default(),
+            declared_teams: None,
+            declared_slots: None,
         };
 
+        let mut constraint_count = 0;
         for node in doc.descendants().filter(|n| n.is_element()) {
             match node.tag_name().name() {
                 "InstanceName" => {
@@ -56,22 +76,157 @@ impl XmlManager {
                         raw_data.instance_name = text.to_string();
                     }
                 }
+                "DataType" => raw_data.metadata.data_type = Self::node_text(&node),
+                "Contributor" => raw_data.metadata.contributor = Self::node_text(&node),
+                "Objective" => raw_data.metadata.objective = Self::node_text(&node),
+                "compactness" => raw_data.metadata.game_mode = Self::node_text(&node),
+                "gameMode" => raw_data.metadata.phased = Self::node_text(&node).eq_ignore_ascii_case("P"),
+                "Teams" => raw_data.declared_teams = node.attribute("number").and_then(|number| number.trim().parse().ok()),
+                "Slots" => raw_data.declared_slots = node.attribute("number").and_then(|number| number.trim().parse().ok()),
                 "team" => raw_data.teams.push(Self::parse_team(&node)),
                 "slot" => raw_data.slots.push(Self::parse_slot(&node)),
                 "distance" => raw_data.distances.push(Self::parse_distance(&node)),
-                name if name.starts_with("CA") => raw_data.capacity_constraints.push(Self::parse_capacity(&node)),
-                name if name.starts_with("SE") => raw_data.separation_constraints.push(Self::parse_separation(&node)),
+                name if name.starts_with("CA") => {
+                    let mut capacity = Self::parse_capacity(&node, constraint_count)?;
+                    capacity.c_group_pairs = name == "CA2";
+                    constraint_count += 1;
+                    raw_data.capacity_constraints.push(capacity);
+                }
+                name if name.starts_with("SE") => {
+                    let separation = Self::parse_separation(&node, constraint_count)?;
+                    constraint_count += 1;
+                    raw_data.separation_constraints.push(separation);
+                }
+                name if Self::is_constraint(&node) => {
+                    raw_data.unsupported_constraints.push(name.to_string());
+                    constraint_count += 1;
+                }
                 _ => {}
             }
         }
 
-        raw_data
+        raw_data.unsupported_constraints.sort();
+        raw_data.unsupported_constraints.dedup();
+
+        raw_data.check_declared_counts()?;
+
+        Ok(raw_data)
+    }
+
+    /// Writes a solution as a RobinX solution XML file.
+    ///
+    /// The document is streamed to the file by a `quick_xml::Writer` over a `BufWriter`, one
+    /// `<ScheduledMatch>` element at a time, so memory stays bounded whatever the size of the
+    /// schedule. The writer escapes the instance name.
+    /// Each pairing is written once, from the side that plays at home, and byes are omitted:
+    ///
+    /// ```xml
+    /// <Solution>
+    ///     <MetaData>
+    ///         <InstanceName>NL8</InstanceName>
+    ///         <SolutionName>NL8_1</SolutionName>
+    ///         <ObjectiveValue infeasibility="0" objective="39721"/>
+    ///     </MetaData>
+    ///     <Games>
+    ///         <ScheduledMatch home="0" away="1" slot="0"/>
+    ///     </Games>
+    /// </Solution>
+    /// ```
+    ///
+    /// # Arguments
+    /// * `data` - A reference to the `Rawdata` of the instance.
+    /// * `solution` - A reference to the `Solution` to write.
+    /// * `objective` - The objective value (traveling distance) of the solution.
+    /// * `infeasibility` - The number of violations of the solution.
+    /// * `path` - A string slice specifying the file path.
+    ///
+    /// # Returns
+    /// A `Result` indicating success (`Ok(())`) or failure (`Err`) with a `TtpError::Io`
+    /// if the file cannot be created or written.
+    ///
+    /// # Example
+    /// ```
+    /// let report = Solution::violation_report(&raw_data, &EvaluationOptions::default(), &solution);
+    /// XmlManager::write_solution_xml(&raw_data, &solution, 39721, report.violations.len(), "solution_1.xml").unwrap();
+    /// ```
+    pub fn write_solution_xml(
+        data: &Rawdata,
+        solution: &Solution,
+        objective: i128,
+        infeasibility: usize,
+        path: &str,
+    ) -> Result<(), TtpError> {
+        let mut writer = Writer::new_with_indent(BufWriter::new(File::create(path)?), b' ', 4);
+
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        writer.create_element("Solution").write_inner_content(|writer| {
+            writer.create_element("MetaData").write_inner_content(|writer| {
+                writer.create_element("InstanceName").write_text_content(BytesText::new(&data.instance_name))?;
+                writer
+                    .create_element("SolutionName")
+                    .write_text_content(BytesText::new(&format!("{}_{}", data.instance_name, solution.id)))?;
+                writer
+                    .create_element("ObjectiveValue")
+                    .with_attribute(("infeasibility", infeasibility.to_string().as_str()))
+                    .with_attribute(("objective", objective.to_string().as_str()))
+                    .write_empty()?;
+                Ok(())
+            })?;
+
+            writer.create_element("Games").write_inner_content(|writer| {
+                for (slot, row) in solution.solution.iter().enumerate() {
+                    for (team, game) in row.iter().enumerate() {
+                        if game.home_game && game.opponent >= 0 {
+                            writer
+                                .create_element("ScheduledMatch")
+                                .with_attribute(("home", team.to_string().as_str()))
+                                .with_attribute(("away", game.opponent.to_string().as_str()))
+                                .with_attribute(("slot", slot.to_string().as_str()))
+                                .write_empty()?;
+                        }
+                    }
+                }
+                Ok(())
+            })?;
+            Ok(())
+        })?;
+
+        let mut file = writer.into_inner();
+        writeln!(file)?;
+        file.flush()?;
+
+        Ok(())
+    }
+
+    /// Returns the trimmed text of an XML node, or an empty string if it has none.
+    ///
+    /// # Arguments
+    /// * `node` - A reference to a `roxmltree::Node`.
+    fn node_text(node: &roxmltree::Node) -> String {
+        node.text().map(|text| text.trim().to_string()).unwrap_or_default()
+    }
+
+    /// Checks if an XML node is a constraint element.
+    ///
+    /// In RobinX instances, constraints are the children of the constraint family
+    /// elements (`<CapacityConstraints>`, `<BreakConstraints>`, ...) inside `<Constraints>`.
+    ///
+    /// # Arguments
+    /// * `node` - A reference to a `roxmltree::Node`.
+    ///
+    /// # Returns
+    /// `true` if the node is a constraint, `false` otherwise.
+    fn is_constraint(node: &roxmltree::Node) -> bool {
+        node.parent_element()
+            .and_then(|family| family.parent_element())
+            .is_some_and(|constraints| constraints.tag_name().name() == "Constraints")
     }
 
     /// Parses a `<Team>` XML node and converts it into a `Team` struct.
     ///
     /// This function reads the attributes of the given XML node and fills the corresponding
     /// fields in `Team`. If a numeric attribute is missing or cannot be parsed, it defaults to `0`.
+    /// The optional `venue` attribute sets the home venue of the team (`None` if missing or invalid).
     ///
     /// # Arguments
     /// * `node` - A reference to a `roxmltree::Node` representing the `<Team>` element.
@@ -97,6 +252,7 @@ impl XmlManager {
                 "league" => team.league = attr.value().parse().unwrap_or(0),
                 "name" => team.name = attr.value().to_string(),
                 "teamGroups" => team.team_groups = attr.value().parse().unwrap_or(0),
+                "venue" => team.venue = attr.value().trim().parse().ok(),
                 _ => {}
             }
         }
@@ -171,24 +327,29 @@ impl XmlManager {
     ///
     /// This function reads the attributes of the given XML node and fills the corresponding
     /// fields in `CapacityConstraints`. If an attribute is missing or cannot be parsed,
-    /// numeric fields default to `0`.
+    /// numeric fields default to `0`, except the team groups (see `parse_team_group`). The
+    /// optional `slots` attribute is a `;` separated list of slot ids, used by the group-pair
+    /// (`CA2`) evaluation.
     ///
     /// # Arguments
     /// * `node` - A reference to a `roxmltree::Node` representing the `<CapacityConstraints>` element.
+    /// * `index` - The number of the constraint in the file (stored in `index`).
     ///
     /// # Returns
-    /// A `CapacityConstraints` struct populated with the parsed values.
+    /// A `Result` with the `CapacityConstraints` struct populated with the parsed values, or a
+    /// `TtpError::Validation` if a team group is invalid.
     ///
     /// # Example
     /// ```
     /// let doc = roxmltree::Document::parse(r#"<Capacity intp="2" max="5" min="1" mode1="H" mode2="A" penalty="10" teamGroups1="3" teamGroups2="2" type="hard"/>"#).unwrap();
     /// let node = doc.root_element();
-    /// let capacity = parse_capacity(&node);
+    /// let capacity = parse_capacity(&node, 0).unwrap();
     /// assert_eq!(capacity.c_intp, 2);
     /// assert_eq!(capacity.c_type, "hard".to_string());
     /// ```
-    fn parse_capacity(node: &roxmltree::Node) -> CapacityConstraints {
+    fn parse_capacity(node: &roxmltree::Node, index: usize) -> Result<CapacityConstraints, TtpError> {
         let mut cap = CapacityConstraints::new();
+        cap.index = index;
         for attr in node.attributes() {
             match attr.name() {
                 "intp" => cap.c_intp = attr.value().parse().unwrap_or(0),
@@ -197,48 +358,312 @@ impl XmlManager {
                 "mode1" => cap.c_mode1 = attr.value().chars().next().unwrap_or('n'),
                 "mode2" => cap.c_mode2 = attr.value().to_string(),
                 "penalty" => cap.c_penalty = attr.value().parse().unwrap_or(0),
-                "teamGroups1" => cap.c_team_groups1 = attr.value().parse().unwrap_or(0),
-                "teamGroups2" => cap.c_team_groups2 = attr.value().parse().unwrap_or(0),
+                "teamGroups1" => cap.c_team_groups1 = Self::parse_team_group(node, index, &attr)?,
+                "teamGroups2" => cap.c_team_groups2 = Self::parse_team_group(node, index, &attr)?,
+                "slots" => cap.c_slots = Self::parse_slot_list(attr.value()),
                 "type" => cap.c_type = attr.value().to_string(),
                 _ => {}
             }
         }
-        cap
+        Ok(cap)
     }
 
     /// Parses a `<SeparationConstraint>` XML node and converts it into a `SeparationConstraints` struct.
     ///
     /// This function reads the attributes of the given XML node and fills the corresponding
     /// fields in `SeparationConstraints`. If an attribute is missing or cannot be parsed
-    /// as a number, it defaults to `0`.
+    /// as a number, it defaults to `0`, except the team group (see `parse_team_group`). The
+    /// optional `slots` attribute is a `;` separated list of slot ids restricting the
+    /// constraint, all slots when it is missing.
     ///
     /// # Arguments
     /// * `node` - A reference to a `roxmltree::Node` representing the `<SeparationConstraint>` element.
+    /// * `index` - The number of the constraint in the file (stored in `index`).
     ///
     /// # Returns
-    /// A `SeparationConstraints` struct populated with the parsed values.
+    /// A `Result` with the `SeparationConstraints` struct populated with the parsed values, or
+    /// a `TtpError::Validation` if the team group is invalid.
     ///
     /// # Example
     /// ```
-    /// let doc = roxmltree::Document::parse(r#"<Separation max="3" min="1" penalty="5" teamGroups="2" type="soft"/>"#).unwrap();
+    /// let doc = roxmltree::Document::parse(r#"<Separation max="3" min="1" penalty="5" teamGroups="2" slots="0;1;2;3" type="soft"/>"#).unwrap();
     /// let node = doc.root_element();
-    /// let separation = parse_separation(&node);
+    /// let separation = parse_separation(&node, 0).unwrap();
     /// assert_eq!(separation.c_max, 3);
+    /// assert_eq!(separation.c_slots, vec![0, 1, 2, 3]);
     /// assert_eq!(separation.c_type, "soft".to_string());
     /// ```
-    fn parse_separation(node: &roxmltree::Node) -> SeparationConstraints {
+    fn parse_separation(node: &roxmltree::Node, index: usize) -> Result<SeparationConstraints, TtpError> {
         let mut sep = SeparationConstraints::new();
+        sep.index = index;
         for attr in node.attributes() {
             match attr.name() {
                 "max" => sep.c_max = attr.value().parse().unwrap_or(0),
                 "min" => sep.c_min = attr.value().parse().unwrap_or(0),
                 "penalty" => sep.c_penalty = attr.value().parse().unwrap_or(0),
-                "teamGroups" => sep.c_team_groups = attr.value().parse().unwrap_or(0),
+                "teamGroups" => sep.c_team_groups = Self::parse_team_group(node, index, &attr)?,
+                "slots" => sep.c_slots = Self::parse_slot_list(attr.value()),
                 "type" => sep.c_type = attr.value().to_string(),
                 _ => {}
             }
         }
-        sep
+        Ok(sep)
+    }
+
+    /// Parses the team group attribute of a constraint.
+    ///
+    /// An empty (or blank) value and `-1` are read as `ALL_TEAMS_GROUP` (all teams), as is a
+    /// missing attribute (the default of the constraint). Any other value must be a team group
+    /// id (a non-negative number).
+    ///
+    /// # Arguments
+    /// * `node` - A reference to the `roxmltree::Node` of the constraint.
+    /// * `index` - The number of the constraint in the file.
+    /// * `attr` - The team group attribute (`teamGroups`, `teamGroups1` or `teamGroups2`).
+    ///
+    /// # Returns
+    /// A `Result` with the team group, or a `TtpError::Validation` naming the constraint if
+    /// the value is not a team group id.
+    ///
+    /// # Example
+    /// ```
+    /// let doc = roxmltree::Document::parse(r#"<SE1 max="14" min="1" teamGroups="0;1"/>"#).unwrap();
+    /// let node = doc.root_element();
+    /// let attr = node.attributes().find(|attr| attr.name() == "teamGroups").unwrap();
+    /// assert!(parse_team_group(&node, 2, &attr).is_err());
+    /// ```
+    fn parse_team_group(node: &roxmltree::Node, index: usize, attr: &roxmltree::Attribute) -> Result<i32, TtpError> {
+        let value = attr.value().trim();
+        if value.is_empty() {
+            return Ok(ALL_TEAMS_GROUP);
+        }
+        match value.parse::<i32>() {
+            Ok(group) if group >= 0 || group == ALL_TEAMS_GROUP => Ok(group),
+            _ => Err(TtpError::Validation(format!(
+                "Constraint {} #{} has an invalid {} \"{}\" (expected a team group id, or empty or -1 for all teams)",
+                node.tag_name().name(),
+                index,
+                attr.name(),
+                attr.value()
+            ))),
+        }
+    }
+
+    /// Parses a `;` separated list of slot ids, skipping the entries that are not numbers.
+    ///
+    /// # Example
+    /// ```
+    /// assert_eq!(parse_slot_list("0; 2;x;5"), vec![0, 2, 5]);
+    /// ```
+    fn parse_slot_list(value: &str) -> Vec<i32> {
+        value.split(';').filter_map(|slot| slot.trim().parse().ok()).collect()
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{ConstructionMethod, EvaluationOptions};
+    use crate::test_support::temp_path;
+    use crate::violation_report::ConstraintFamily;
+    use std::time::Instant;
+
+    /// Parses NL8 with `from` replaced by `to` in the XML text.
+    fn read_edited(name: &str, from: &str, to: &str) -> Result<Rawdata, TtpError> {
+        let xml = fs::read_to_string("NL8.xml").unwrap();
+        assert!(xml.contains(from), "{} is not in NL8.xml", from);
+        let path = temp_path(name);
+        fs::write(&path, xml.replace(from, to)).unwrap();
+        let raw_data = XmlManager::read_xml(&path);
+        fs::remove_file(&path).unwrap();
+        raw_data
+    }
+
+    /// NL8 widened to `num_teams` teams and a double round-robin of slots.
+    fn widened(num_teams: i32) -> Rawdata {
+        let mut data = XmlManager::read_xml("NL8.xml").unwrap();
+        data.teams = (0..num_teams).map(|id| Team { id, ..data.teams[0].clone() }).collect();
+        data.slots = (0..2 * (num_teams - 1)).map(|id| Slot { id, name: format!("Slot{}", id) }).collect();
+        data
+    }
+
+    #[test]
+    fn solution_xml_lists_each_home_game_and_escapes_the_name() {
+        let mut data = XmlManager::read_xml("NL8.xml").unwrap();
+        data.instance_name = "NL8 <A&B>".to_string();
+        let permutation: Vec<i32> = (0..data.n_teams() as i32).collect();
+        let solution = Solution::regenerate(&data, &[permutation], 1, ConstructionMethod::Florian, None).unwrap();
+        let path = temp_path("solution.xml");
+        XmlManager::write_solution_xml(&data, &solution, 39721, 2, &path).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let document = Document::parse(&content).unwrap();
+        let text = |name: &str| document.descendants().find(|node| node.has_tag_name(name)).and_then(|node| node.text()).unwrap().to_string();
+        assert_eq!(text("InstanceName"), "NL8 <A&B>");
+        assert_eq!(text("SolutionName"), "NL8 <A&B>_1");
+        let objective = document.descendants().find(|node| node.has_tag_name("ObjectiveValue")).unwrap();
+        assert_eq!(objective.attribute("objective"), Some("39721"));
+        assert_eq!(objective.attribute("infeasibility"), Some("2"));
+
+        let mut games: Vec<(usize, usize, usize)> = document
+            .descendants()
+            .filter(|node| node.has_tag_name("ScheduledMatch"))
+            .map(|node| {
+                let attribute = |name: &str| node.attribute(name).unwrap().parse::<usize>().unwrap();
+                (attribute("slot"), attribute("home"), attribute("away"))
+            })
+            .collect();
+        games.sort();
+        let mut expected: Vec<(usize, usize, usize)> = Vec::new();
+        for (slot, row) in solution.solution.iter().enumerate() {
+            for (team, game) in row.iter().enumerate() {
+                if game.home_game {
+                    expected.push((slot, team, game.opponent as usize));
+                }
+            }
+        }
+        assert_eq!(games, expected);
+    }
+
+    #[test]
+    #[ignore = "benchmark, run with cargo test --release -- --ignored --nocapture"]
+    fn benchmark_write_solution_xml() {
+        let data = widened(40);
+        let permutation: Vec<i32> = (0..40).collect();
+        let solution = Solution::regenerate(&data, &[permutation], 1, ConstructionMethod::Circle, None).unwrap();
+        let path = temp_path("benchmark.xml");
+
+        let started = Instant::now();
+        for _ in 0..100 {
+            XmlManager::write_solution_xml(&data, &solution, 0, 0, &path).unwrap();
+        }
+        let elapsed = started.elapsed();
+        let size = fs::metadata(&path).unwrap().len();
+        fs::remove_file(&path).unwrap();
+        println!("write_solution_xml, 40 teams: {:.3} ms per file of {} bytes", elapsed.as_secs_f64() * 10.0, size);
+    }
+
+    #[test]
+    fn unsupported_constraints_are_listed_by_tag() {
+        let raw_data = read_edited(
+            "unsupported.xml",
+            "<BreakConstraints/>",
+            r#"<BreakConstraints><BR1 intp="0" mode2="HOME" penalty="1" slots="0" teams="0" type="SOFT"/></BreakConstraints>"#,
+        )
+        .unwrap();
+
+        assert_eq!(raw_data.unsupported_constraints, vec!["BR1".to_string()]);
+        assert_eq!(raw_data.capacity_constraints.len(), 2);
+        assert_eq!(raw_data.separation_constraints.len(), 1);
+        assert!(XmlManager::read_xml("NL8.xml").unwrap().unsupported_constraints.is_empty());
+    }
+
+    #[test]
+    fn an_empty_or_minus_one_team_group_matches_every_team() {
+        for group in ["-1", "", " "] {
+            let raw_data = read_edited("wildcard.xml", r#"min="1" penalty="1" teamGroups="0""#, &format!(r#"min="1" penalty="1" teamGroups="{}""#, group)).unwrap();
+            assert_eq!(raw_data.separation_constraints[0].c_team_groups, ALL_TEAMS_GROUP);
+            assert_eq!(raw_data.teams_in_group(ALL_TEAMS_GROUP).len(), raw_data.teams.len());
+        }
+    }
+
+    #[test]
+    fn an_invalid_team_group_is_rejected() {
+        for group in ["0;1", "O", "-2"] {
+            let error = read_edited("bad_group.xml", r#"min="1" penalty="1" teamGroups="0""#, &format!(r#"min="1" penalty="1" teamGroups="{}""#, group))
+                .unwrap_err()
+                .to_string();
+            assert_eq!(
+                error,
+                format!("Validation error: Constraint SE1 #2 has an invalid teamGroups \"{}\" (expected a team group id, or empty or -1 for all teams)", group)
+            );
+        }
+
+        let error = read_edited("bad_group1.xml", r#"mode1="A" mode2="GAMES" penalty="1" teamGroups1="0""#, r#"mode1="A" mode2="GAMES" penalty="1" teamGroups1="-2""#)
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("Validation error: Constraint CA3 #1 has an invalid teamGroups1 \"-2\""), "{}", error);
+
+        let raw_data = read_edited("literal_group.xml", r#"min="1" penalty="1" teamGroups="0""#, r#"min="1" penalty="1" teamGroups=" 3 ""#).unwrap();
+        assert_eq!(raw_data.separation_constraints[0].c_team_groups, 3);
+        assert!(raw_data.teams_in_group(3).is_empty());
+    }
+
+    #[test]
+    fn a_separation_constraint_only_checks_the_meetings_in_its_slots() {
+        // With a minimum gap of 14 slots, every pair of a double round-robin of 14 slots is too close
+        let separation = |slots: &str| {
+            let raw_data = read_edited("separation_slots.xml", r#"<SE1 max="14" min="1""#, &format!(r#"<SE1{} max="14" min="14""#, slots)).unwrap();
+            let solution = Solution::regenerate(&raw_data, &[(0..8).collect()], 1, ConstructionMethod::Florian, None).unwrap();
+            let report = Solution::violation_report(&raw_data, &EvaluationOptions::default(), &solution);
+            (raw_data.separation_constraints[0].c_slots.clone(), report.count(ConstraintFamily::Separation))
+        };
+
+        assert_eq!(separation(""), (vec![], 28));
+        // Each pair meets once in the first half, so there is no gap to check
+        assert_eq!(separation(r#" slots="0;1;2;3;4;5;6""#), ((0..7).collect(), 0));
+    }
+
+    #[test]
+    fn declared_counts_are_checked_against_the_listed_elements() {
+        let matching = read_edited("declared.xml", "<Teams>", r#"<Teams number="8">"#).unwrap();
+        assert_eq!(matching.declared_teams, Some(8));
+
+        let Err(TtpError::Validation(message)) = read_edited("declared.xml", "<Teams>", r#"<Teams number="10">"#) else {
+            panic!("10 declared teams should not match the 8 listed");
+        };
+        assert_eq!(message, "instance declares 10 teams but lists 8");
+        let Err(TtpError::Validation(message)) = read_edited("declared.xml", "<Slots>", r#"<Slots number="12">"#) else {
+            panic!("12 declared slots should not match the 14 listed");
+        };
+        assert_eq!(message, "instance declares 12 slots but lists 14");
+    }
+
+    #[test]
+    fn a_ca2_constraint_limits_the_games_between_its_two_groups() {
+        let mut raw_data = read_edited(
+            "ca2.xml",
+            r#"<CA3 intp="4" max="3" min="0" mode1="H" mode2="GAMES" penalty="1" teamGroups1="0" teamGroups2="0""#,
+            r#"<CA2 intp="2" max="1" min="0" mode1="A" mode2="GAMES" penalty="1" slots="0;1" teamGroups1="0" teamGroups2="1""#,
+        )
+        .unwrap();
+        for team in raw_data.teams.iter_mut().filter(|team| team.id % 2 == 1) {
+            team.team_groups = 1;
+        }
+        let constraint = &raw_data.capacity_constraints[0];
+        assert!(constraint.c_group_pairs && !raw_data.capacity_constraints[1].c_group_pairs);
+        assert_eq!((constraint.c_team_groups1, constraint.c_team_groups2, constraint.c_slots.clone()), (0, 1, vec![0, 1]));
+
+        // Games of the first two slots with an even team (group 0) at home against an odd team (group 1)
+        let solution = Solution::regenerate(&raw_data, &[(0..8).collect()], 1, ConstructionMethod::Florian, None).unwrap();
+        let expected = solution.solution[..2]
+            .iter()
+            .flat_map(|row| row.iter().enumerate())
+            .filter(|(team, game)| team % 2 == 0 && game.home_game && game.opponent % 2 == 1)
+            .count();
+        assert_eq!(expected, 5);
+        assert_eq!(solution.cross_group_games(constraint, &raw_data.group_map()), expected);
+
+        // At most one such game is allowed, the single violation records the five games
+        let evaluation = EvaluationOptions { ca2_group_pairs: true, ..Default::default() };
+        let report = Solution::violation_report(&raw_data, &evaluation, &solution);
+        let group_pair_violations: Vec<i32> =
+            report.violations.iter().filter(|violation| violation.constraint == constraint.index).map(|violation| violation.value).collect();
+        assert_eq!(group_pair_violations, vec![5]);
+    }
+
+    #[test]
+    fn negative_and_zero_distances_are_reported() {
+        assert!(XmlManager::read_xml("NL8.xml").unwrap().negative_distance_errors().is_empty());
+
+        let raw_data = read_edited("negative.xml", r#"<distance dist="1020" team1="4" team2="2"/>"#, r#"<distance dist="-1020" team1="4" team2="2"/>"#).unwrap();
+        assert_eq!(raw_data.negative_distance_errors(), vec!["Distance from team 4 to team 2 is negative (-1020)".to_string()]);
+        assert!(raw_data.zero_distance_pairs().is_empty());
+
+        let raw_data = read_edited("zero.xml", r#"<distance dist="1020" team1="4" team2="2"/>"#, r#"<distance dist="0" team1="4" team2="2"/>"#).unwrap();
+        assert!(raw_data.negative_distance_errors().is_empty());
+        assert_eq!(raw_data.zero_distance_pairs(), vec![(4, 2)]);
+    }
+}